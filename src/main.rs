@@ -5,14 +5,14 @@ use crossterm::{
 };
 use ratatui::{
     backend::{Backend, CrosstermBackend},
-    layout::{Constraint, Direction, Layout, Alignment},
+    layout::{Constraint, Direction, Layout, Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Table, Row, Cell, Wrap},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Sparkline, Wrap},
     Frame, Terminal,
 };
 use std::{error::Error, io, time::{Duration, Instant}, fs, io::Write};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
@@ -21,6 +21,7 @@ struct SoundEffects {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[allow(dead_code)] // Not every sound has a trigger yet
 enum SoundType {
     Purchase,       // Successful purchase
     Sale,          // Successful sale/order fulfillment  
@@ -113,42 +114,216 @@ impl SoundEffects {
     }
 }
 
+// Seedable SplitMix64 generator so a run can be reproduced from its seed and
+// stays deterministic across save/load (the state is serialized with the game)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GameRng {
+    seed: u64,
+    state: u64,
+}
+
+impl GameRng {
+    fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    fn from_entropy() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0x5EED);
+        Self::new(nanos)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform float in [0, 1)
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    // Uniform integer in [min, max] (inclusive)
+    fn range(&mut self, min: u32, max: u32) -> u32 {
+        if max <= min {
+            return min;
+        }
+        let span = (max - min) as u64 + 1;
+        min + (self.next_u64() % span) as u32
+    }
+
+    fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as u32 - 1) as usize]
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+// A retailer whose gift cards we trade. `id` is what saves, orders and the event catalog refer to.
+#[derive(Debug)]
+struct Retailer {
+    id: &'static str,
+    name: &'static str,                          // Shown on screen
+    markup: f32,                                 // Default resale value of its cards as a multiple of face value
+    wholesale_rate: f32,                         // Default base wholesale cost as a share of face value
+    seasonal_bonus: &'static [(Season, f32)],    // Price/demand boost by season (1.0 when absent)
+}
+
+// Every retailer in the game and its trading terms; adding one here (plus which cards it sells in
+// CARD_CATALOG) is all it takes
+const RETAILERS: &[Retailer] = &[
+    Retailer { id: "Amazon", name: "Amazon", markup: 1.28, wholesale_rate: 0.80, seasonal_bonus: &[(Season::Fall, 1.2), (Season::Winter, 1.5)] },        // Online shopping, holiday gifts
+    Retailer { id: "Starbucks", name: "Starbucks", markup: 1.22, wholesale_rate: 0.82, seasonal_bonus: &[(Season::Winter, 1.3)] },                      // Holiday coffee gifts
+    Retailer { id: "Target", name: "Target", markup: 1.27, wholesale_rate: 0.84, seasonal_bonus: &[(Season::Summer, 1.2), (Season::Winter, 1.2)] },    // Summer vacation shopping
+    Retailer { id: "iTunes", name: "iTunes", markup: 1.20, wholesale_rate: 0.82, seasonal_bonus: &[(Season::Fall, 1.3), (Season::Winter, 1.4)] },      // Back to school and holiday tech
+    Retailer { id: "Walmart", name: "Walmart", markup: 1.18, wholesale_rate: 0.85, seasonal_bonus: &[(Season::Summer, 1.1), (Season::Winter, 1.2)] },  // General summer demand
+];
+
+impl Retailer {
+    fn seasonal_bonus(&self, season: &Season) -> f32 {
+        self.seasonal_bonus.iter()
+            .find(|(bonus_season, _)| bonus_season == season)
+            .map(|(_, bonus)| *bonus)
+            .unwrap_or(1.0)
+    }
+
+    fn wholesale_cost(&self, denomination: u32) -> u32 {
+        (denomination as f32 * self.wholesale_rate).round() as u32
+    }
+
+    // Card the retailer is best known for, used when a card has to be conjured up (event gifts)
+    fn flagship_product(&self) -> Option<&'static CardProduct> {
+        CARD_CATALOG.iter()
+            .filter(|product| product.retailer == self.id)
+            .max_by_key(|product| product.demand_weight)
+    }
+}
+
+fn retailer(id: &str) -> Option<&'static Retailer> {
+    RETAILERS.iter().find(|retailer| retailer.id == id)
+}
+
+fn retailer_name(id: &str) -> &str {
+    retailer(id).map(|retailer| retailer.name).unwrap_or(id)
+}
+
+// One gift card denomination a retailer sells, with its supply and customer demand.
+// Prices come from the retailer's terms in RETAILERS unless the card overrides them.
+#[derive(Debug)]
+struct CardProduct {
+    retailer: &'static str,                // Id of a RETAILERS entry
+    denomination: u32,
+    cost_override: Option<u32>,            // Wholesale cost before market conditions, if not the retailer's rate
+    markup_override: Option<f32>,          // Resale value as a multiple of face value, if not the retailer's markup
+    demand_weight: u32,                    // Relative share of customer orders asking for this card
+    max_order_quantity: u32,               // Bigger cards sell in smaller batches
+    max_stock: u32,                        // Wholesale supply when fully stocked
+    restock_per_day: f32,
+    volume_tiers: &'static [(u32, f32)],   // (min quantity, discount) for bulk purchases
+}
+
+// Every card the wholesale market carries and customers can order
+const CARD_CATALOG: &[CardProduct] = &[
+    CardProduct { retailer: "Amazon", denomination: 10, cost_override: None, markup_override: Some(1.25), demand_weight: 4, max_order_quantity: 6, max_stock: 60, restock_per_day: 12.0, volume_tiers: &[(10, 0.04), (30, 0.08)] },
+    CardProduct { retailer: "Amazon", denomination: 25, cost_override: None, markup_override: Some(1.30), demand_weight: 5, max_order_quantity: 5, max_stock: 50, restock_per_day: 10.0, volume_tiers: &[(10, 0.05), (25, 0.08), (50, 0.12)] },
+    CardProduct { retailer: "Amazon", denomination: 50, cost_override: Some(41), markup_override: None, demand_weight: 3, max_order_quantity: 4, max_stock: 25, restock_per_day: 5.0, volume_tiers: &[(5, 0.04), (15, 0.08)] },
+    CardProduct { retailer: "Amazon", denomination: 100, cost_override: Some(82), markup_override: Some(1.26), demand_weight: 1, max_order_quantity: 2, max_stock: 10, restock_per_day: 2.0, volume_tiers: &[(5, 0.05)] },
+    CardProduct { retailer: "Starbucks", denomination: 10, cost_override: None, markup_override: Some(1.25), demand_weight: 5, max_order_quantity: 5, max_stock: 30, restock_per_day: 6.0, volume_tiers: &[(10, 0.04), (20, 0.08)] },
+    CardProduct { retailer: "Starbucks", denomination: 25, cost_override: None, markup_override: None, demand_weight: 3, max_order_quantity: 4, max_stock: 20, restock_per_day: 4.0, volume_tiers: &[(10, 0.05)] },
+    CardProduct { retailer: "Starbucks", denomination: 50, cost_override: Some(42), markup_override: Some(1.20), demand_weight: 1, max_order_quantity: 2, max_stock: 10, restock_per_day: 2.0, volume_tiers: &[(5, 0.04)] },
+    CardProduct { retailer: "Target", denomination: 25, cost_override: None, markup_override: Some(1.28), demand_weight: 3, max_order_quantity: 5, max_stock: 30, restock_per_day: 6.0, volume_tiers: &[(10, 0.05), (20, 0.08)] },
+    CardProduct { retailer: "Target", denomination: 50, cost_override: None, markup_override: Some(1.28), demand_weight: 3, max_order_quantity: 5, max_stock: 15, restock_per_day: 3.0, volume_tiers: &[(5, 0.05), (10, 0.10)] },
+    CardProduct { retailer: "Target", denomination: 100, cost_override: None, markup_override: Some(1.25), demand_weight: 1, max_order_quantity: 2, max_stock: 8, restock_per_day: 1.5, volume_tiers: &[(4, 0.05)] },
+    CardProduct { retailer: "iTunes", denomination: 10, cost_override: None, markup_override: Some(1.22), demand_weight: 3, max_order_quantity: 6, max_stock: 40, restock_per_day: 8.0, volume_tiers: &[(10, 0.04), (30, 0.08)] },
+    CardProduct { retailer: "iTunes", denomination: 15, cost_override: None, markup_override: Some(1.22), demand_weight: 4, max_order_quantity: 5, max_stock: 25, restock_per_day: 5.0, volume_tiers: &[(10, 0.05), (25, 0.09)] },
+    CardProduct { retailer: "iTunes", denomination: 25, cost_override: None, markup_override: None, demand_weight: 3, max_order_quantity: 4, max_stock: 20, restock_per_day: 4.0, volume_tiers: &[(10, 0.05)] },
+    CardProduct { retailer: "iTunes", denomination: 50, cost_override: Some(42), markup_override: Some(1.18), demand_weight: 1, max_order_quantity: 2, max_stock: 10, restock_per_day: 2.0, volume_tiers: &[(5, 0.04)] },
+    CardProduct { retailer: "Walmart", denomination: 10, cost_override: None, markup_override: Some(1.20), demand_weight: 3, max_order_quantity: 6, max_stock: 40, restock_per_day: 8.0, volume_tiers: &[(10, 0.04), (30, 0.08)] },
+    CardProduct { retailer: "Walmart", denomination: 20, cost_override: None, markup_override: Some(1.20), demand_weight: 4, max_order_quantity: 5, max_stock: 40, restock_per_day: 8.0, volume_tiers: &[(10, 0.05), (30, 0.10)] },
+    CardProduct { retailer: "Walmart", denomination: 50, cost_override: Some(42), markup_override: None, demand_weight: 2, max_order_quantity: 3, max_stock: 15, restock_per_day: 3.0, volume_tiers: &[(5, 0.05)] },
+    CardProduct { retailer: "Walmart", denomination: 100, cost_override: None, markup_override: Some(1.16), demand_weight: 1, max_order_quantity: 2, max_stock: 8, restock_per_day: 1.5, volume_tiers: &[(4, 0.05)] },
+];
+
+impl CardProduct {
+    // Wholesale cost before market conditions
+    fn base_cost(&self) -> u32 {
+        self.cost_override.unwrap_or_else(|| {
+            retailer(self.retailer).map_or(self.denomination, |retailer| retailer.wholesale_cost(self.denomination))
+        })
+    }
+
+    fn markup(&self) -> f32 {
+        self.markup_override.unwrap_or_else(|| retailer(self.retailer).map_or(1.25, |retailer| retailer.markup))
+    }
+}
+
+fn catalog_product(retailer: &str, denomination: u32) -> Option<&'static CardProduct> {
+    CARD_CATALOG.iter().find(|product| product.retailer == retailer && product.denomination == denomination)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct GiftCard {
     retailer: String,
     denomination: u32,
     purchase_price: u32,
-    days_until_expiration: u32,
+    #[serde(default)]
+    purchase_day: Option<u32>, // Game day bought; None for starting stock, gifts and older saves
+    expiration_day: u32,       // First game day on which the card is no longer valid
 }
 
 impl GiftCard {
-    fn new(retailer: &str, denomination: u32, purchase_price: u32, days_until_expiration: u32) -> Self {
+    fn new(retailer: &str, denomination: u32, purchase_price: u32, expiration_day: u32) -> Self {
         Self {
             retailer: retailer.to_string(),
             denomination,
             purchase_price,
-            days_until_expiration,
+            purchase_day: None,
+            expiration_day,
         }
     }
 
+    fn purchased_on(mut self, day: u32) -> Self {
+        self.purchase_day = Some(day);
+        self
+    }
+
     fn market_value(&self) -> u32 {
-        // Basic markup calculation - 20-30% depending on retailer
-        match self.retailer.as_str() {
-            "Amazon" => (self.denomination as f32 * 1.30) as u32,
-            "Starbucks" => (self.denomination as f32 * 1.25) as u32,
-            "Target" => (self.denomination as f32 * 1.28) as u32,
-            "iTunes" => (self.denomination as f32 * 1.22) as u32,
-            "Walmart" => (self.denomination as f32 * 1.20) as u32,
-            _ => (self.denomination as f32 * 1.25) as u32,
-        }
+        // Markup of 16-30% depending on retailer and denomination
+        let markup = catalog_product(&self.retailer, self.denomination)
+            .map(CardProduct::markup)
+            .or_else(|| retailer(&self.retailer).map(|retailer| retailer.markup))
+            .unwrap_or(1.25);
+        (self.denomination as f32 * markup) as u32
     }
 
+    #[allow(dead_code)]
     fn potential_profit(&self) -> i32 {
         self.market_value() as i32 - self.purchase_price as i32
     }
 
-    fn is_expiring_soon(&self) -> bool {
-        self.days_until_expiration <= 15
+    fn days_until_expiration(&self, today: u32) -> u32 {
+        self.expiration_day.saturating_sub(today)
+    }
+
+    fn is_expired(&self, today: u32) -> bool {
+        today >= self.expiration_day
+    }
+
+    fn is_expiring_soon(&self, today: u32) -> bool {
+        self.days_until_expiration(today) <= 15
     }
 }
 
@@ -172,16 +347,135 @@ impl InventoryItem {
     }
 }
 
+// One retailer/denomination line of a customer order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct CardRequest {
+    retailer: String,
+    denomination: u32,
+    quantity: u32,
+    price_per_card: u32,
+    #[serde(default)]
+    shipped: u32, // Cards already delivered on this line
+}
+
+impl CardRequest {
+    fn new(retailer: &str, denomination: u32, quantity: u32, price_per_card: u32) -> Self {
+        Self {
+            retailer: retailer.to_string(),
+            denomination,
+            quantity,
+            price_per_card,
+            shipped: 0,
+        }
+    }
+
+    fn outstanding(&self) -> u32 {
+        self.quantity.saturating_sub(self.shipped)
+    }
+
+    fn total_price(&self) -> u32 {
+        self.price_per_card * self.quantity
+    }
+
+    fn describe(&self) -> String {
+        format!("{} {} ${}", self.quantity, retailer_name(&self.retailer), self.denomination)
+    }
+
+    // "3" when untouched, "1/3" once part of the line has shipped
+    fn progress(&self) -> String {
+        if self.shipped == 0 {
+            self.quantity.to_string()
+        } else {
+            format!("{}/{}", self.shipped, self.quantity)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "SavedCustomerOrder")]
 struct CustomerOrder {
     id: u32,
     customer_name: String,
-    retailer: String,
+    requested_cards: Vec<CardRequest>,
+    deadline_days: u32,
+    initial_deadline_days: u32, // Deadline when the order was placed
+    priority: OrderPriority,
+    patience: u32, // Counter-offers the customer will still sit through
+    customer_id: u32, // 0 for orders from saves that predate the customer base
+    allow_partial: bool, // Ship what's in stock now and backorder the rest
+    backordered: bool,   // Part shipped; deadline_days now counts down the backorder
+}
+
+// On-disk shape of a CustomerOrder. Saves from before multi-line orders stored
+// a single retailer/denomination/quantity inline instead of `requested_cards`.
+#[derive(Deserialize)]
+struct SavedCustomerOrder {
+    id: u32,
+    customer_name: String,
+    #[serde(default)]
+    requested_cards: Vec<CardRequest>,
+    #[serde(default)]
+    retailer: Option<String>,
+    #[serde(default)]
     denomination: u32,
+    #[serde(default)]
     quantity: u32,
+    #[serde(default)]
     offered_price_per_card: u32,
     deadline_days: u32,
+    #[serde(default)]
+    initial_deadline_days: u32,
     priority: OrderPriority,
+    #[serde(default = "default_order_patience")]
+    patience: u32,
+    #[serde(default)]
+    customer_id: u32,
+    #[serde(default)]
+    allow_partial: bool,
+    #[serde(default)]
+    backordered: bool,
+}
+
+impl From<SavedCustomerOrder> for CustomerOrder {
+    fn from(saved: SavedCustomerOrder) -> Self {
+        let mut requested_cards = saved.requested_cards;
+        if requested_cards.is_empty()
+            && let Some(retailer) = saved.retailer {
+            requested_cards.push(CardRequest::new(&retailer, saved.denomination, saved.quantity, saved.offered_price_per_card));
+        }
+
+        Self {
+            id: saved.id,
+            customer_name: saved.customer_name,
+            requested_cards,
+            deadline_days: saved.deadline_days,
+            initial_deadline_days: saved.initial_deadline_days,
+            priority: saved.priority,
+            patience: saved.patience,
+            customer_id: saved.customer_id,
+            allow_partial: saved.allow_partial,
+            backordered: saved.backordered,
+        }
+    }
+}
+
+const DEFAULT_ORDER_PATIENCE: u32 = 2;
+
+fn default_order_patience() -> u32 {
+    DEFAULT_ORDER_PATIENCE
+}
+
+// Most orders are a single line; some bundle a couple of different retailers
+const ORDER_THREE_LINE_CHANCE: f32 = 0.1;
+const ORDER_TWO_LINE_CHANCE: f32 = 0.25; // Of the orders that aren't three lines
+const ORDER_FAVOURITE_CHANCE: f32 = 0.7;  // Lines that ask for one of the customer's preferred retailers
+
+// Customer response to a counter-offer on their order
+#[derive(Debug, Clone)]
+enum NegotiationOutcome {
+    Accepted(CustomerOrder),  // Order revised at the asked price
+    Countered(CustomerOrder), // Customer meets partway; order revised at their new price
+    WalkedAway,               // Customer cancels the order
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -192,6 +486,20 @@ enum OrderPriority {
 }
 
 impl OrderPriority {
+    // Priority based on offer amount per card
+    fn for_lines(lines: &[CardRequest]) -> Self {
+        let offered: u32 = lines.iter().map(CardRequest::total_price).sum();
+        let face_value: u32 = lines.iter().map(|line| line.denomination * line.quantity).sum();
+        let cards: u32 = lines.iter().map(|line| line.quantity).sum();
+        if offered >= face_value + 8 * cards {
+            OrderPriority::High
+        } else if offered >= face_value + 5 * cards {
+            OrderPriority::Medium
+        } else {
+            OrderPriority::Low
+        }
+    }
+
     fn display(&self) -> &str {
         match self {
             OrderPriority::Low => "Low",
@@ -202,21 +510,122 @@ impl OrderPriority {
 }
 
 impl CustomerOrder {
+    // Single-line order
+    #[cfg(test)]
+    #[allow(clippy::too_many_arguments)]
     fn new(id: u32, customer_name: &str, retailer: &str, denomination: u32, quantity: u32, offered_price_per_card: u32, deadline_days: u32, priority: OrderPriority) -> Self {
+        let mut order = Self::from_lines(
+            id,
+            customer_name,
+            vec![CardRequest::new(retailer, denomination, quantity, offered_price_per_card)],
+            deadline_days,
+        );
+        order.priority = priority;
+        order
+    }
+
+    fn from_lines(id: u32, customer_name: &str, requested_cards: Vec<CardRequest>, deadline_days: u32) -> Self {
         Self {
             id,
             customer_name: customer_name.to_string(),
-            retailer: retailer.to_string(),
-            denomination,
-            quantity,
-            offered_price_per_card,
+            priority: OrderPriority::for_lines(&requested_cards),
+            requested_cards,
             deadline_days,
-            priority,
+            initial_deadline_days: deadline_days,
+            patience: DEFAULT_ORDER_PATIENCE,
+            customer_id: 0,
+            allow_partial: false,
+            backordered: false,
         }
     }
 
     fn total_offered(&self) -> u32 {
-        self.offered_price_per_card * self.quantity
+        self.requested_cards.iter().map(CardRequest::total_price).sum()
+    }
+
+    // Value of the lines delivered so far
+    fn shipped_value(&self) -> u32 {
+        self.requested_cards.iter().map(|line| line.price_per_card * line.shipped).sum()
+    }
+
+    fn card_count(&self) -> u32 {
+        self.requested_cards.iter().map(|line| line.quantity).sum()
+    }
+
+    fn outstanding_cards(&self) -> u32 {
+        self.requested_cards.iter().map(CardRequest::outstanding).sum()
+    }
+
+    fn is_complete(&self) -> bool {
+        self.outstanding_cards() == 0
+    }
+
+    // Once cards have shipped they're paid for at the agreed price, so the price is locked
+    fn has_shipped(&self) -> bool {
+        self.outstanding_cards() < self.card_count()
+    }
+
+    // Share of ordered cards delivered so far (0-1)
+    fn fill_rate(&self) -> f32 {
+        let shipped = self.card_count() - self.outstanding_cards();
+        shipped as f32 / self.card_count().max(1) as f32
+    }
+
+    // "3 Amazon $25 + 2 Starbucks $10"
+    fn summary(&self) -> String {
+        self.requested_cards.iter()
+            .map(CardRequest::describe)
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+
+    // Scale every line's price so the order is worth roughly `total`
+    fn reprice(&mut self, total: u32) {
+        let current = self.total_offered().max(1) as f32;
+        for line in &mut self.requested_cards {
+            line.price_per_card = (line.price_per_card as f32 * total as f32 / current).round() as u32;
+        }
+        self.priority = OrderPriority::for_lines(&self.requested_cards);
+    }
+
+    // Chance (0-1) the customer takes a counter-offer of `asked_total` for the whole order.
+    // Better reputation and hotter demand let us push further above their offer;
+    // a customer running out of patience is less willing to move.
+    fn counter_offer_acceptance(&self, asked_total: u32, reputation: u8, demand_multiplier: f32) -> f32 {
+        if asked_total <= self.total_offered() {
+            return 1.0;
+        }
+        if self.patience == 0 {
+            return 0.0;
+        }
+        let markup = asked_total as f32 / self.total_offered().max(1) as f32 - 1.0;
+        let tolerance = (0.03 + 0.03 * reputation as f32) * demand_multiplier.max(0.1);
+        let patience_factor = 0.5 + 0.5 * (self.patience as f32 / 3.0).min(1.0);
+        ((1.0 - markup / tolerance) * patience_factor).clamp(0.0, 1.0)
+    }
+
+    fn respond_to_counter_offer(&self, asked_total: u32, reputation: u8, demand_multiplier: f32, rng: &mut GameRng) -> NegotiationOutcome {
+        let acceptance = self.counter_offer_acceptance(asked_total, reputation, demand_multiplier);
+        let accepted = rng.chance(acceptance);
+        if !accepted && (self.patience <= 1 || acceptance == 0.0) {
+            return NegotiationOutcome::WalkedAway;
+        }
+
+        let mut revised = self.clone();
+        revised.patience = self.patience.saturating_sub(1);
+        if accepted {
+            revised.reprice(asked_total);
+            NegotiationOutcome::Accepted(revised)
+        } else {
+            // Not a flat no: the customer splits the difference, rounding in our favour. When the
+            // gap is too small to split, meeting us halfway means taking our price.
+            revised.reprice((self.total_offered() + asked_total).div_ceil(2));
+            if revised.total_offered() <= self.total_offered() || revised.total_offered() >= asked_total {
+                revised.reprice(asked_total);
+                return NegotiationOutcome::Accepted(revised);
+            }
+            NegotiationOutcome::Countered(revised)
+        }
     }
 
     fn is_expired(&self) -> bool {
@@ -224,3659 +633,9011 @@ impl CustomerOrder {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-enum Screen {
-    MainMenu,
-    Dashboard,
-    Market,
-    Orders,
-    Inventory,
-    Analytics,
-    Achievements,
-    Settings,
-    RandomEvent,
+// How an order from a customer ended up
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+enum OrderOutcome {
+    Open,
+    Fulfilled,
+    PartiallyFulfilled, // Backorder expired after part of the order shipped
+    Expired,
+    Declined, // Declined by us or the customer walked away
+    Lost,     // A competitor filled it before we could
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-enum Season {
-    Spring,   // March-May: Fresh start, moderate demand
-    Summer,   // June-August: Vacation season, travel cards popular
-    Fall,     // September-November: Back to school, tech cards popular
-    Winter,   // December-February: Holiday season, gift cards surge
+impl OrderOutcome {
+    fn display(&self) -> &str {
+        match self {
+            OrderOutcome::Open => "Open",
+            OrderOutcome::Fulfilled => "Fulfilled",
+            OrderOutcome::PartiallyFulfilled => "Partial",
+            OrderOutcome::Expired => "Expired",
+            OrderOutcome::Declined => "Declined",
+            OrderOutcome::Lost => "Lost",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct MarketEvent {
-    name: String,
-    description: String,
-    retailer_affected: Option<String>, // None means all retailers
-    price_multiplier: f32,  // 1.0 = normal, 1.5 = 50% more expensive, 0.8 = 20% cheaper
-    demand_multiplier: f32, // Affects order frequency
-    duration_days: u32,
-    remaining_days: u32,
+struct CustomerHistoryEntry {
+    order_id: u32,
+    day: u32,
+    #[serde(default)]
+    items: String, // Order summary, e.g. "3 Amazon $25 + 2 Starbucks $10"
+    #[serde(default)]
+    total_price: u32,
+    outcome: OrderOutcome,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct MarketConditions {
-    current_season: Season,
-    active_events: Vec<MarketEvent>,
-    base_demand_modifier: f32, // Seasonal base modifier
-    next_event_in_days: u32,
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+enum LoyaltyTier {
+    New,
+    Regular,
+    Loyal,
+    Vip,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-enum AchievementType {
-    // Progress milestones
-    FirstSale,
-    EarlyBird,      // Complete first 10 orders
-    Entrepreneur,   // Reach $10,000 cash
-    BusinessMogul,  // Reach $50,000 cash
-    Millionaire,    // Reach $1,000,000 cash
-    
-    // Performance achievements
-    PerfectWeek,    // 7 days with 100% order completion
-    SpeedDemon,     // Fulfill 5 orders in one day
-    Efficiency,     // Maintain 90%+ order success rate for 30 days
-    MarketMaster,   // Buy during 5 different favorable market events
-    
-    // Reputation achievements
-    LegendaryStatus,    // Reach 5-star reputation
-    CustomerFavorite,   // Complete 100 orders
-    TrustedSeller,     // Complete 500 orders
-    
-    // Seasonal achievements
-    WinterWinner,      // Earn $5,000 profit in Winter season
-    SeasonVeteran,     // Survive all 4 seasons
-    EventSurvivor,     // Survive 10 market events
-    
-    // Inventory achievements
-    Collector,         // Own 100+ cards simultaneously
-    DiversifiedPortfolio, // Own cards from all 5 retailers simultaneously
-    QuickTurnaround,   // Sell inventory within 3 days of purchase
+impl LoyaltyTier {
+    fn display(&self) -> &str {
+        match self {
+            LoyaltyTier::New => "New",
+            LoyaltyTier::Regular => "Regular",
+            LoyaltyTier::Loyal => "Loyal",
+            LoyaltyTier::Vip => "VIP",
+        }
+    }
+
+    fn icon(&self) -> &str {
+        match self {
+            LoyaltyTier::New => "🆕",
+            LoyaltyTier::Regular => "🙂",
+            LoyaltyTier::Loyal => "💙",
+            LoyaltyTier::Vip => "👑",
+        }
+    }
+
+    // Share of face value repeat customers add on top of their usual offer
+    fn price_premium(&self) -> f32 {
+        match self {
+            LoyaltyTier::New => 0.0,
+            LoyaltyTier::Regular => 0.02,
+            LoyaltyTier::Loyal => 0.04,
+            LoyaltyTier::Vip => 0.06,
+        }
+    }
+
+    // How often this customer places orders relative to a new one
+    fn order_weight(&self) -> u32 {
+        match self {
+            LoyaltyTier::New => 2,
+            LoyaltyTier::Regular => 3,
+            LoyaltyTier::Loyal => 4,
+            LoyaltyTier::Vip => 5,
+        }
+    }
 }
 
+// A returning customer and everything they've ordered from us
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct Achievement {
-    achievement_type: AchievementType,
+struct Customer {
+    id: u32,
     name: String,
-    description: String,
-    unlocked: bool,
-    unlock_date: Option<u32>, // Game day when unlocked
-    progress: u32,           // Current progress toward goal
-    target: u32,            // Target value to unlock
-    reward_cash: u32,       // Cash reward for unlocking
+    preferred_retailers: Vec<String>,
+    price_sensitivity: f32, // 0.0 = pays whatever, 1.0 = hunts for every dollar
+    orders_placed: u32,
+    orders_fulfilled: u32,
+    orders_expired: u32,
+    orders_declined: u32,
+    total_spent: u32,
+    history: Vec<CustomerHistoryEntry>, // Oldest first
+    #[serde(default)]
+    partial_shipments: bool, // New orders from this customer accept partial shipment + backorder
+    #[serde(default = "default_backorder_days")]
+    backorder_days: u32,     // How long they'll wait for the backordered remainder
+    #[serde(default)]
+    orders_partial: u32,     // Backorders that expired after part of the order shipped
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct AchievementTracker {
-    achievements: Vec<Achievement>,
-    total_unlocked: u32,
-    recent_unlock: Option<String>, // Recently unlocked achievement name
-    // Progress tracking variables
-    consecutive_perfect_days: u32,
-    consecutive_efficiency_days: u32,
-    orders_today: u32,
-    favorable_market_purchases: u32,
-    seasonal_winter_profit: i32,
-    seasons_survived: Vec<Season>,
-    events_survived: u32,
+const DEFAULT_BACKORDER_DAYS: u32 = 3;
+
+fn default_backorder_days() -> u32 {
+    DEFAULT_BACKORDER_DAYS
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-enum RandomEventType {
-    // Positive events
-    LoyalCustomer,      // Customer offers premium price for bulk order
-    SupplierDiscount,   // Get discount on next 3 purchases
-    MediaAttention,     // Reputation boost and customer influx
-    LuckyFind,          // Discover extra valuable cards in inventory
-    TechGlitch,         // Online competitor down, more customers
-    
-    // Negative events  
-    CardTheft,          // Lose some inventory to theft
-    CustomerComplaint,  // Reputation damage and compensation needed
-    SupplierIssue,      // Next few purchases more expensive
-    MarketCrash,        // All inventory temporarily worth less
-    RegulationChange,   // New rules cause complications
-    
-    // Neutral/Choice events
-    BusinessOffer,      // Choose between different business opportunities
-    CharityRequest,     // Option to donate for reputation boost
-    InventoryAudit,     // Discover accounting discrepancies
-    CompetitorMeeting,  // Opportunity for partnership or rivalry
-    CustomerSurvey,     // Feedback that affects future operations
-}
+const CUSTOMER_HISTORY_LIMIT: usize = 20;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct RandomEvent {
-    event_type: RandomEventType,
-    title: String,
-    description: String,
-    choice_a: Option<String>,   // First choice option
-    choice_b: Option<String>,   // Second choice option
-    choice_c: Option<String>,   // Third choice option (rare)
-    auto_resolve: bool,         // True if event resolves automatically
-    cash_impact: i32,           // Immediate cash change (can be negative)
-    reputation_impact: i8,      // Reputation change (-2 to +2)
-    inventory_impact: Vec<(String, i32)>, // (retailer, quantity change)
-    duration_days: u32,         // How long effects last
-    active: bool,               // Whether event is currently active
-}
+const CUSTOMER_NAMES: [&str; 16] = [
+    "Alice", "Bob", "Charlie", "Diana", "Eve", "Frank", "Grace", "Henry",
+    "Ivy", "Jack", "Kara", "Liam", "Maya", "Noah", "Olive", "Paul",
+];
 
-#[derive(Debug, Serialize, Deserialize)]
-struct RandomEventManager {
-    active_event: Option<RandomEvent>,
-    next_event_in_days: u32,
-    event_history: Vec<String>, // Record of past events
-    player_choice_pending: bool,
-    choice_deadline: u32,       // Day when choice must be made
-    temp_modifiers: Vec<TempModifier>, // Temporary effects from events
+impl Customer {
+    fn new(id: u32, name: &str, rng: &mut GameRng) -> Self {
+        let mut preferred_retailers = vec![rng.pick(RETAILERS).id.to_string()];
+        if rng.chance(0.5) {
+            let second = rng.pick(RETAILERS).id.to_string();
+            if !preferred_retailers.contains(&second) {
+                preferred_retailers.push(second);
+            }
+        }
+
+        Self {
+            id,
+            name: name.to_string(),
+            preferred_retailers,
+            price_sensitivity: rng.next_f32(),
+            orders_placed: 0,
+            orders_fulfilled: 0,
+            orders_expired: 0,
+            orders_declined: 0,
+            total_spent: 0,
+            history: Vec::new(),
+            partial_shipments: rng.chance(0.5),
+            backorder_days: rng.range(2, 5),
+            orders_partial: 0,
+        }
+    }
+
+    // Starting customer base: the first eight names
+    fn initial_base(rng: &mut GameRng) -> Vec<Self> {
+        CUSTOMER_NAMES[..8].iter()
+            .enumerate()
+            .map(|(i, name)| Customer::new(i as u32 + 1, name, rng))
+            .collect()
+    }
+
+    // Fulfilled orders build loyalty; each expired order undoes two of them
+    fn loyalty_tier(&self) -> LoyaltyTier {
+        let score = self.orders_fulfilled.saturating_sub(self.orders_expired * 2);
+        match score {
+            0 => LoyaltyTier::New,
+            1..=2 => LoyaltyTier::Regular,
+            3..=6 => LoyaltyTier::Loyal,
+            _ => LoyaltyTier::Vip,
+        }
+    }
+
+    // Customers we keep letting down stop ordering
+    fn has_churned(&self) -> bool {
+        self.orders_expired >= self.orders_fulfilled + 3
+    }
+
+    // Chance one line of this customer's order asks for a retailer's cards
+    fn order_line_chance(&self, retailer: &str) -> f32 {
+        let weight = |favourites_only: bool, retailer_only: bool| -> u32 {
+            CARD_CATALOG.iter()
+                .filter(|product| !favourites_only || self.preferred_retailers.iter().any(|preferred| preferred == product.retailer))
+                .filter(|product| !retailer_only || product.retailer == retailer)
+                .map(|product| product.demand_weight)
+                .sum()
+        };
+        let any_card = weight(false, true) as f32 / weight(false, false).max(1) as f32;
+        match weight(true, false) {
+            0 => any_card,
+            favourites => ORDER_FAVOURITE_CHANCE * weight(true, true) as f32 / favourites as f32
+                + (1.0 - ORDER_FAVOURITE_CHANCE) * any_card,
+        }
+    }
+
+    fn record_order(&mut self, order: &CustomerOrder, day: u32) {
+        self.orders_placed += 1;
+        self.history.push(CustomerHistoryEntry {
+            order_id: order.id,
+            day,
+            items: order.summary(),
+            total_price: order.total_offered(),
+            outcome: OrderOutcome::Open,
+        });
+        if self.history.len() > CUSTOMER_HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+    }
+
+    // Close out an order; whatever was shipped before it ended still counts as spend
+    fn record_outcome(&mut self, order: &CustomerOrder, outcome: OrderOutcome) {
+        match outcome {
+            OrderOutcome::Fulfilled => self.orders_fulfilled += 1,
+            OrderOutcome::Expired => self.orders_expired += 1,
+            OrderOutcome::PartiallyFulfilled => self.orders_partial += 1,
+            OrderOutcome::Declined => self.orders_declined += 1,
+            OrderOutcome::Lost | OrderOutcome::Open => {}
+        }
+        self.total_spent += order.shipped_value();
+        if let Some(entry) = self.history.iter_mut().find(|entry| entry.order_id == order.id) {
+            entry.total_price = order.total_offered();
+            entry.outcome = outcome;
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct TempModifier {
-    name: String,
-    description: String,
-    price_multiplier: f32,      // Affects purchase prices
-    demand_multiplier: f32,     // Affects order frequency
-    reputation_protection: bool, // Prevents reputation loss
-    remaining_days: u32,
+// How a rival reseller prices its cards
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+enum PricingStrategy {
+    Discounter, // Thin margins for bargain hunters; starts price wars when it loses ground
+    Balanced,
+    Premium,    // Leans on reputation and sits out price wars
 }
 
-impl TempModifier {
-    fn age_day(&mut self) {
-        if self.remaining_days > 0 {
-            self.remaining_days -= 1;
+impl PricingStrategy {
+    fn display(&self) -> &str {
+        match self {
+            PricingStrategy::Discounter => "Discounter",
+            PricingStrategy::Balanced => "Balanced",
+            PricingStrategy::Premium => "Premium",
         }
     }
-    
-    fn is_expired(&self) -> bool {
-        self.remaining_days == 0
+
+    // Asking price as a share of face value
+    fn price_ratio(&self, price_war: bool) -> f32 {
+        match (self, price_war) {
+            (PricingStrategy::Discounter, false) => 0.88,
+            (PricingStrategy::Discounter, true) => 0.84,
+            (PricingStrategy::Balanced, false) => 0.91,
+            (PricingStrategy::Balanced, true) => 0.87,
+            (PricingStrategy::Premium, _) => 0.95,
+        }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct GameData {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CompetitorStock {
+    retailer: String,
+    denomination: u32,
+    quantity: u32,
+}
+
+// A rival reseller buying from the same wholesale market and serving the same customers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Competitor {
+    name: String,
+    strategy: PricingStrategy,
     cash: u32,
     reputation: u8, // 1-5 stars
-    day: u32,
-    hour: u8,
-    minute: u8,
-    recent_activities: Vec<String>,
-    inventory: Vec<InventoryItem>,
-    customer_orders: VecDeque<CustomerOrder>,
-    next_order_id: u32,
-    analytics: BusinessAnalytics,
-    market_conditions: MarketConditions,
-    achievements: AchievementTracker,
-    random_events: RandomEventManager,
+    stock: Vec<CompetitorStock>,
+    orders_won: u32,
+    revenue: u32,
+    price_war_days: u32, // Days left cutting prices in a price war
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct BusinessAnalytics {
-    total_revenue: u32,
-    total_purchases: u32,
-    orders_completed: u32,
-    orders_expired: u32,
-    best_day_revenue: u32,
-    cards_sold: u32,
-    cards_expired: u32,
-    daily_revenues: Vec<u32>, // Track daily performance
-    profit_margins: Vec<f32>, // Track efficiency over time
-}
+const COMPETITOR_BASE_WIN_CHANCE: f32 = 0.25;
+const COMPETITOR_RESTOCK_TARGET: u32 = 8;   // Cards per SKU a rival tries to keep on hand
+const COMPETITOR_RESTOCK_PICKS: u32 = 3;    // Listings a rival shops each day
+const COMPETITOR_DAILY_BUDGET: f32 = 0.3;   // Share of its cash a rival spends on stock per day
+const COMPETITOR_DAILY_OVERHEAD: u32 = 25;
+const COMPETITOR_ORDERS_PER_STAR: u32 = 15; // Orders won per reputation star gained
+
+// Temp modifier that marks a price war, whether we or a rival started it
+const PRICE_WAR_MODIFIER: &str = "Price War";
+const PRICE_WAR_DAYS: u32 = 7;
+const PRICE_WAR_SHARE_TRIGGER: f32 = 0.15;  // Our market share that makes rivals fight back
+const PRICE_WAR_REACTION_CHANCE: f32 = 0.1; // Daily chance a provoked rival starts or escalates a price war
+
+const RIVALS: [(&str, PricingStrategy, u32, u8); 3] = [
+    ("BargainCards", PricingStrategy::Discounter, 4000, 2),
+    ("CardHub", PricingStrategy::Balanced, 6000, 3),
+    ("Prestige Gifts", PricingStrategy::Premium, 8000, 4),
+];
+
+impl Competitor {
+    fn initial_rivals() -> Vec<Self> {
+        RIVALS.iter()
+            .map(|(name, strategy, cash, reputation)| Competitor {
+                name: name.to_string(),
+                strategy: *strategy,
+                cash: *cash,
+                reputation: *reputation,
+                stock: Vec::new(),
+                orders_won: 0,
+                revenue: 0,
+                price_war_days: 0,
+            })
+            .collect()
+    }
 
-impl BusinessAnalytics {
-    fn new() -> Self {
-        Self {
-            total_revenue: 0,
-            total_purchases: 0,
-            orders_completed: 0,
-            orders_expired: 0,
-            best_day_revenue: 0,
-            cards_sold: 0,
-            cards_expired: 0,
-            daily_revenues: vec![0], // Start with day 1
-            profit_margins: Vec::new(),
-        }
+    fn stock_of(&self, retailer: &str, denomination: u32) -> u32 {
+        self.stock.iter()
+            .find(|stock| stock.retailer == retailer && stock.denomination == denomination)
+            .map_or(0, |stock| stock.quantity)
     }
 
-    fn record_purchase(&mut self, amount: u32) {
-        self.total_purchases += amount;
+    fn card_count(&self) -> u32 {
+        self.stock.iter().map(|stock| stock.quantity).sum()
     }
 
-    fn record_sale(&mut self, revenue: u32, cost: u32, cards_sold: u32) {
-        self.total_revenue += revenue;
-        self.orders_completed += 1;
-        self.cards_sold += cards_sold;
-        
-        // Calculate profit margin for this sale
-        if revenue > 0 {
-            let profit_margin = ((revenue as f32 - cost as f32) / revenue as f32) * 100.0;
-            self.profit_margins.push(profit_margin);
-        }
-        
-        // Update daily revenue
-        if let Some(today_revenue) = self.daily_revenues.last_mut() {
-            *today_revenue += revenue;
-            self.best_day_revenue = self.best_day_revenue.max(*today_revenue);
+    fn add_stock(&mut self, retailer: &str, denomination: u32, quantity: u32) {
+        match self.stock.iter_mut().find(|stock| stock.retailer == retailer && stock.denomination == denomination) {
+            Some(stock) => stock.quantity += quantity,
+            None => self.stock.push(CompetitorStock { retailer: retailer.to_string(), denomination, quantity }),
         }
     }
 
-    fn record_expired_order(&mut self) {
-        self.orders_expired += 1;
+    fn in_price_war(&self) -> bool {
+        self.price_war_days > 0
     }
 
-    fn record_expired_cards(&mut self, count: u32) {
-        self.cards_expired += count;
+    fn asking_price(&self, denomination: u32) -> u32 {
+        (denomination as f32 * self.strategy.price_ratio(self.in_price_war())).round() as u32
     }
 
-    fn start_new_day(&mut self) {
-        self.daily_revenues.push(0);
-        // Keep only last 30 days
-        if self.daily_revenues.len() > 30 {
-            self.daily_revenues.remove(0);
-        }
+    fn asking_total(&self, order: &CustomerOrder) -> u32 {
+        order.requested_cards.iter()
+            .map(|line| self.asking_price(line.denomination) * line.outstanding())
+            .sum()
     }
 
-    fn average_profit_margin(&self) -> f32 {
-        if self.profit_margins.is_empty() {
-            0.0
-        } else {
-            self.profit_margins.iter().sum::<f32>() / self.profit_margins.len() as f32
-        }
+    fn can_fill(&self, order: &CustomerOrder) -> bool {
+        order.requested_cards.iter()
+            .all(|line| self.stock_of(&line.retailer, line.denomination) >= line.outstanding())
     }
 
-    fn recent_daily_average(&self) -> f32 {
-        if self.daily_revenues.len() <= 1 {
+    // Chance the customer takes this order to the rival instead of us. Price matters more to
+    // bargain hunters, a better-rated rival pulls harder and loyal customers mostly stay put.
+    fn win_chance(&self, order: &CustomerOrder, customer: &Customer, our_reputation: u8) -> f32 {
+        if !self.can_fill(order) {
             return 0.0;
         }
-        
-        let recent_days = self.daily_revenues.len().min(7); // Last 7 days
-        let sum: u32 = self.daily_revenues.iter().rev().take(recent_days).sum();
-        sum as f32 / recent_days as f32
+        let price_factor = if self.asking_total(order) <= order.total_offered() {
+            1.0 + customer.price_sensitivity
+        } else {
+            1.0 - customer.price_sensitivity
+        };
+        let reputation_factor = self.reputation as f32 / our_reputation.max(1) as f32;
+        let loyalty_factor = 1.0 - customer.loyalty_tier().price_premium() * 5.0;
+        (COMPETITOR_BASE_WIN_CHANCE * price_factor * reputation_factor * loyalty_factor).clamp(0.0, 0.75)
+    }
+
+    // Ship the whole order from stock at our asking price; returns what the customer paid
+    fn fill_order(&mut self, order: &CustomerOrder) -> u32 {
+        let total = self.asking_total(order);
+        for line in &order.requested_cards {
+            if let Some(stock) = self.stock.iter_mut()
+                .find(|stock| stock.retailer == line.retailer && stock.denomination == line.denomination) {
+                stock.quantity -= line.outstanding();
+            }
+        }
+        self.stock.retain(|stock| stock.quantity > 0);
+        self.cash += total;
+        self.revenue += total;
+        self.orders_won += 1;
+        if self.orders_won.is_multiple_of(COMPETITOR_ORDERS_PER_STAR) {
+            self.reputation = (self.reputation + 1).min(5);
+        }
+        total
     }
+}
 
-    fn total_profit(&self) -> i32 {
-        self.total_revenue as i32 - self.total_purchases as i32
-    }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum Screen {
+    MainMenu,
+    NewGameSetup,
+    Dashboard,
+    Market,
+    Orders,
+    Inventory,
+    Analytics,
+    Achievements,
+    Settings,
+    RandomEvent,
+    CustomerDetail,
+    Competitors,
+    GameOver,
 }
 
-impl Season {
-    fn from_day(day: u32) -> Self {
-        // Approximate seasons based on day of year (assuming 90-day seasons)
-        let season_day = (day - 1) % 360; // 360-day year for simplicity
-        match season_day {
-            0..=89 => Season::Spring,
-            90..=179 => Season::Summer, 
-            180..=269 => Season::Fall,
-            270..=359 => Season::Winter,
-            _ => Season::Spring,
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+enum Difficulty {
+    Easy,     // $10k start, cheaper wholesale, long expirations, forgiving customers
+    #[default]
+    Normal,   // $5k start, standard market
+    Hard,     // $2.5k start, pricier wholesale, short expirations, demanding customers
+}
+
+impl Difficulty {
+    const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    fn display(&self) -> &str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
         }
     }
 
-    fn display(&self) -> &str {
+    // Built from the same numbers the game runs on, so it can't drift from them
+    fn description(&self) -> String {
+        let (min_expiration, max_expiration) = self.expiration_range();
+        let (min_deadline, max_deadline) = self.order_deadline_range();
+        let customers = match self {
+            Difficulty::Easy => "first missed order each day forgiven",
+            Difficulty::Normal => "standard customers",
+            Difficulty::Hard => "missed orders cost double",
+        };
+        format!(
+            "${} capital, ~{:.0}% margin on face value, {}-{} day expirations, {}-{} day deadlines, {}",
+            self.starting_cash(),
+            self.face_value_margin() * 100.0,
+            min_expiration,
+            max_expiration,
+            min_deadline,
+            max_deadline,
+            customers
+        )
+    }
+
+    // Average share of face value left after paying wholesale, across the catalog
+    fn face_value_margin(&self) -> f32 {
+        let average_cost_rate = CARD_CATALOG.iter()
+            .map(|product| product.base_cost() as f32 / product.denomination as f32)
+            .sum::<f32>() / CARD_CATALOG.len() as f32;
+        1.0 - average_cost_rate * self.wholesale_cost_multiplier()
+    }
+
+    fn starting_cash(&self) -> u32 {
         match self {
-            Season::Spring => "Spring",
-            Season::Summer => "Summer", 
-            Season::Fall => "Fall",
-            Season::Winter => "Winter",
+            Difficulty::Easy => 10000,
+            Difficulty::Normal => 5000,
+            Difficulty::Hard => 2500,
         }
     }
 
-    fn demand_modifier(&self) -> f32 {
+    // Scales wholesale costs, which sets the achievable profit margin
+    fn wholesale_cost_multiplier(&self) -> f32 {
         match self {
-            Season::Spring => 1.0,  // Normal demand
-            Season::Summer => 1.1,  // Slightly higher (vacation)
-            Season::Fall => 0.9,    // Slightly lower (back to school)
-            Season::Winter => 1.4,  // Much higher (holidays)
+            Difficulty::Easy => 0.9,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.08,
         }
     }
 
-    fn retailer_bonus(&self, retailer: &str) -> f32 {
-        match (self, retailer) {
-            (Season::Summer, "Target") => 1.2,     // Summer vacation shopping
-            (Season::Summer, "Walmart") => 1.1,   // General summer demand
-            (Season::Fall, "iTunes") => 1.3,      // Back to school tech
-            (Season::Fall, "Amazon") => 1.2,      // Online shopping increase
-            (Season::Winter, "Amazon") => 1.5,    // Holiday online shopping
-            (Season::Winter, "Starbucks") => 1.3, // Holiday coffee gifts
-            (Season::Winter, "iTunes") => 1.4,    // Holiday tech gifts
-            (Season::Winter, _) => 1.2,           // General holiday boost
-            _ => 1.0,
+    // Scales the fixed operating expenses (rent and wages)
+    fn expense_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.25,
         }
     }
-}
 
-impl MarketEvent {
-    fn new(name: &str, description: &str, retailer: Option<&str>, price_mult: f32, demand_mult: f32, duration: u32) -> Self {
-        Self {
-            name: name.to_string(),
-            description: description.to_string(),
-            retailer_affected: retailer.map(|s| s.to_string()),
-            price_multiplier: price_mult,
-            demand_multiplier: demand_mult,
-            duration_days: duration,
-            remaining_days: duration,
+    // (min, max) days until a newly purchased card expires
+    fn expiration_range(&self) -> (u32, u32) {
+        match self {
+            Difficulty::Easy => (60, 120),
+            Difficulty::Normal => (30, 89),
+            Difficulty::Hard => (15, 45),
         }
     }
 
-    fn is_expired(&self) -> bool {
-        self.remaining_days == 0
+    // (min, max) days a customer gives us to fulfill an order
+    fn order_deadline_range(&self) -> (u32, u32) {
+        match self {
+            Difficulty::Easy => (3, 8),
+            Difficulty::Normal => (2, 6),
+            Difficulty::Hard => (1, 4),
+        }
     }
 
-    fn affects_retailer(&self, retailer: &str) -> bool {
-        self.retailer_affected.as_ref().map_or(true, |r| r == retailer)
+    // Reputation stars lost when `expired_orders` orders expire on the same day
+    fn expired_order_penalty(&self, expired_orders: u32) -> u32 {
+        match self {
+            Difficulty::Easy => expired_orders.saturating_sub(1), // First miss each day is forgiven
+            Difficulty::Normal => expired_orders,
+            Difficulty::Hard => expired_orders * 2,
+        }
     }
 }
 
-impl MarketConditions {
-    fn new() -> Self {
-        Self {
-            current_season: Season::Spring,
-            active_events: Vec::new(),
-            base_demand_modifier: 1.0,
-            next_event_in_days: 3 + (1 % 7), // Next event in 3-9 days
+// Which cost lots are consumed (and expensed) when cards leave inventory
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+enum CostBasisPolicy {
+    #[default]
+    Fifo,             // Oldest lots first
+    Lifo,             // Newest lots first
+    SoonestExpiring,  // Lots closest to expiration first
+    WeightedAverage,  // Average unit cost across all matching lots
+}
+
+impl CostBasisPolicy {
+    fn display(&self) -> &str {
+        match self {
+            CostBasisPolicy::Fifo => "FIFO",
+            CostBasisPolicy::Lifo => "LIFO",
+            CostBasisPolicy::SoonestExpiring => "Soonest Expiring",
+            CostBasisPolicy::WeightedAverage => "Weighted Average",
         }
     }
 
-    fn update_season(&mut self, day: u32) {
-        let new_season = Season::from_day(day);
-        if !matches!((&self.current_season, &new_season), 
-            (Season::Spring, Season::Spring) | 
-            (Season::Summer, Season::Summer) | 
-            (Season::Fall, Season::Fall) | 
-            (Season::Winter, Season::Winter)) {
-            // Season changed
-            self.current_season = new_season;
-            self.base_demand_modifier = self.current_season.demand_modifier();
+    fn next(&self) -> Self {
+        match self {
+            CostBasisPolicy::Fifo => CostBasisPolicy::Lifo,
+            CostBasisPolicy::Lifo => CostBasisPolicy::SoonestExpiring,
+            CostBasisPolicy::SoonestExpiring => CostBasisPolicy::WeightedAverage,
+            CostBasisPolicy::WeightedAverage => CostBasisPolicy::Fifo,
         }
     }
+}
 
-    fn process_daily_events(&mut self, day: u32, activities: &mut Vec<String>) {
-        // Age existing events
-        self.active_events.retain_mut(|event| {
-            if event.remaining_days > 0 {
-                event.remaining_days -= 1;
-                true
-            } else {
-                activities.insert(0, format!("📈 Market event '{}' has ended", event.name));
-                false
-            }
-        });
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+enum Season {
+    Spring,   // March-May: Fresh start, moderate demand
+    Summer,   // June-August: Vacation season, travel cards popular
+    Fall,     // September-November: Back to school, tech cards popular
+    Winter,   // December-February: Holiday season, gift cards surge
+}
 
-        // Check for new events
-        if self.next_event_in_days > 0 {
-            self.next_event_in_days -= 1;
-        } else {
-            self.generate_random_event(day, activities);
-            self.next_event_in_days = 5 + (day % 10); // Next event in 5-14 days
-        }
-    }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MarketEvent {
+    name: String,
+    description: String,
+    retailer_affected: Option<String>, // None means all retailers
+    price_multiplier: f32,  // 1.0 = normal, 1.5 = 50% more expensive, 0.8 = 20% cheaper
+    demand_multiplier: f32, // Affects order frequency
+    duration_days: u32,
+    remaining_days: u32,
+}
 
-    fn generate_random_event(&mut self, day: u32, activities: &mut Vec<String>) {
-        let event_type = day % 8; // 8 different event types
-        
-        let event = match event_type {
-            0 => MarketEvent::new(
-                "Tech Surge", 
-                "New gadget releases drive tech gift card demand",
-                Some("iTunes"),
-                0.9, // 10% cheaper to buy
-                1.5, // 50% more demand
-                4
-            ),
-            1 => MarketEvent::new(
-                "Coffee Festival",
-                "Local coffee festival increases Starbucks popularity", 
-                Some("Starbucks"),
-                1.1, // 10% more expensive
-                1.8, // 80% more demand
-                3
-            ),
-            2 => MarketEvent::new(
-                "Supply Chain Issues",
-                "Logistics problems affect all retailers",
-                None,
-                1.3, // 30% more expensive
-                0.7, // 30% less demand
-                5
-            ),
-            3 => MarketEvent::new(
-                "Amazon Prime Day",
-                "Special Amazon promotion increases demand",
-                Some("Amazon"),
-                0.85, // 15% cheaper
-                2.0, // 100% more demand
-                2
-            ),
-            4 => MarketEvent::new(
-                "Back to School",
-                "Students need supplies, Target benefits",
-                Some("Target"),
-                1.05, // 5% more expensive
-                1.4, // 40% more demand
-                7
-            ),
-            5 => MarketEvent::new(
-                "Economic Downturn",
-                "Customers tighten budgets, demand drops",
-                None,
-                1.0, // Same price
-                0.6, // 40% less demand
-                6
-            ),
-            6 => MarketEvent::new(
-                "Walmart Expansion",
-                "New Walmart stores increase accessibility",
-                Some("Walmart"),
-                0.95, // 5% cheaper
-                1.3, // 30% more demand
-                4
-            ),
-            _ => MarketEvent::new(
-                "Market Boom",
-                "General economic growth benefits all retailers",
-                None,
-                0.9, // 10% cheaper
-                1.2, // 20% more demand
-                5
-            ),
-        };
+#[derive(Debug, Serialize, Deserialize)]
+struct MarketConditions {
+    current_season: Season,
+    active_events: Vec<MarketEvent>,
+    base_demand_modifier: f32, // Seasonal base modifier
+    next_event_in_days: u32,
+}
 
-        activities.insert(0, format!("🎯 New market event: {}", event.name));
-        self.active_events.push(event);
-    }
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+enum AchievementType {
+    // Progress milestones
+    FirstSale,
+    EarlyBird,      // Complete first 10 orders
+    Entrepreneur,   // Reach $10,000 cash
+    BusinessMogul,  // Reach $50,000 cash
+    Millionaire,    // Reach $1,000,000 cash
+    
+    // Performance achievements
+    PerfectWeek,    // 7 days with 100% order completion
+    SpeedDemon,     // Fulfill 5 orders in one day
+    Efficiency,     // Maintain 90%+ order success rate for 30 days
+    MarketMaster,   // Buy during 5 different favorable market events
+    MarketPlayer,   // Hold 10% of the local market over 30 days
+    MarketLeader,   // Hold 25% of the local market over 30 days
+    
+    // Reputation achievements
+    LegendaryStatus,    // Reach 5-star reputation
+    CustomerFavorite,   // Complete 100 orders
+    TrustedSeller,     // Complete 500 orders
+    
+    // Seasonal achievements
+    WinterWinner,      // Earn $5,000 profit in Winter season
+    SeasonVeteran,     // Survive all 4 seasons
+    EventSurvivor,     // Survive 10 market or random events
+    
+    // Inventory achievements
+    Collector,         // Own 100+ cards simultaneously
+    DiversifiedPortfolio, // Own cards from every retailer simultaneously
+    QuickTurnaround,   // Sell inventory within 3 days of purchase
+}
 
-    fn get_price_multiplier(&self, retailer: &str) -> f32 {
-        let mut multiplier = 1.0;
-        
-        // Apply seasonal bonus
-        multiplier *= self.current_season.retailer_bonus(retailer);
-        
-        // Apply active events
-        for event in &self.active_events {
-            if event.affects_retailer(retailer) {
-                multiplier *= event.price_multiplier;
-            }
-        }
-        
-        multiplier
-    }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Achievement {
+    achievement_type: AchievementType,
+    name: String,
+    description: String,
+    unlocked: bool,
+    unlock_date: Option<u32>, // Game day when unlocked
+    progress: u32,           // Current progress toward goal
+    target: u32,            // Target value to unlock
+    reward_cash: u32,       // Cash reward for unlocking
+}
+
+// Cash reward earned by an unlock; paid into GameData::cash once claimed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RewardLedgerEntry {
+    achievement: String,
+    amount: u32,
+    unlocked_day: u32,
+    claimed_day: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AchievementTracker {
+    achievements: Vec<Achievement>,
+    total_unlocked: u32,
+    recent_unlock: Option<String>, // Recently unlocked achievement name
+    #[serde(default)]
+    reward_ledger: Vec<RewardLedgerEntry>,
+    // Progress tracking variables
+    consecutive_perfect_days: u32,
+    consecutive_efficiency_days: u32,
+    orders_today: u32,                // Orders completed since the day began
+    #[serde(default)]
+    orders_expired_today: u32,        // Orders that expired at the start of the day
+    favorable_market_purchases: u32,
+    seasonal_winter_profit: i32,
+    seasons_survived: Vec<Season>,
+    events_survived: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+enum RandomEventType {
+    // Positive events
+    LoyalCustomer,      // Customer offers premium price for bulk order
+    SupplierDiscount,   // Get discount on next 3 purchases
+    MediaAttention,     // Reputation boost and customer influx
+    LuckyFind,          // Discover extra valuable cards in inventory
+    TechGlitch,         // Online competitor down, more customers
     
-    fn get_price_multiplier_with_random_events(&self, retailer: &str, random_events: &RandomEventManager) -> f32 {
-        let mut multiplier = self.get_price_multiplier(retailer);
-        
-        // Apply random event modifiers
-        for modifier in &random_events.temp_modifiers {
-            multiplier *= modifier.price_multiplier;
-        }
-        
-        multiplier
-    }
+    // Negative events  
+    CardTheft,          // Lose some inventory to theft
+    CustomerComplaint,  // Reputation damage and compensation needed
+    SupplierIssue,      // Next few purchases more expensive
+    MarketCrash,        // All inventory temporarily worth less
+    RegulationChange,   // New rules cause complications
+    
+    // Neutral/Choice events
+    BusinessOffer,      // Choose between different business opportunities
+    CharityRequest,     // Option to donate for reputation boost
+    InventoryAudit,     // Discover accounting discrepancies
+    CompetitorMeeting,  // Opportunity for partnership or rivalry
+    CustomerSurvey,     // Feedback that affects future operations
 
-    fn get_demand_multiplier(&self, retailer: &str) -> f32 {
-        let mut multiplier = self.base_demand_modifier;
-        
-        // Apply seasonal bonus for demand
-        multiplier *= self.current_season.retailer_bonus(retailer);
-        
-        // Apply active events
-        for event in &self.active_events {
-            if event.affects_retailer(retailer) {
-                multiplier *= event.demand_multiplier;
-            }
-        }
-        
-        multiplier
-    }
+    // Designer-defined events from the event catalog
+    #[default]
+    Custom,
 }
 
-impl Achievement {
-    fn new(achievement_type: AchievementType, name: &str, description: &str, target: u32, reward: u32) -> Self {
-        Self {
-            achievement_type,
-            name: name.to_string(),
-            description: description.to_string(),
-            unlocked: false,
-            unlock_date: None,
-            progress: 0,
-            target,
-            reward_cash: reward,
-        }
-    }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RandomEvent {
+    event_type: RandomEventType,
+    title: String,
+    description: String,
+    choice_a: Option<String>,   // First choice option
+    choice_b: Option<String>,   // Second choice option
+    choice_c: Option<String>,   // Third choice option (rare)
+    auto_resolve: bool,         // True if event resolves automatically
+    cash_impact: i32,           // Immediate cash change (can be negative)
+    reputation_impact: i8,      // Reputation change (-2 to +2)
+    inventory_impact: Vec<(String, i32)>, // (retailer, quantity change)
+    duration_days: u32,         // How long effects last
+    active: bool,               // Whether event is currently active
+    #[serde(default)]
+    choice_effects: Vec<EventEffect>, // Effect per choice (a single entry for auto events)
+}
 
-    fn update_progress(&mut self, new_progress: u32) -> bool {
-        self.progress = new_progress;
-        if !self.unlocked && self.progress >= self.target {
-            self.unlocked = true;
-            true // Achievement unlocked
-        } else {
-            false
-        }
+// Event catalog: market and random events are data, loaded from `events.json` if present,
+// otherwise from the built-in catalog in assets/events.json
+const BUILTIN_EVENT_CATALOG: &str = include_str!("../assets/events.json");
+const EVENTS_FILE: &str = "events.json";
+
+fn default_multiplier() -> f32 {
+    1.0
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+fn default_duration() -> u32 {
+    1
+}
+
+// Preconditions an event needs before it can be drawn (all optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct EventConditions {
+    min_day: Option<u32>,
+    max_day: Option<u32>,
+    seasons: Vec<Season>,       // Empty means any season
+    min_reputation: Option<u8>,
+    max_reputation: Option<u8>,
+    min_cash: Option<u32>,
+    requires_stock: Option<String>, // Retailer we must hold cards for
+}
+
+// Snapshot of the business used to check event preconditions
+#[derive(Debug, Clone)]
+struct EventContext {
+    day: u32,
+    season: Season,
+    reputation: u8,
+    cash: u32,
+    retailers_in_stock: Vec<String>,
+}
+
+impl EventConditions {
+    fn is_met(&self, context: &EventContext) -> bool {
+        self.min_day.is_none_or(|min| context.day >= min)
+            && self.max_day.is_none_or(|max| context.day <= max)
+            && (self.seasons.is_empty() || self.seasons.contains(&context.season))
+            && self.min_reputation.is_none_or(|min| context.reputation >= min)
+            && self.max_reputation.is_none_or(|max| context.reputation <= max)
+            && self.min_cash.is_none_or(|min| context.cash >= min)
+            && self.requires_stock.as_ref().is_none_or(|r| context.retailers_in_stock.contains(r))
     }
+}
 
-    fn unlock(&mut self, day: u32) {
-        if !self.unlocked {
-            self.unlocked = true;
-            self.unlock_date = Some(day);
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ModifierTemplate {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default = "default_multiplier")]
+    price_multiplier: f32,
+    #[serde(default = "default_multiplier")]
+    demand_multiplier: f32,
+    #[serde(default)]
+    reputation_protection: bool,
+    #[serde(default = "default_duration")]
+    duration_days: u32,
+}
+
+impl ModifierTemplate {
+    fn instantiate(&self) -> TempModifier {
+        TempModifier {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            price_multiplier: self.price_multiplier,
+            demand_multiplier: self.demand_multiplier,
+            reputation_protection: self.reputation_protection,
+            remaining_days: self.duration_days,
         }
     }
+}
 
-    fn progress_percentage(&self) -> f32 {
-        if self.target == 0 {
-            0.0
-        } else {
-            (self.progress as f32 / self.target as f32 * 100.0).min(100.0)
-        }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InventoryEffect {
+    retailer: String,
+    quantity: i32, // Negative removes cards
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct EventEffect {
+    cash: i32,
+    reputation: i8,
+    inventory: Vec<InventoryEffect>,
+    modifiers: Vec<ModifierTemplate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EventChoiceDef {
+    label: String,
+    #[serde(default)]
+    effect: EventEffect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MarketEventDef {
+    name: String,
+    description: String,
+    #[serde(default)]
+    retailer: Option<String>, // None means all retailers
+    #[serde(default = "default_multiplier")]
+    price_multiplier: f32,
+    #[serde(default = "default_multiplier")]
+    demand_multiplier: f32,
+    #[serde(default = "default_duration")]
+    duration_days: u32,
+    #[serde(default = "default_weight")]
+    weight: u32,
+    #[serde(default)]
+    conditions: EventConditions,
+}
+
+impl MarketEventDef {
+    fn instantiate(&self) -> MarketEvent {
+        MarketEvent::new(
+            &self.name,
+            &self.description,
+            self.retailer.as_deref(),
+            self.price_multiplier,
+            self.demand_multiplier,
+            self.duration_days,
+        )
     }
 }
 
-impl AchievementTracker {
-    fn new() -> Self {
-        let mut tracker = Self {
-            achievements: Vec::new(),
-            total_unlocked: 0,
-            recent_unlock: None,
-            consecutive_perfect_days: 0,
-            consecutive_efficiency_days: 0,
-            orders_today: 0,
-            favorable_market_purchases: 0,
-            seasonal_winter_profit: 0,
-            seasons_survived: Vec::new(),
-            events_survived: 0,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RandomEventDef {
+    #[serde(default)]
+    event_type: RandomEventType,
+    title: String,
+    description: String,
+    #[serde(default = "default_weight")]
+    weight: u32,
+    #[serde(default)]
+    conditions: EventConditions,
+    #[serde(default = "default_duration")]
+    duration_days: u32,
+    #[serde(default)]
+    effect: EventEffect,           // Applied when the event has no choices
+    #[serde(default)]
+    choices: Vec<EventChoiceDef>,  // Up to 3; empty means the event auto-resolves
+}
+
+impl RandomEventDef {
+    fn instantiate(&self) -> RandomEvent {
+        let auto_resolve = self.choices.is_empty();
+        let label = |i: usize| self.choices.get(i).map(|c| c.label.clone());
+        let choice_effects = if auto_resolve {
+            vec![self.effect.clone()]
+        } else {
+            self.choices.iter().map(|c| c.effect.clone()).collect()
         };
-        
-        tracker.initialize_achievements();
-        tracker
+
+        RandomEvent {
+            event_type: self.event_type.clone(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            choice_a: label(0),
+            choice_b: label(1),
+            choice_c: label(2),
+            auto_resolve,
+            cash_impact: if auto_resolve { self.effect.cash } else { 0 },
+            reputation_impact: if auto_resolve { self.effect.reputation } else { 0 },
+            inventory_impact: Vec::new(),
+            duration_days: self.duration_days,
+            active: true,
+            choice_effects,
+        }
     }
+}
 
-    fn initialize_achievements(&mut self) {
-        self.achievements = vec![
-            // Progress milestones
-            Achievement::new(AchievementType::FirstSale, "First Sale", "Complete your first customer order", 1, 100),
-            Achievement::new(AchievementType::EarlyBird, "Early Bird", "Complete your first 10 orders", 10, 500),
-            Achievement::new(AchievementType::Entrepreneur, "Entrepreneur", "Accumulate $10,000 in cash", 10000, 1000),
-            Achievement::new(AchievementType::BusinessMogul, "Business Mogul", "Accumulate $50,000 in cash", 50000, 5000),
-            Achievement::new(AchievementType::Millionaire, "Millionaire", "Accumulate $1,000,000 in cash", 1000000, 50000),
-            
-            // Performance achievements
-            Achievement::new(AchievementType::PerfectWeek, "Perfect Week", "7 consecutive days with 100% order completion", 7, 2000),
-            Achievement::new(AchievementType::SpeedDemon, "Speed Demon", "Fulfill 5 orders in a single day", 5, 1500),
-            Achievement::new(AchievementType::Efficiency, "Efficiency Expert", "Maintain 90%+ success rate for 30 days", 30, 3000),
-            Achievement::new(AchievementType::MarketMaster, "Market Master", "Make purchases during 5 favorable market events", 5, 2500),
-            
-            // Reputation achievements
-            Achievement::new(AchievementType::LegendaryStatus, "Legendary Status", "Reach maximum 5-star reputation", 5, 2000),
-            Achievement::new(AchievementType::CustomerFavorite, "Customer Favorite", "Complete 100 customer orders", 100, 3000),
-            Achievement::new(AchievementType::TrustedSeller, "Trusted Seller", "Complete 500 customer orders", 500, 10000),
-            
-            // Seasonal achievements
-            Achievement::new(AchievementType::WinterWinner, "Winter Winner", "Earn $5,000 profit during Winter season", 5000, 2000),
-            Achievement::new(AchievementType::SeasonVeteran, "Season Veteran", "Experience all 4 seasons", 4, 3000),
-            Achievement::new(AchievementType::EventSurvivor, "Event Survivor", "Survive 10 market events", 10, 2500),
-            
-            // Inventory achievements
-            Achievement::new(AchievementType::Collector, "Collector", "Own 100+ gift cards simultaneously", 100, 2000),
-            Achievement::new(AchievementType::DiversifiedPortfolio, "Diversified Portfolio", "Own cards from all 5 retailers", 5, 1000),
-            Achievement::new(AchievementType::QuickTurnaround, "Quick Turnaround", "Sell inventory within 3 days of purchase", 1, 1500),
-        ];
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EventCatalog {
+    #[serde(default)]
+    market_events: Vec<MarketEventDef>,
+    #[serde(default)]
+    random_events: Vec<RandomEventDef>,
+}
+
+impl Default for EventCatalog {
+    fn default() -> Self {
+        Self::builtin()
     }
+}
 
-    fn check_cash_achievements(&mut self, cash: u32, day: u32, activities: &mut Vec<String>) {
-        self.check_and_unlock(&AchievementType::Entrepreneur, cash, day, activities);
-        self.check_and_unlock(&AchievementType::BusinessMogul, cash, day, activities);
-        self.check_and_unlock(&AchievementType::Millionaire, cash, day, activities);
+impl EventCatalog {
+    fn builtin() -> Self {
+        Self::from_json(BUILTIN_EVENT_CATALOG).expect("built-in event catalog is valid")
     }
 
-    fn check_order_achievements(&mut self, total_orders: u32, reputation: u8, day: u32, activities: &mut Vec<String>) {
-        self.check_and_unlock(&AchievementType::FirstSale, total_orders, day, activities);
-        self.check_and_unlock(&AchievementType::EarlyBird, total_orders, day, activities);
-        self.check_and_unlock(&AchievementType::CustomerFavorite, total_orders, day, activities);
-        self.check_and_unlock(&AchievementType::TrustedSeller, total_orders, day, activities);
-        self.check_and_unlock(&AchievementType::LegendaryStatus, reputation as u32, day, activities);
+    fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        let catalog: EventCatalog = serde_json::from_str(json)?;
+        catalog.validate()?;
+        Ok(catalog)
     }
 
-    fn check_inventory_achievements(&mut self, inventory: &[InventoryItem], day: u32, activities: &mut Vec<String>) {
-        // Check collector achievement
-        let total_cards: u32 = inventory.iter().map(|item| item.quantity).sum();
-        self.check_and_unlock(&AchievementType::Collector, total_cards, day, activities);
-        
-        // Check diversified portfolio
-        let retailers: std::collections::HashSet<&str> = inventory.iter()
-            .map(|item| item.card.retailer.as_str())
-            .collect();
-        self.check_and_unlock(&AchievementType::DiversifiedPortfolio, retailers.len() as u32, day, activities);
+    fn load(filename: &str) -> Result<Self, Box<dyn Error>> {
+        Self::from_json(&fs::read_to_string(filename)?)
     }
 
-    fn check_seasonal_achievements(&mut self, season: &Season, winter_profit: i32, day: u32, activities: &mut Vec<String>) {
-        // Track seasons survived
-        if !self.seasons_survived.contains(season) {
-            self.seasons_survived.push(season.clone());
+    // Custom catalog if the file exists, otherwise the built-in one. Returns a warning
+    // message when a custom catalog was found but could not be used.
+    fn load_or_builtin(filename: &str) -> (Self, Option<String>) {
+        if !std::path::Path::new(filename).exists() {
+            return (Self::builtin(), None);
         }
-        self.check_and_unlock(&AchievementType::SeasonVeteran, self.seasons_survived.len() as u32, day, activities);
-        
-        // Check winter winner
-        if matches!(season, Season::Winter) && winter_profit >= 5000 {
-            self.check_and_unlock(&AchievementType::WinterWinner, winter_profit as u32, day, activities);
+        match Self::load(filename) {
+            Ok(catalog) => (catalog, None),
+            Err(err) => (Self::builtin(), Some(format!("⚠️ Ignoring {}: {}", filename, err))),
         }
     }
 
-    fn record_order_completion(&mut self, day: u32) {
-        self.orders_today += 1;
-        
-        // Check speed demon (5 orders in one day)
-        if self.orders_today >= 5 {
-            if let Some(achievement) = self.achievements.iter_mut()
-                .find(|a| matches!(a.achievement_type, AchievementType::SpeedDemon) && !a.unlocked) {
-                achievement.unlock(day);
-                self.total_unlocked += 1;
-                self.recent_unlock = Some(achievement.name.clone());
+    fn validate(&self) -> Result<(), String> {
+        for event in &self.random_events {
+            if event.choices.len() > 3 {
+                return Err(format!("event '{}' has {} choices (max 3)", event.title, event.choices.len()));
+            }
+            let effects = std::iter::once(&event.effect).chain(event.choices.iter().map(|choice| &choice.effect));
+            for change in effects.flat_map(|effect| &effect.inventory) {
+                if retailer(&change.retailer).is_none() {
+                    return Err(format!("event '{}' refers to unknown retailer '{}'", event.title, change.retailer));
+                }
+            }
+        }
+        for event in &self.market_events {
+            if event.duration_days == 0 {
+                return Err(format!("market event '{}' must last at least 1 day", event.name));
+            }
+            if let Some(id) = &event.retailer
+                && retailer(id).is_none() {
+                return Err(format!("market event '{}' refers to unknown retailer '{}'", event.name, id));
             }
         }
+        Ok(())
     }
 
-    fn record_market_purchase(&mut self, price_multiplier: f32, day: u32, activities: &mut Vec<String>) {
-        // Favorable market = prices 10%+ below normal
-        if price_multiplier <= 0.9 {
-            self.favorable_market_purchases += 1;
-            self.check_and_unlock(&AchievementType::MarketMaster, self.favorable_market_purchases, day, activities);
-        }
+    fn pick_market_event(&self, context: &EventContext, rng: &mut GameRng) -> Option<&MarketEventDef> {
+        pick_weighted(
+            self.market_events.iter()
+                .filter(|e| e.conditions.is_met(context))
+                .map(|e| (e, e.weight)),
+            rng,
+        )
     }
 
-    fn process_daily_achievements(&mut self, orders_completed_today: u32, orders_expired_today: u32, analytics: &BusinessAnalytics, day: u32) {
-        // Reset daily counters
-        self.orders_today = 0;
+    fn pick_random_event(&self, context: &EventContext, rng: &mut GameRng) -> Option<&RandomEventDef> {
+        pick_weighted(
+            self.random_events.iter()
+                .filter(|e| e.conditions.is_met(context))
+                .map(|e| (e, e.weight)),
+            rng,
+        )
+    }
+}
 
-        // Track perfect days
-        if orders_expired_today == 0 && orders_completed_today > 0 {
-            self.consecutive_perfect_days += 1;
-        } else {
-            self.consecutive_perfect_days = 0;
-        }
+fn pick_weighted<'a, T>(candidates: impl Iterator<Item = (&'a T, u32)>, rng: &mut GameRng) -> Option<&'a T> {
+    let candidates: Vec<(&T, u32)> = candidates.filter(|(_, weight)| *weight > 0).collect();
+    let total: u32 = candidates.iter().map(|(_, weight)| weight).sum();
+    if total == 0 {
+        return None;
+    }
 
-        // Check perfect week
-        if self.consecutive_perfect_days >= 7 {
-            if let Some(achievement) = self.achievements.iter_mut()
-                .find(|a| matches!(a.achievement_type, AchievementType::PerfectWeek) && !a.unlocked) {
-                achievement.unlock(day);
-                self.total_unlocked += 1;
-                self.recent_unlock = Some(achievement.name.clone());
-            }
+    let mut roll = rng.range(0, total - 1);
+    for (item, weight) in candidates {
+        if roll < weight {
+            return Some(item);
         }
+        roll -= weight;
+    }
+    None
+}
 
-        // Track efficiency
-        let total_orders = analytics.orders_completed + analytics.orders_expired;
-        if total_orders > 0 {
-            let success_rate = analytics.orders_completed as f32 / total_orders as f32;
-            if success_rate >= 0.9 {
-                self.consecutive_efficiency_days += 1;
-            } else {
-                self.consecutive_efficiency_days = 0;
-            }
-        }
+// Effects of a resolved random event, applied to the business by GameData::apply_event_outcome
+#[derive(Debug, Clone, Default)]
+struct EventOutcome {
+    title: String,
+    cash: i32,
+    reputation: i8,
+    inventory: Vec<(String, i32)>, // (retailer, quantity change)
+    modifiers: Vec<TempModifier>,
+}
 
-        // Check efficiency achievement
-        if self.consecutive_efficiency_days >= 30 {
-            if let Some(achievement) = self.achievements.iter_mut()
-                .find(|a| matches!(a.achievement_type, AchievementType::Efficiency) && !a.unlocked) {
-                achievement.unlock(day);
-                self.total_unlocked += 1;
-                self.recent_unlock = Some(achievement.name.clone());
-            }
+#[derive(Debug, Serialize, Deserialize)]
+struct RandomEventManager {
+    active_event: Option<RandomEvent>,
+    next_event_in_days: u32,
+    event_history: Vec<String>, // Record of past events
+    player_choice_pending: bool,
+    choice_deadline: u32,       // Day when choice must be made
+    temp_modifiers: Vec<TempModifier>, // Temporary effects from events
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TempModifier {
+    name: String,
+    description: String,
+    price_multiplier: f32,      // Affects purchase prices
+    demand_multiplier: f32,     // Affects order frequency
+    reputation_protection: bool, // Prevents reputation loss
+    remaining_days: u32,
+}
+
+impl TempModifier {
+    fn age_day(&mut self) {
+        if self.remaining_days > 0 {
+            self.remaining_days -= 1;
         }
     }
-
-    fn record_event_survival(&mut self, day: u32, activities: &mut Vec<String>) {
-        self.events_survived += 1;
-        self.check_and_unlock(&AchievementType::EventSurvivor, self.events_survived, day, activities);
+    
+    fn is_expired(&self) -> bool {
+        self.remaining_days == 0
     }
+}
 
-    fn check_and_unlock(&mut self, achievement_type: &AchievementType, progress: u32, day: u32, activities: &mut Vec<String>) {
-        if let Some(achievement) = self.achievements.iter_mut()
-            .find(|a| a.achievement_type == *achievement_type && !a.unlocked) {
-            
-            achievement.progress = achievement.progress.max(progress);
-            
-            if progress >= achievement.target {
-                achievement.unlock(day);
-                self.total_unlocked += 1;
-                self.recent_unlock = Some(achievement.name.clone());
-                
-                activities.insert(0, format!(
-                    "🏆 Achievement Unlocked: {} (+${})", 
-                    achievement.name, achievement.reward_cash
-                ));
-            }
+// What the business spends money on besides stock
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+enum ExpenseCategory {
+    Rent,
+    Wages,
+    StorageUpkeep,
+    StorageUpgrades,
+    PaymentFees,
+}
+
+impl ExpenseCategory {
+    fn display(&self) -> &str {
+        match self {
+            ExpenseCategory::Rent => "Rent",
+            ExpenseCategory::Wages => "Wages",
+            ExpenseCategory::StorageUpkeep => "Storage Upkeep",
+            ExpenseCategory::StorageUpgrades => "Storage Upgrades",
+            ExpenseCategory::PaymentFees => "Payment Fees",
         }
     }
+}
 
-    fn get_recent_unlock(&mut self) -> Option<String> {
-        self.recent_unlock.take()
-    }
+#[derive(Debug)]
+enum ExpenseSchedule {
+    Daily,
+    Weekly, // Charged when a new week starts (days 8, 15, ...)
+}
 
-    fn get_unlocked_achievements(&self) -> Vec<&Achievement> {
-        self.achievements.iter().filter(|a| a.unlocked).collect()
+// Fixed overhead charged on a schedule; scaled by difficulty
+#[derive(Debug)]
+struct OperatingExpense {
+    category: ExpenseCategory,
+    amount: u32,
+    schedule: ExpenseSchedule,
+}
+
+const OPERATING_EXPENSES: &[OperatingExpense] = &[
+    OperatingExpense { category: ExpenseCategory::Rent, amount: 70, schedule: ExpenseSchedule::Weekly },
+    OperatingExpense { category: ExpenseCategory::Wages, amount: 5, schedule: ExpenseSchedule::Daily },
+];
+
+// Card processor's cut of each day's sales revenue
+const PAYMENT_FEE_RATE: f32 = 0.02;
+
+// A level of storage space; level 0 is what every business starts with
+#[derive(Debug)]
+struct StorageTier {
+    name: &'static str,
+    capacity: u32,      // Cards that fit
+    upgrade_cost: u32,  // One-off price to move up to this tier
+    daily_upkeep: u32,  // Rent and running costs charged every day
+}
+
+const STORAGE_TIERS: &[StorageTier] = &[
+    StorageTier { name: "Back Room", capacity: 100, upgrade_cost: 0, daily_upkeep: 0 },
+    StorageTier { name: "Storage Unit", capacity: 250, upgrade_cost: 1500, daily_upkeep: 15 },
+    StorageTier { name: "Small Warehouse", capacity: 600, upgrade_cost: 5000, daily_upkeep: 40 },
+    StorageTier { name: "Distribution Center", capacity: 1500, upgrade_cost: 15000, daily_upkeep: 100 },
+];
+
+// Win conditions from the design doc; the player picks which ones must be reached to win
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+enum GameGoal {
+    NetWorth,    // $50,000 in cash and inventory
+    Reputation,  // 5-star rating
+    Survival,    // Stay in business for 365 days
+    MarketShare, // Win 25% of the local market
+}
+
+const NET_WORTH_GOAL: u32 = 50_000;
+const SURVIVAL_GOAL_DAYS: u32 = 365;
+const MARKET_SHARE_GOAL: f32 = 0.25;
+
+// Failure conditions
+const REPUTATION_LOSS_DAYS: u32 = 30;       // Days in a row below 2 stars
+const INVENTORY_CRISIS_SHARE: f32 = 0.5;    // Share of stock lost to expiry within 30 days
+const INVENTORY_CRISIS_MIN_CARDS: u32 = 10; // A handful of stray cards isn't a crisis
+
+fn default_active_goals() -> Vec<GameGoal> {
+    vec![GameGoal::NetWorth]
+}
+
+impl GameGoal {
+    const ALL: [GameGoal; 4] = [GameGoal::NetWorth, GameGoal::Reputation, GameGoal::Survival, GameGoal::MarketShare];
+
+    fn display(&self) -> &str {
+        match self {
+            GameGoal::NetWorth => "Reach $50,000 net worth",
+            GameGoal::Reputation => "Earn a 5-star reputation",
+            GameGoal::Survival => "Survive 365 days",
+            GameGoal::MarketShare => "Win 25% of the local market",
+        }
     }
 
-    fn get_in_progress_achievements(&self) -> Vec<&Achievement> {
-        self.achievements.iter().filter(|a| !a.unlocked && a.progress > 0).collect()
+    fn is_met(&self, game: &GameData) -> bool {
+        match self {
+            GameGoal::NetWorth => game.net_worth() >= NET_WORTH_GOAL,
+            GameGoal::Reputation => game.reputation >= 5,
+            GameGoal::Survival => game.day > SURVIVAL_GOAL_DAYS,
+            GameGoal::MarketShare => game.analytics.market_history.len() >= MARKET_SHARE_MIN_DAYS
+                && game.market_share() >= MARKET_SHARE_GOAL,
+        }
     }
 
-    fn calculate_total_rewards(&self) -> u32 {
-        self.achievements.iter()
-            .filter(|a| a.unlocked)
-            .map(|a| a.reward_cash)
-            .sum()
+    fn progress(&self, game: &GameData) -> String {
+        match self {
+            GameGoal::NetWorth => format!("${} / ${}", game.net_worth(), NET_WORTH_GOAL),
+            GameGoal::Reputation => format!("{} / 5 stars", game.reputation),
+            GameGoal::Survival => format!("day {} / {}", game.day, SURVIVAL_GOAL_DAYS),
+            GameGoal::MarketShare => format!("{:.0}% / {:.0}%", game.market_share() * 100.0, MARKET_SHARE_GOAL * 100.0),
+        }
     }
 }
 
-impl RandomEvent {
-    fn new_auto_event(event_type: RandomEventType, title: &str, description: &str, cash: i32, reputation: i8, duration: u32) -> Self {
-        Self {
-            event_type,
-            title: title.to_string(),
-            description: description.to_string(),
-            choice_a: None,
-            choice_b: None,
-            choice_c: None,
-            auto_resolve: true,
-            cash_impact: cash,
-            reputation_impact: reputation,
-            inventory_impact: Vec::new(),
-            duration_days: duration,
-            active: true,
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+enum GameOutcome {
+    Victory,         // Every active goal reached
+    Bankruptcy,      // Out of cash, losing money and nothing left to sell
+    ReputationLoss,  // Below 2 stars for 30 days
+    InventoryCrisis, // Half the stock expired within a month
+}
+
+impl GameOutcome {
+    fn display(&self) -> &str {
+        match self {
+            GameOutcome::Victory => "🏆 Victory!",
+            GameOutcome::Bankruptcy => "💸 Bankrupt",
+            GameOutcome::ReputationLoss => "👎 Customers Gave Up",
+            GameOutcome::InventoryCrisis => "💀 Inventory Crisis",
         }
     }
 
-    fn new_choice_event(event_type: RandomEventType, title: &str, description: &str, choice_a: &str, choice_b: &str, choice_c: Option<&str>) -> Self {
-        Self {
-            event_type,
-            title: title.to_string(),
-            description: description.to_string(),
-            choice_a: Some(choice_a.to_string()),
-            choice_b: Some(choice_b.to_string()),
-            choice_c: choice_c.map(|s| s.to_string()),
-            auto_resolve: false,
-            cash_impact: 0,
-            reputation_impact: 0,
-            inventory_impact: Vec::new(),
-            duration_days: 1,
-            active: true,
+    fn description(&self) -> &str {
+        match self {
+            GameOutcome::Victory => "You reached every goal you set for your gift card empire.",
+            GameOutcome::Bankruptcy => "The cash ran out, the bills kept coming and there was nothing left to sell.",
+            GameOutcome::ReputationLoss => "A month below two stars and customers stopped coming back.",
+            GameOutcome::InventoryCrisis => "Half your stock expired on the shelves in a single month.",
         }
     }
 
-    fn apply_choice(&mut self, choice: usize) -> (i32, i8, Vec<TempModifier>) {
-        let mut temp_modifiers = Vec::new();
-        
-        match (&self.event_type, choice) {
-            // Business Offer choices
-            (RandomEventType::BusinessOffer, 0) => {
-                // Choice A: Accept partnership - get discount modifier
-                self.cash_impact = -1000;
-                self.reputation_impact = 0;
-                temp_modifiers.push(TempModifier {
-                    name: "Business Partnership".to_string(),
-                    description: "10% discount on purchases".to_string(),
-                    price_multiplier: 0.9,
-                    demand_multiplier: 1.0,
-                    reputation_protection: false,
-                    remaining_days: 14,
-                });
-            },
-            (RandomEventType::BusinessOffer, 1) => {
-                // Choice B: Go solo - get reputation boost
-                self.cash_impact = 0;
-                self.reputation_impact = 1;
-            },
+    fn is_victory(&self) -> bool {
+        matches!(self, GameOutcome::Victory)
+    }
+}
 
-            // Charity Request choices
-            (RandomEventType::CharityRequest, 0) => {
-                // Choice A: Donate money
-                self.cash_impact = -500;
-                self.reputation_impact = 2;
-            },
-            (RandomEventType::CharityRequest, 1) => {
-                // Choice B: Donate cards (if possible)
-                self.cash_impact = 0;
-                self.reputation_impact = 1;
-                self.inventory_impact.push(("Amazon".to_string(), -2));
-            },
-            (RandomEventType::CharityRequest, 2) => {
-                // Choice C: Decline
-                self.cash_impact = 0;
-                self.reputation_impact = -1;
-            },
+// How and when the run ended
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GameOver {
+    outcome: GameOutcome,
+    day: u32,
+}
 
-            // Competitor Meeting choices
-            (RandomEventType::CompetitorMeeting, 0) => {
-                // Choice A: Collaborate
-                self.cash_impact = 0;
-                self.reputation_impact = 0;
-                temp_modifiers.push(TempModifier {
-                    name: "Market Collaboration".to_string(),
-                    description: "Increased customer demand".to_string(),
-                    price_multiplier: 1.0,
-                    demand_multiplier: 1.3,
-                    reputation_protection: false,
-                    remaining_days: 10,
-                });
-            },
-            (RandomEventType::CompetitorMeeting, 1) => {
-                // Choice B: Compete aggressively
-                self.cash_impact = -200;
-                self.reputation_impact = 0;
-                temp_modifiers.push(TempModifier {
-                    name: "Price War".to_string(),
-                    description: "Cheaper purchases but lower demand".to_string(),
-                    price_multiplier: 0.85,
-                    demand_multiplier: 0.8,
-                    reputation_protection: false,
-                    remaining_days: 7,
-                });
-            },
+// A retailer's wholesale prices and demand on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PricePoint {
+    day: u32,
+    costs: BTreeMap<u32, u32>, // Wholesale price by denomination
+    demand_multiplier: f32,
+}
 
-            // Default case
-            _ => {
-                self.cash_impact = 0;
-                self.reputation_impact = 0;
-            }
-        }
+const PRICE_HISTORY_DAYS: usize = 30;
+
+// Lowest, average and highest of a series of prices
+fn price_range(prices: &[u32]) -> Option<(u32, f32, u32)> {
+    let min = *prices.iter().min()?;
+    let max = *prices.iter().max()?;
+    let average = prices.iter().sum::<u32>() as f32 / prices.len() as f32;
+    Some((min, average, max))
+}
+
+const FORECAST_DAYS: u32 = 14;
+const FORECAST_PRIOR_DAYS: f32 = 7.0;           // Weight of the catalog guess against observed orders
+const FORECAST_PRICE_SWING: f32 = 0.05;         // Price change worth timing a purchase around
 
-        (self.cash_impact, self.reputation_impact, temp_modifiers)
+// Expected order volume and wholesale prices for one retailer over the coming days
+#[derive(Debug, Clone)]
+struct RetailerForecast {
+    retailer: String,
+    daily_orders: Vec<f32>,      // Cards customers should order, day by day starting tomorrow
+    price_multipliers: Vec<f32>, // Wholesale price relative to today, day by day starting tomorrow
+    usable_stock: u32,           // Cards on hand we can expect to sell before they expire
+    expiring: u32,               // Cards on hand that expire inside the forecast window
+}
+
+impl RetailerForecast {
+    fn expected_orders(&self, days: usize) -> f32 {
+        self.daily_orders.iter().take(days).sum()
     }
-    
-    fn get_choices(&self) -> Vec<&str> {
-        let mut choices = Vec::new();
-        if let Some(choice) = &self.choice_a {
-            choices.push(choice.as_str());
+
+    // Cards to buy to cover expected orders over the whole window
+    fn suggested_purchase(&self) -> u32 {
+        (self.expected_orders(self.daily_orders.len()).ceil() as u32).saturating_sub(self.usable_stock)
+    }
+
+    // Day offset (1 = tomorrow) and price of the cheapest day ahead, if it beats today by enough to wait
+    fn cheaper_day_ahead(&self) -> Option<(u32, f32)> {
+        self.price_multipliers.iter()
+            .copied()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .filter(|(_, multiplier)| *multiplier < 1.0 - FORECAST_PRICE_SWING)
+            .map(|(offset, multiplier)| (offset as u32 + 1, multiplier))
+    }
+
+    fn price_rises_ahead(&self) -> bool {
+        self.price_multipliers.iter().any(|multiplier| *multiplier > 1.0 + FORECAST_PRICE_SWING)
+    }
+
+    fn recommendation(&self, today: u32) -> String {
+        let quantity = self.suggested_purchase();
+        if quantity == 0 {
+            return "Stocked".to_string();
         }
-        if let Some(choice) = &self.choice_b {
-            choices.push(choice.as_str());
+        match self.cheaper_day_ahead() {
+            Some((offset, multiplier)) if offset > 1 => format!(
+                "Buy {} on day {} ({:.0}% cheaper)", quantity, today + offset, (1.0 - multiplier) * 100.0
+            ),
+            _ if self.price_rises_ahead() => format!("Buy {} now, prices rising", quantity),
+            _ => format!("Buy {}", quantity),
         }
-        if let Some(choice) = &self.choice_c {
-            choices.push(choice.as_str());
+    }
+}
+
+// Our asking price for walk-in customers on one card type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RetailPrice {
+    retailer: String,
+    denomination: u32,
+    price: u32,
+}
+
+const BUSINESS_HOURS: std::ops::Range<u8> = 9..21; // Hours the shop is open to walk-ins
+const WALK_IN_BASE_CHANCE: f32 = 0.01;  // Hourly chance of selling a card priced at its market value
+const WALK_IN_PRICE_ELASTICITY: f32 = 4.0;
+const WALK_IN_MAX_PRICE_FACTOR: f32 = 3.0; // Bargains only pull in so many extra customers
+// Shoppers in town for each one who buys from us at market value with a 3-star reputation. Covers
+// the best price and reputation factors, so every walk-in sale comes from a recorded shopper.
+const WALK_IN_SHOPPERS_PER_SALE: f32 = 5.0;
+
+// Hourly chance a shopper comes looking for this card at normal demand: popular cards bring more
+fn walk_in_shopper_chance(product: &CardProduct) -> f32 {
+    WALK_IN_BASE_CHANCE * WALK_IN_SHOPPERS_PER_SALE * product.demand_weight as f32 / 3.0
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GameData {
+    cash: u32,
+    reputation: u8, // 1-5 stars
+    day: u32,
+    hour: u8,
+    minute: u8,
+    recent_activities: Vec<String>,
+    inventory: Vec<InventoryItem>,
+    customer_orders: VecDeque<CustomerOrder>,
+    next_order_id: u32,
+    analytics: BusinessAnalytics,
+    market_conditions: MarketConditions,
+    achievements: AchievementTracker,
+    random_events: RandomEventManager,
+    #[serde(default)]
+    rng: GameRng,
+    #[serde(default)]
+    difficulty: Difficulty,
+    #[serde(skip)]
+    event_catalog: EventCatalog, // Loaded at startup, not part of the save
+    #[serde(default)]
+    cost_basis_policy: CostBasisPolicy,
+    #[serde(default)]
+    market: Market,
+    #[serde(default)]
+    decline_strain: u32, // Declines and missed backorders since the last reputation hit they caused
+    #[serde(default)]
+    customers: Vec<Customer>,
+    #[serde(default)]
+    auto_claim_rewards: bool, // Pay achievement rewards as soon as they unlock
+    #[serde(default)]
+    storage_level: usize,     // Index into STORAGE_TIERS
+    #[serde(default)]
+    day_start_cash: Option<u32>, // Cash when the current day began, for the daily cash flow
+    #[serde(default = "default_active_goals")]
+    active_goals: Vec<GameGoal>,        // Goals that must all be reached to win
+    #[serde(default)]
+    goals_reached: Vec<(GameGoal, u32)>, // Goal and the day it was first reached
+    #[serde(default)]
+    low_reputation_days: u32,           // Days in a row below 2 stars
+    #[serde(default)]
+    game_over: Option<GameOver>,
+    #[serde(default)]
+    competitors: Vec<Competitor>,
+    #[serde(default)]
+    retail_prices: Vec<RetailPrice>, // Asking prices we've set; others sell at face value
+    #[serde(default)]
+    price_history: BTreeMap<String, Vec<PricePoint>>, // Daily wholesale prices by retailer, last 30 days
+}
+
+// Declining (or missing a mostly-shipped backorder) is cheaper than letting an order expire:
+// only every Nth one costs a star
+const DECLINES_PER_REPUTATION_POINT: u32 = 3;
+
+const MARKET_SHARE_WINDOW_DAYS: usize = 30;
+const MARKET_SHARE_MIN_DAYS: usize = 7; // Market days on record before the share counts toward goals
+const MARKET_SIZE_REPUTATION: u8 = 3;   // The town's order volume is sized for an average shop, not ours
+
+// One day of the local gift card market: what customers wanted and who sold it to them.
+// Demand is sized from the season and market events; the orders and walk-ins we see are the
+// part of it that reaches us and set a floor on it, so it can't be inflated by dumping stock.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MarketDay {
+    day: u32,
+    demand: BTreeMap<String, u32>,      // Cards wanted locally, by retailer (set when the day closes)
+    our_sales: BTreeMap<String, u32>,   // Cards we sold to local customers that day, by retailer
+    rival_sales: BTreeMap<String, u32>, // Cards sold by each rival, by rival name
+    #[serde(default)]
+    ordered: BTreeMap<String, u32>,     // Cards asked for in customer orders placed that day, by retailer
+    #[serde(default)]
+    walk_ins: BTreeMap<String, u32>,    // Walk-in shoppers looking for a card, by retailer
+    #[serde(default)]
+    rival_retailer_sales: BTreeMap<String, u32>, // Cards sold by all rivals, by retailer
+}
+
+impl MarketDay {
+    fn total_demand(&self) -> u32 {
+        self.demand.values().sum()
+    }
+
+    fn total_our_sales(&self) -> u32 {
+        self.our_sales.values().sum()
+    }
+
+    // Sales filling orders placed on earlier days can push a single day past its own demand
+    fn share(&self) -> f32 {
+        match self.total_demand() {
+            0 => 0.0,
+            demand => (self.total_our_sales() as f32 / demand as f32).min(1.0),
         }
-        choices
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct BusinessAnalytics {
+    total_revenue: u32,
+    total_purchases: u32,
+    orders_completed: u32,
+    orders_expired: u32,
+    best_day_revenue: u32,
+    cards_sold: u32,
+    cards_expired: u32,
+    daily_revenues: Vec<u32>, // Track daily performance
+    profit_margins: Vec<f32>, // Track efficiency over time
+    #[serde(default)]
+    cost_of_goods_sold: u32,  // Actual lot cost of every card sold
+    #[serde(default)]
+    orders_declined: u32,     // Declined by us or lost when a customer walked away
+    #[serde(default)]
+    achievement_rewards: u32, // Reward cash claimed from achievements (not sales revenue)
+    #[serde(default)]
+    expenses: BTreeMap<ExpenseCategory, u32>, // Operating expenses paid, by category
+    #[serde(default)]
+    unpaid_expenses: u32,     // Bills we couldn't cover when they came due
+    #[serde(default)]
+    daily_cash_flow: Vec<i32>, // Net change in cash per day, last 30 days
+    #[serde(default)]
+    daily_expired_cards: Vec<u32>, // Cards lost to expiry per day, last 30 days
+    #[serde(default)]
+    market_today: MarketDay,        // Sales so far today; sized and closed when the day ends
+    #[serde(default)]
+    market_history: Vec<MarketDay>, // Closed market days, last 30
+    #[serde(default)]
+    walk_in_cards_sold: u32,        // Cards sold to walk-in customers at our own prices
+}
 
-impl RandomEventManager {
+impl BusinessAnalytics {
     fn new() -> Self {
         Self {
-            active_event: None,
-            next_event_in_days: 3 + (1 % 5), // Next event in 3-7 days
-            event_history: Vec::new(),
-            player_choice_pending: false,
-            choice_deadline: 0,
-            temp_modifiers: Vec::new(),
+            total_revenue: 0,
+            total_purchases: 0,
+            orders_completed: 0,
+            orders_expired: 0,
+            best_day_revenue: 0,
+            cards_sold: 0,
+            cards_expired: 0,
+            daily_revenues: vec![0], // Start with day 1
+            profit_margins: Vec::new(),
+            cost_of_goods_sold: 0,
+            orders_declined: 0,
+            achievement_rewards: 0,
+            expenses: BTreeMap::new(),
+            unpaid_expenses: 0,
+            daily_cash_flow: Vec::new(),
+            daily_expired_cards: vec![0],
+            market_today: MarketDay::default(),
+            market_history: Vec::new(),
+            walk_in_cards_sold: 0,
         }
     }
 
-    fn process_daily_events(&mut self, day: u32, activities: &mut Vec<String>) -> Option<RandomEvent> {
-        // Age temporary modifiers
-        self.temp_modifiers.retain_mut(|modifier| {
-            modifier.age_day();
-            if modifier.is_expired() {
-                activities.insert(0, format!("📅 {} effect has ended", modifier.name));
-                false
-            } else {
-                true
-            }
-        });
-
-        // Check for choice deadline
-        if self.player_choice_pending && day >= self.choice_deadline {
-            // Force auto-resolve if player didn't choose
-            if let Some(ref mut event) = self.active_event {
-                let (cash, reputation, modifiers) = event.apply_choice(0); // Default to first choice
-                self.temp_modifiers.extend(modifiers);
-                activities.insert(0, format!("⏰ {} auto-resolved (no choice made)", event.title));
-                
-                self.player_choice_pending = false;
-                self.active_event = None;
-            }
-        }
+    fn record_purchase(&mut self, amount: u32) {
+        self.total_purchases += amount;
+    }
 
-        // Check for new events
-        if self.active_event.is_none() && self.next_event_in_days > 0 {
-            self.next_event_in_days -= 1;
-            None
-        } else if self.active_event.is_none() && self.next_event_in_days == 0 {
-            let mut new_event = self.generate_random_event(day);
-            activities.insert(0, format!("🎲 Random event: {}", new_event.title));
-            
-            if new_event.auto_resolve {
-                // Auto-resolve immediate events
-                let (cash, reputation, modifiers) = new_event.apply_choice(0);
-                self.temp_modifiers.extend(modifiers);
-                self.next_event_in_days = 3 + (day % 5); // Schedule next event
-                None
-            } else {
-                // Set up choice event
-                self.player_choice_pending = true;
-                self.choice_deadline = day + 2; // 2 days to choose
-                self.next_event_in_days = 3 + (day % 5); // Schedule next event
-                Some(new_event)
-            }
-        } else {
-            None
-        }
+    fn record_sale(&mut self, revenue: u32, cost: u32, cards_sold: u32) {
+        self.orders_completed += 1;
+        self.record_card_sale(revenue, cost, cards_sold);
     }
 
-    fn generate_random_event(&mut self, day: u32) -> RandomEvent {
-        let event_type = day % 15; // 15 different event types
+    // Sale of cards outside a customer order (e.g. dumping stock at a discount)
+    fn record_card_sale(&mut self, revenue: u32, cost: u32, cards_sold: u32) {
+        self.total_revenue += revenue;
+        self.cards_sold += cards_sold;
+        self.cost_of_goods_sold += cost;
         
-        let event = match event_type {
-            0 => RandomEvent::new_auto_event(
-                RandomEventType::LoyalCustomer,
-                "Loyal Customer Returns",
-                "A satisfied customer wants to buy $2000 worth of gift cards at premium prices!",
-                2000,
-                1,
-                1
-            ),
-            1 => RandomEvent::new_auto_event(
-                RandomEventType::SupplierDiscount,
-                "Supplier Discount",
-                "Your supplier offers 15% off your next 3 purchases due to good relationship!",
-                0,
-                0,
-                1
-            ),
-            2 => RandomEvent::new_auto_event(
-                RandomEventType::MediaAttention,
-                "Positive Media Coverage",
-                "Local news features your business! Reputation increases and more customers arrive.",
-                500,
-                1,
-                3
-            ),
-            3 => RandomEvent::new_auto_event(
-                RandomEventType::LuckyFind,
-                "Inventory Audit Bonus",
-                "During inventory count, you discover some cards are worth more than expected!",
-                800,
-                0,
-                1
-            ),
-            4 => RandomEvent::new_auto_event(
-                RandomEventType::TechGlitch,
-                "Competitor System Down",
-                "Major online competitor experiences technical issues. Customers flock to you!",
-                0,
-                0,
-                2
-            ),
-            5 => RandomEvent::new_auto_event(
-                RandomEventType::CardTheft,
-                "Security Incident",
-                "Unfortunately, some inventory was stolen. Insurance covers part of the loss.",
-                -300,
-                -1,
-                1
-            ),
-            6 => RandomEvent::new_auto_event(
-                RandomEventType::CustomerComplaint,
-                "Customer Complaint",
-                "An unsatisfied customer posts negative reviews. You compensate to maintain reputation.",
-                -400,
-                -1,
-                1
-            ),
-            7 => RandomEvent::new_auto_event(
-                RandomEventType::SupplierIssue,
-                "Supplier Price Increase",
-                "Your main supplier raises prices due to increased demand. Costs go up temporarily.",
-                0,
-                0,
-                5
-            ),
-            8 => RandomEvent::new_auto_event(
-                RandomEventType::MarketCrash,
-                "Market Downturn",
-                "Economic uncertainty affects gift card values. Customer demand drops temporarily.",
-                0,
-                0,
-                4
-            ),
-            9 => RandomEvent::new_auto_event(
-                RandomEventType::RegulationChange,
-                "New Regulations",
-                "Government introduces new gift card regulations. Compliance costs required.",
-                -600,
-                0,
-                1
-            ),
-            10 => RandomEvent::new_choice_event(
-                RandomEventType::BusinessOffer,
-                "Partnership Proposal",
-                "Another gift card business proposes a partnership. Split costs but share profits.",
-                "Accept partnership (-$1000, get purchase discount)",
-                "Decline and stay independent (+reputation)",
-                None
-            ),
-            11 => RandomEvent::new_choice_event(
-                RandomEventType::CharityRequest,
-                "Charity Fundraiser",
-                "Local charity asks for donation. Good for reputation but costs money or inventory.",
-                "Donate $500 cash (++reputation)",
-                "Donate 2 Amazon cards (+reputation)",
-                Some("Politely decline (-reputation)")
-            ),
-            12 => RandomEvent::new_auto_event(
-                RandomEventType::InventoryAudit,
-                "Surprise Inventory Check",
-                "Accounting review reveals minor discrepancies. Small penalty but processes improved.",
-                -200,
-                0,
-                1
-            ),
-            13 => RandomEvent::new_choice_event(
-                RandomEventType::CompetitorMeeting,
-                "Competitor Conference",
-                "Industry meeting with other gift card sellers. Choose your approach.",
-                "Collaborate for mutual benefit (+demand)",
-                "Compete aggressively (price war)",
-                None
-            ),
-            _ => RandomEvent::new_auto_event(
-                RandomEventType::CustomerSurvey,
-                "Customer Feedback Survey",
-                "Customer survey results show satisfaction with your service. Reputation boost!",
-                0,
-                1,
-                1
-            ),
-        };
-
-        // Schedule next event
-        self.next_event_in_days = 7 + (day % 14); // Next event in 7-20 days
+        // Calculate profit margin for this sale
+        if revenue > 0 {
+            let profit_margin = ((revenue as f32 - cost as f32) / revenue as f32) * 100.0;
+            self.profit_margins.push(profit_margin);
+        }
         
-        // Record in history
-        self.event_history.push(format!("Day {}: {}", day, event.title));
-        if self.event_history.len() > 10 {
-            self.event_history.remove(0); // Keep only last 10 events
+        // Update daily revenue
+        if let Some(today_revenue) = self.daily_revenues.last_mut() {
+            *today_revenue += revenue;
+            self.best_day_revenue = self.best_day_revenue.max(*today_revenue);
         }
+    }
 
-        // Set as active event
-        if event.auto_resolve {
-            // Will be processed immediately
-            event
-        } else {
-            // Store for player choice
-            self.active_event = Some(event.clone());
-            event
-        }
+    fn record_expired_order(&mut self) {
+        self.orders_expired += 1;
     }
 
-    fn make_choice(&mut self, choice: usize) -> Option<(i32, i8, Vec<TempModifier>)> {
-        if let Some(ref mut event) = self.active_event {
-            let result = event.apply_choice(choice);
-            self.player_choice_pending = false;
-            self.active_event = None;
-            Some(result)
-        } else {
-            None
-        }
+    fn record_declined_order(&mut self) {
+        self.orders_declined += 1;
     }
 
-    fn get_active_choice_event(&self) -> Option<&RandomEvent> {
-        if self.player_choice_pending {
-            self.active_event.as_ref()
-        } else {
-            None
-        }
+    fn record_achievement_reward(&mut self, amount: u32) {
+        self.achievement_rewards += amount;
     }
 
-    fn get_total_price_multiplier(&self) -> f32 {
-        self.temp_modifiers.iter()
-            .map(|m| m.price_multiplier)
-            .product()
+    fn record_expense(&mut self, category: ExpenseCategory, amount: u32) {
+        *self.expenses.entry(category).or_insert(0) += amount;
     }
 
-    fn get_total_demand_multiplier(&self) -> f32 {
-        self.temp_modifiers.iter()
-            .map(|m| m.demand_multiplier)
-            .product()
+    fn total_expenses(&self) -> u32 {
+        self.expenses.values().sum()
     }
 
-    fn has_reputation_protection(&self) -> bool {
-        self.temp_modifiers.iter()
-            .any(|m| m.reputation_protection)
+    fn record_daily_cash_flow(&mut self, net: i32) {
+        self.daily_cash_flow.push(net);
+        if self.daily_cash_flow.len() > 30 {
+            self.daily_cash_flow.remove(0);
+        }
     }
 
-    fn get_active_modifiers(&self) -> &[TempModifier] {
-        &self.temp_modifiers
+    // Net cash flow over the last `days` recorded days
+    fn recent_cash_flow(&self, days: usize) -> i32 {
+        self.daily_cash_flow.iter().rev().take(days).sum()
     }
-}
 
-impl GameData {
-    fn new() -> Self {
-        // Create some sample inventory for testing
-        let sample_inventory = vec![
-            InventoryItem::new(
-                GiftCard::new("Amazon", 25, 20, 45),
-                12
-            ),
-            InventoryItem::new(
-                GiftCard::new("Target", 50, 42, 30),
-                8
-            ),
-            InventoryItem::new(
-                GiftCard::new("Starbucks", 10, 8, 120),
-                15
-            ),
-            InventoryItem::new(
-                GiftCard::new("iTunes", 15, 12, 15),
-                3
-            ),
-            InventoryItem::new(
-                GiftCard::new("Walmart", 20, 17, 60),
-                6
-            ),
-        ];
+    fn record_expired_cards(&mut self, count: u32) {
+        self.cards_expired += count;
+        match self.daily_expired_cards.last_mut() {
+            Some(today_expired) => *today_expired += count,
+            None => self.daily_expired_cards.push(count),
+        }
+    }
 
-        let mut game_data = Self {
-            cash: 5000,
-            reputation: 3,
-            day: 1,
-            hour: 9,
-            minute: 0,
-            recent_activities: vec![
-                "Welcome to Gift Card Empire!".to_string(),
-                "Starting with $5,000 capital".to_string(),
-                "Visit the Market to buy your first cards".to_string(),
-            ],
-            inventory: sample_inventory,
-            customer_orders: VecDeque::new(),
-            next_order_id: 1000,
-            analytics: BusinessAnalytics::new(),
-            market_conditions: MarketConditions::new(),
-            achievements: AchievementTracker::new(),
-            random_events: RandomEventManager::new(),
-        };
+    fn record_market_sale(&mut self, retailer: &str, cards: u32) {
+        *self.market_today.our_sales.entry(retailer.to_string()).or_insert(0) += cards;
+    }
 
-        // Generate some initial customer orders
-        game_data.generate_random_order();
-        game_data.generate_random_order();
-        
-        game_data
+    fn record_order_demand(&mut self, retailer: &str, cards: u32) {
+        *self.market_today.ordered.entry(retailer.to_string()).or_insert(0) += cards;
     }
 
-    fn advance_time(&mut self, minutes: u8) {
-        self.minute += minutes;
-        if self.minute >= 60 {
-            self.hour += self.minute / 60;
-            self.minute = self.minute % 60;
-        }
-        
-        if self.hour >= 24 {
-            self.day += (self.hour / 24) as u32;
-            self.hour = self.hour % 24;
-            
-            // Process daily events when a new day starts
-            self.process_daily_events();
-        }
+    fn record_walk_in_demand(&mut self, retailer: &str) {
+        *self.market_today.walk_ins.entry(retailer.to_string()).or_insert(0) += 1;
     }
 
-    fn process_daily_events(&mut self) {
-        // Age all inventory by 1 day
-        for item in &mut self.inventory {
-            if item.card.days_until_expiration > 0 {
-                item.card.days_until_expiration -= 1;
-            }
-        }
-
-        // Remove expired cards and calculate losses
-        let mut expired_value = 0;
-        let mut expired_count = 0;
-        
-        self.inventory.retain(|item| {
-            if item.card.days_until_expiration == 0 {
-                expired_value += item.total_cost();
-                expired_count += item.quantity;
-                false
-            } else {
-                true
-            }
-        });
+    fn record_rival_sale(&mut self, rival: &str, retailer: &str, cards: u32) {
+        *self.market_today.rival_sales.entry(rival.to_string()).or_insert(0) += cards;
+        *self.market_today.rival_retailer_sales.entry(retailer.to_string()).or_insert(0) += cards;
+    }
 
-        if expired_count > 0 {
-            // Record expired cards in analytics
-            self.analytics.record_expired_cards(expired_count);
-            
-            self.recent_activities.insert(0, format!(
-                "❌ Lost {} cards worth ${} to expiration", 
-                expired_count, expired_value
-            ));
-            
-            // Keep only the last 10 activities
-            if self.recent_activities.len() > 10 {
-                self.recent_activities.truncate(10);
-            }
+    // Close the day's market with its expected size per retailer. Demand is that size, or the
+    // cards customers were seen asking for in orders and walk-ins if more turned up.
+    fn close_market_day(&mut self, day: u32, market_size: BTreeMap<String, u32>) {
+        let mut market_day = std::mem::take(&mut self.market_today);
+        market_day.day = day;
+        let mut demand = market_day.ordered.clone();
+        for (retailer, shoppers) in &market_day.walk_ins {
+            *demand.entry(retailer.clone()).or_insert(0) += shoppers;
         }
+        for (retailer, size) in market_size {
+            let wanted = demand.entry(retailer).or_insert(0);
+            *wanted = (*wanted).max(size);
+        }
+        market_day.demand = demand;
+        self.market_history.push(market_day);
+        if self.market_history.len() > MARKET_SHARE_WINDOW_DAYS {
+            self.market_history.remove(0);
+        }
+    }
 
-        // Process customer orders aging
-        self.process_order_aging();
+    // Cards of a retailer we sold and the market wanted over the last `days` market days. Orders
+    // are often filled a day or more after they're placed, so sales are only capped at what's left
+    // of demand after the rivals across the whole window, not day by day.
+    fn retailer_sales_and_demand(&self, retailer: &str, days: usize) -> (u32, u32) {
+        let recent = self.market_history.iter().rev().take(days);
+        let (sold, rivals, demand) = recent.fold((0, 0, 0), |(sold, rivals, demand), market_day| {
+            (sold + market_day.our_sales.get(retailer).copied().unwrap_or(0),
+             rivals + market_day.rival_retailer_sales.get(retailer).copied().unwrap_or(0),
+             demand + market_day.demand.get(retailer).copied().unwrap_or(0))
+        });
+        (sold.min(demand.saturating_sub(rivals)), demand)
+    }
 
-        // Start new day in analytics
-        self.analytics.start_new_day();
+    // Our share of all cards sold locally over the last `days` market days
+    fn market_share(&self, days: usize) -> f32 {
+        let (sold, demand) = RETAILERS.iter()
+            .map(|retailer| self.retailer_sales_and_demand(retailer.id, days))
+            .fold((0, 0), |(sold, demand), (retailer_sold, retailer_demand)| (sold + retailer_sold, demand + retailer_demand));
+        if demand == 0 { 0.0 } else { sold as f32 / demand as f32 }
+    }
 
-        // Update market conditions and process events
-        self.market_conditions.update_season(self.day);
-        self.market_conditions.process_daily_events(self.day, &mut self.recent_activities);
+    fn retailer_market_share(&self, retailer: &str, days: usize) -> (f32, u32) {
+        let (sold, demand) = self.retailer_sales_and_demand(retailer, days);
+        (if demand == 0 { 0.0 } else { sold as f32 / demand as f32 }, demand)
+    }
 
-        // Process daily achievements
-        let orders_completed_today = 0; // TODO: Track daily completion count
-        let orders_expired_today = expired_count;
-        self.achievements.process_daily_achievements(orders_completed_today, orders_expired_today, &self.analytics, self.day);
+    fn rival_market_share(&self, rival: &str, days: usize) -> f32 {
+        let recent = self.market_history.iter().rev().take(days);
+        let (sold, demand) = recent.fold((0, 0), |(sold, demand), market_day| {
+            (sold + market_day.rival_sales.get(rival).copied().unwrap_or(0), demand + market_day.total_demand())
+        });
+        if demand == 0 { 0.0 } else { (sold as f32 / demand as f32).min(1.0) }
+    }
 
-        // Check cash and inventory achievements
-        self.achievements.check_cash_achievements(self.cash, self.day, &mut self.recent_activities);
-        self.achievements.check_inventory_achievements(&self.inventory, self.day, &mut self.recent_activities);
-        self.achievements.check_seasonal_achievements(&self.market_conditions.current_season, self.achievements.seasonal_winter_profit, self.day, &mut self.recent_activities);
+    fn recent_expired_cards(&self, days: usize) -> u32 {
+        self.daily_expired_cards.iter().rev().take(days).sum()
+    }
 
-        // Process random events
-        if let Some(event) = self.random_events.process_daily_events(self.day, &mut self.recent_activities) {
-            // Handle any returned events (choice-based events)
-            self.random_events.active_event = Some(event);
+    fn start_new_day(&mut self) {
+        self.daily_revenues.push(0);
+        self.daily_expired_cards.push(0);
+        // Keep only last 30 days
+        if self.daily_revenues.len() > 30 {
+            self.daily_revenues.remove(0);
         }
-
-        // Add daily startup message
-        let season = self.market_conditions.current_season.display();
-        self.recent_activities.insert(0, format!("🌅 Day {} begins ({} season)", self.day, season));
-        if self.recent_activities.len() > 10 {
-            self.recent_activities.truncate(10);
+        if self.daily_expired_cards.len() > 30 {
+            self.daily_expired_cards.remove(0);
         }
     }
 
-    fn reputation_stars(&self) -> String {
-        let filled = "★".repeat(self.reputation as usize);
-        let empty = "☆".repeat(5 - self.reputation as usize);
-        format!("{}{}", filled, empty)
-    }
-
-    fn reputation_description(&self) -> &str {
-        match self.reputation {
-            5 => "Legendary",
-            4 => "Excellent", 
-            3 => "Good",
-            2 => "Fair",
-            1 => "Poor",
-            _ => "Unknown",
+    fn average_profit_margin(&self) -> f32 {
+        if self.profit_margins.is_empty() {
+            0.0
+        } else {
+            self.profit_margins.iter().sum::<f32>() / self.profit_margins.len() as f32
         }
     }
 
-    fn time_display(&self) -> String {
-        let period = if self.hour < 12 { "AM" } else { "PM" };
-        let display_hour = if self.hour == 0 { 12 } else if self.hour > 12 { self.hour - 12 } else { self.hour };
-        format!("{}:{:02} {}", display_hour, self.minute, period)
+    fn recent_daily_average(&self) -> f32 {
+        if self.daily_revenues.len() <= 1 {
+            return 0.0;
+        }
+        
+        let recent_days = self.daily_revenues.len().min(7); // Last 7 days
+        let sum: u32 = self.daily_revenues.iter().rev().take(recent_days).sum();
+        sum as f32 / recent_days as f32
     }
 
-    fn total_inventory_value(&self) -> u32 {
-        self.inventory.iter().map(|item| item.total_value()).sum()
+    fn total_profit(&self) -> i32 {
+        self.total_revenue as i32 + self.achievement_rewards as i32
+            - self.total_purchases as i32 - self.total_expenses() as i32
     }
 
-    fn total_inventory_cost(&self) -> u32 {
-        self.inventory.iter().map(|item| item.total_cost()).sum()
+    // Profit locked in by sales: revenue minus the actual cost of the cards sold
+    fn realized_gains(&self) -> i32 {
+        self.total_revenue as i32 - self.cost_of_goods_sold as i32
     }
+}
 
-    fn inventory_count(&self) -> u32 {
-        self.inventory.iter().map(|item| item.quantity).sum()
+impl Season {
+    fn from_day(day: u32) -> Self {
+        // Approximate seasons based on day of year (assuming 90-day seasons)
+        let season_day = (day - 1) % 360; // 360-day year for simplicity
+        match season_day {
+            0..=89 => Season::Spring,
+            90..=179 => Season::Summer, 
+            180..=269 => Season::Fall,
+            270..=359 => Season::Winter,
+            _ => Season::Spring,
+        }
     }
 
-    fn expiring_items_count(&self) -> usize {
-        self.inventory.iter().filter(|item| item.card.is_expiring_soon()).count()
+    fn display(&self) -> &str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer", 
+            Season::Fall => "Fall",
+            Season::Winter => "Winter",
+        }
     }
 
-    fn add_to_inventory(&mut self, card: GiftCard, quantity: u32) {
-        // Check if we already have this type of card
-        for item in &mut self.inventory {
-            if item.card.retailer == card.retailer && 
-               item.card.denomination == card.denomination &&
-               item.card.purchase_price == card.purchase_price {
-                item.quantity += quantity;
-                return;
-            }
+    fn demand_modifier(&self) -> f32 {
+        match self {
+            Season::Spring => 1.0,  // Normal demand
+            Season::Summer => 1.1,  // Slightly higher (vacation)
+            Season::Fall => 0.9,    // Slightly lower (back to school)
+            Season::Winter => 1.4,  // Much higher (holidays)
         }
-        
-        // Add new inventory item if not found
-        self.inventory.push(InventoryItem::new(card, quantity));
     }
 
-    fn can_afford(&self, cost: u32) -> bool {
-        self.cash >= cost
+    fn retailer_bonus(&self, retailer_id: &str) -> f32 {
+        match retailer(retailer_id) {
+            Some(retailer) => retailer.seasonal_bonus(self),
+            None if matches!(self, Season::Winter) => 1.2, // General holiday boost
+            None => 1.0,
+        }
     }
+}
 
-    fn spend_money(&mut self, amount: u32) -> bool {
-        if self.can_afford(amount) {
-            self.cash -= amount;
-            true
-        } else {
-            false
+impl MarketEvent {
+    fn new(name: &str, description: &str, retailer: Option<&str>, price_mult: f32, demand_mult: f32, duration: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            retailer_affected: retailer.map(|s| s.to_string()),
+            price_multiplier: price_mult,
+            demand_multiplier: demand_mult,
+            duration_days: duration,
+            remaining_days: duration,
         }
     }
 
-    fn generate_random_order(&mut self) {
-        // Match exactly what's available in market - retailer to denomination mapping
-        let available_cards = [
-            ("Amazon", 25),
-            ("Starbucks", 10), 
-            ("Target", 50),
-            ("iTunes", 15),
-            ("Walmart", 20),
-        ];
-        let customer_names = ["Alice", "Bob", "Charlie", "Diana", "Eve", "Frank", "Grace", "Henry"];
-        
-        // Simple randomization based on current time/day
-        let card_idx = (self.day + self.hour as u32) % available_cards.len() as u32;
-        let customer_idx = (self.next_order_id + self.day) % customer_names.len() as u32;
-        
-        let (retailer, denomination) = available_cards[card_idx as usize];
-        let customer_name = customer_names[customer_idx as usize];
-        
-        let quantity = 1 + (self.day % 5); // 1-5 cards
-        
-        // Customers want to buy at a discount from face value (that's the business model)
-        // Base offer is 85-95% of face value depending on reputation
-        let discount_percentage: f32 = match self.reputation {
-            5 => 0.95,  // 5% discount for 5-star (customers pay more for reliable service)
-            4 => 0.93,  // 7% discount for 4-star
-            3 => 0.90,  // 10% discount for 3-star
-            2 => 0.87,  // 13% discount for 2-star
-            1 => 0.85,  // 15% discount for 1-star (need deep discounts)
-            _ => 0.85,
-        };
-        
-        // Apply market demand multiplier
-        let demand_multiplier = self.market_conditions.get_demand_multiplier(retailer);
-        let demand_adjustment = if demand_multiplier > 1.2 {
-            0.02  // High demand = customers pay 2% more
-        } else if demand_multiplier < 0.8 {
-            -0.03  // Low demand = customers want 3% more discount
-        } else {
-            0.0  // Normal demand = no adjustment
-        };
-        
-        let final_discount = (discount_percentage + demand_adjustment).clamp(0.80, 0.98);
-        let offered_price = (denomination as f32 * final_discount) as u32;
-        
-        let deadline_days = 2 + (self.day % 5); // 2-6 days to fulfill
-        
-        // Priority based on offer amount
-        let priority = if offered_price >= denomination + 8 {
-            OrderPriority::High
-        } else if offered_price >= denomination + 5 {
-            OrderPriority::Medium
-        } else {
-            OrderPriority::Low
-        };
-
-        let order = CustomerOrder::new(
-            self.next_order_id,
-            customer_name,
-            retailer,
-            denomination,
-            quantity,
-            offered_price,
-            deadline_days,
-            priority,
-        );
+    #[allow(dead_code)]
+    fn is_expired(&self) -> bool {
+        self.remaining_days == 0
+    }
 
-        self.customer_orders.push_back(order);
-        self.next_order_id += 1;
+    fn affects_retailer(&self, retailer: &str) -> bool {
+        self.retailer_affected.as_ref().is_none_or(|r| r == retailer)
+    }
+}
 
-        // Add notification
-        self.recent_activities.insert(0, format!(
-            "📋 New order: {} wants {} {} ${} cards",
-            customer_name, quantity, retailer, denomination
-        ));
-        if self.recent_activities.len() > 10 {
-            self.recent_activities.truncate(10);
+impl MarketConditions {
+    fn new() -> Self {
+        Self {
+            current_season: Season::Spring,
+            active_events: Vec::new(),
+            base_demand_modifier: 1.0,
+            next_event_in_days: 4, // First event on day 5
         }
     }
 
-    fn process_order_aging(&mut self) {
-        // Age all orders by 1 day
-        for order in &mut self.customer_orders {
-            if order.deadline_days > 0 {
-                order.deadline_days -= 1;
-            }
+    fn update_season(&mut self, day: u32) {
+        let new_season = Season::from_day(day);
+        if !matches!((&self.current_season, &new_season), 
+            (Season::Spring, Season::Spring) | 
+            (Season::Summer, Season::Summer) | 
+            (Season::Fall, Season::Fall) | 
+            (Season::Winter, Season::Winter)) {
+            // Season changed
+            self.current_season = new_season;
+            self.base_demand_modifier = self.current_season.demand_modifier();
         }
+    }
 
-        // Remove expired orders and damage reputation
-        let mut expired_count = 0;
-        self.customer_orders.retain(|order| {
-            if order.is_expired() {
-                expired_count += 1;
-                false
-            } else {
+    // Returns how many market events ended today
+    fn process_daily_events(&mut self, catalog: &EventCatalog, context: &EventContext, rng: &mut GameRng, activities: &mut Vec<String>) -> u32 {
+        // Age existing events
+        let mut ended = 0;
+        self.active_events.retain_mut(|event| {
+            if event.remaining_days > 0 {
+                event.remaining_days -= 1;
                 true
+            } else {
+                activities.insert(0, format!("📈 Market event '{}' has ended", event.name));
+                ended += 1;
+                false
             }
         });
 
-        if expired_count > 0 {
-            // Record expired orders in analytics
-            for _ in 0..expired_count {
-                self.analytics.record_expired_order();
-            }
-            
-            self.recent_activities.insert(0, format!(
-                "⏰ {} customer orders expired", expired_count
-            ));
-            if self.recent_activities.len() > 10 {
-                self.recent_activities.truncate(10);
-            }
-            
-            // Damage reputation for each expired order
-            for _ in 0..expired_count {
-                self.decrease_reputation("order_expired");
-            }
+        // Check for new events
+        if self.next_event_in_days > 0 {
+            self.next_event_in_days -= 1;
+        } else {
+            self.generate_random_event(catalog, context, rng, activities);
+            self.next_event_in_days = rng.range(5, 14); // Next event in 5-14 days
         }
 
-        // Generate new orders based on reputation and market conditions
-        // Higher reputation = more frequent orders
-        let base_order_chance = match self.reputation {
-            5 => true,                // Every day (highest reputation)
-            4 => self.day % 2 == 0,   // Every other day
-            3 => self.day % 2 == 0,   // Every other day (default - more frequent now)
-            2 => self.day % 3 == 0,   // Every 3 days
-            1 => self.day % 4 == 0,   // Every 4 days
-            _ => false,
-        };
-        
-        // Apply market demand modifier for additional orders
-        let market_boost = self.market_conditions.base_demand_modifier > 1.0;
-        let extra_market_chance = market_boost && self.day % 2 == 1; // Additional orders on alternate days during good markets
-        let order_chance = base_order_chance || extra_market_chance;
-        
-        if order_chance {
-            self.generate_random_order();
-        }
+        ended
     }
 
-    fn can_fulfill_order(&self, order: &CustomerOrder) -> bool {
-        // Check if we have enough cards total across all inventory items
-        let total_available = self.inventory.iter()
-            .filter(|item| item.card.retailer == order.retailer && 
-                          item.card.denomination == order.denomination)
-            .map(|item| item.quantity)
-            .sum::<u32>();
-            
-        total_available >= order.quantity
-    }
+    fn generate_random_event(&mut self, catalog: &EventCatalog, context: &EventContext, rng: &mut GameRng, activities: &mut Vec<String>) {
+        let Some(definition) = catalog.pick_market_event(context, rng) else {
+            return; // No market event qualifies today
+        };
 
-    fn fulfill_order(&mut self, order_index: usize) -> bool {
-        if order_index >= self.customer_orders.len() {
-            return false;
-        }
+        let event = definition.instantiate();
+        activities.insert(0, format!("🎯 New market event: {}", event.name));
+        self.active_events.push(event);
+    }
 
-        let order = self.customer_orders[order_index].clone();
+    fn get_price_multiplier(&self, retailer: &str) -> f32 {
+        let mut multiplier = 1.0;
         
-        if !self.can_fulfill_order(&order) {
-            // Add failure message
-            self.recent_activities.insert(0, format!(
-                "❌ Cannot fulfill order #{} - insufficient inventory", 
-                order.id
-            ));
-            if self.recent_activities.len() > 10 {
-                self.recent_activities.truncate(10);
-            }
-            return false;
-        }
-
-        // Find and remove cards from inventory
-        let mut cards_needed = order.quantity;
-        let mut inventory_to_remove = Vec::new();
+        // Apply seasonal bonus
+        multiplier *= self.current_season.retailer_bonus(retailer);
         
-        for (i, item) in self.inventory.iter_mut().enumerate() {
-            if item.card.retailer == order.retailer && 
-               item.card.denomination == order.denomination &&
-               cards_needed > 0 {
-                
-                let cards_to_take = cards_needed.min(item.quantity);
-                cards_needed -= cards_to_take;
-                
-                if cards_to_take == item.quantity {
-                    // Remove entire inventory item
-                    inventory_to_remove.push(i);
-                } else {
-                    // Reduce quantity
-                    item.quantity -= cards_to_take;
-                }
-                
-                if cards_needed == 0 {
-                    break;
-                }
+        // Apply active events
+        for event in &self.active_events {
+            if event.affects_retailer(retailer) {
+                multiplier *= event.price_multiplier;
             }
         }
-
-        // Remove depleted inventory items (in reverse order to maintain indices)
-        for &i in inventory_to_remove.iter().rev() {
-            self.inventory.remove(i);
-        }
-
-        // Calculate earnings and profit
-        let total_earnings = order.total_offered();
-        let cost_basis = order.quantity * (order.denomination - 5); // Estimate wholesale cost
-        let profit = total_earnings as i32 - cost_basis as i32;
         
-        // Record sale in analytics
-        self.analytics.record_sale(total_earnings, cost_basis, order.quantity);
+        multiplier
+    }
+    
+    fn get_price_multiplier_with_random_events(&self, retailer: &str, random_events: &RandomEventManager) -> f32 {
+        let mut multiplier = self.get_price_multiplier(retailer);
         
-        // Add money to cash
-        self.cash += total_earnings;
+        // Apply random event modifiers
+        for modifier in &random_events.temp_modifiers {
+            multiplier *= modifier.price_multiplier;
+        }
+        
+        multiplier
+    }
 
-        // Check achievements
-        self.achievements.record_order_completion(self.day);
-        self.achievements.check_order_achievements(self.analytics.orders_completed, self.reputation, self.day, &mut self.recent_activities);
-        self.achievements.check_cash_achievements(self.cash, self.day, &mut self.recent_activities);
+    fn get_demand_multiplier(&self, retailer: &str) -> f32 {
+        let mut multiplier = self.base_demand_modifier;
         
-        // Remove the completed order
-        self.customer_orders.remove(order_index);
+        // Apply seasonal bonus for demand
+        multiplier *= self.current_season.retailer_bonus(retailer);
         
-        // Add success message
-        self.recent_activities.insert(0, format!(
-            "✅ Completed order #{}: {} {} ${} cards for ${} (profit: ${})",
-            order.id, order.quantity, order.retailer, order.denomination, 
-            total_earnings, profit
-        ));
-        if self.recent_activities.len() > 10 {
-            self.recent_activities.truncate(10);
-        }
-
-        // Improve reputation for timely fulfillment
-        // Extra bonus for fast fulfillment (more than half deadline remaining)
-        if order.deadline_days > (2 + (self.day % 5)) / 2 {
-            self.improve_reputation("fast_fulfillment");
-        } else {
-            self.improve_reputation("order_fulfilled");
+        // Apply active events
+        for event in &self.active_events {
+            if event.affects_retailer(retailer) {
+                multiplier *= event.demand_multiplier;
+            }
         }
         
-        true
+        multiplier
     }
+}
 
-    fn improve_reputation(&mut self, reason: &str) {
-        if self.reputation < 5 {
-            self.reputation += 1;
-            let message = match reason {
-                "order_fulfilled" => "⭐ Reputation improved for excellent service!",
-                "fast_fulfillment" => "⭐ Reputation boosted for lightning-fast delivery!",
-                _ => "⭐ Reputation improved!",
-            };
-            self.recent_activities.insert(0, message.to_string());
-            if self.recent_activities.len() > 10 {
-                self.recent_activities.truncate(10);
-            }
+// Bulk discount applied when a single purchase reaches `min_quantity` units
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VolumeTier {
+    min_quantity: u32,
+    discount: f32, // 0.05 = 5% off
+}
+
+// Retailer relationship: lifetime units bought from a retailer unlock better base prices
+const RELATIONSHIP_TIERS: [(u32, f32); 3] = [
+    (50, 0.02),   // Regular buyer
+    (150, 0.04),  // Preferred buyer
+    (400, 0.07),  // Key account
+];
+
+fn default_volume_tiers() -> Vec<VolumeTier> {
+    vec![
+        VolumeTier { min_quantity: 10, discount: 0.05 },
+        VolumeTier { min_quantity: 25, discount: 0.10 },
+    ]
+}
+
+// One wholesale SKU with finite supply that restocks over time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MarketListing {
+    retailer: String,
+    denomination: u32,
+    base_cost: u32,
+    stock: u32,
+    max_stock: u32,
+    restock_per_day: f32,    // Units per day under normal conditions
+    restock_progress: f32,   // Fractional units carried to the next day
+    #[serde(default = "default_volume_tiers")]
+    volume_tiers: Vec<VolumeTier>, // Sorted by min_quantity
+}
+
+impl MarketListing {
+    fn from_product(product: &CardProduct) -> Self {
+        Self {
+            retailer: product.retailer.to_string(),
+            denomination: product.denomination,
+            base_cost: product.base_cost(),
+            stock: product.max_stock,
+            max_stock: product.max_stock,
+            restock_per_day: product.restock_per_day,
+            restock_progress: 0.0,
+            volume_tiers: product.volume_tiers.iter()
+                .map(|&(min_quantity, discount)| VolumeTier { min_quantity, discount })
+                .collect(),
         }
     }
 
-    fn decrease_reputation(&mut self, reason: &str) {
-        if self.reputation > 1 {
-            self.reputation -= 1;
-            let message = match reason {
-                "order_expired" => "💔 Reputation damaged - customers disappointed by expired orders",
-                "slow_service" => "💔 Reputation declined due to slow service",
-                _ => "💔 Reputation decreased!",
-            };
-            self.recent_activities.insert(0, message.to_string());
-            if self.recent_activities.len() > 10 {
-                self.recent_activities.truncate(10);
-            }
-        }
+    fn volume_discount(&self, quantity: u32) -> f32 {
+        self.volume_tiers.iter()
+            .filter(|tier| quantity >= tier.min_quantity)
+            .map(|tier| tier.discount)
+            .fold(0.0, f32::max)
     }
 
-    fn save_game(&self, filename: &str) -> Result<(), Box<dyn Error>> {
-        let save_data = serde_json::to_string_pretty(self)?;
-        fs::write(filename, save_data)?;
-        Ok(())
+    // Smallest purchase that would unlock a better volume tier than `quantity` gets
+    fn next_volume_tier(&self, quantity: u32) -> Option<&VolumeTier> {
+        self.volume_tiers.iter().find(|tier| tier.min_quantity > quantity)
     }
 
-    fn load_game(filename: &str) -> Result<Self, Box<dyn Error>> {
-        let save_data = fs::read_to_string(filename)?;
-        let game_data: GameData = serde_json::from_str(&save_data)?;
-        Ok(game_data)
+    // Prices climb as stock runs low: no premium above half stock, up to +30% when sold out
+    fn scarcity_multiplier(&self) -> f32 {
+        if self.max_stock == 0 {
+            return 1.0;
+        }
+        let fill = self.stock as f32 / self.max_stock as f32;
+        if fill >= 0.5 {
+            1.0
+        } else {
+            1.0 + (0.5 - fill) / 0.5 * 0.3
+        }
     }
 
-    fn save_file_exists(filename: &str) -> bool {
-        std::path::Path::new(filename).exists()
+    // Supply tightens when conditions push prices up (season, events) and loosens when they fall
+    fn daily_restock(&self, conditions: &MarketConditions) -> f32 {
+        self.restock_per_day / conditions.get_price_multiplier(&self.retailer).max(0.1)
+    }
+
+    // Days until the listing is fully restocked (None if already full)
+    fn days_until_full(&self, conditions: &MarketConditions) -> Option<u32> {
+        if self.stock >= self.max_stock {
+            return None;
+        }
+        let missing = (self.max_stock - self.stock) as f32 - self.restock_progress;
+        Some((missing / self.daily_restock(conditions)).ceil().max(1.0) as u32)
     }
 }
 
-#[derive(Debug)]
-struct App {
-    screen: Screen,
-    selected_menu_item: usize,
-    should_quit: bool,
-    game_data: GameData,
-    last_time_update: Instant,
-    game_speed: Duration, // How often to advance time
-    paused: bool,
-    sound_effects: SoundEffects,
+#[derive(Debug, Serialize, Deserialize)]
+struct Market {
+    listings: Vec<MarketListing>,
+    #[serde(default)]
+    lifetime_units: BTreeMap<String, u32>, // Units we've ever bought, by retailer
 }
 
-impl App {
-    fn new() -> App {
-        App {
-            screen: Screen::MainMenu,
-            selected_menu_item: 0,
-            should_quit: false,
-            game_data: GameData::new(),
-            last_time_update: Instant::now(),
-            game_speed: Duration::from_secs(1), // Advance 20 minutes every 1 second
-            paused: false,
-            sound_effects: SoundEffects::new(),
-        }
+impl Default for Market {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    fn update_time(&mut self) {
-        if self.paused || matches!(self.screen, Screen::MainMenu) {
-            return;
+impl Market {
+    fn new() -> Self {
+        Self {
+            listings: CARD_CATALOG.iter().map(MarketListing::from_product).collect(),
+            lifetime_units: BTreeMap::new(),
         }
+    }
 
-        let now = Instant::now();
-        if now.duration_since(self.last_time_update) >= self.game_speed {
-            self.game_data.advance_time(20); // Advance 20 minutes
-            self.last_time_update = now;
+    // Add listings for catalog cards missing from an older save
+    fn sync_with_catalog(&mut self) {
+        for product in CARD_CATALOG {
+            if self.listing_index(product.retailer, product.denomination).is_none() {
+                self.listings.push(MarketListing::from_product(product));
+            }
         }
+        self.listings.sort_by_key(|listing| {
+            CARD_CATALOG.iter()
+                .position(|product| product.retailer == listing.retailer && product.denomination == listing.denomination)
+                .unwrap_or(usize::MAX)
+        });
     }
-    
-    fn check_for_active_events(&mut self) {
-        // Check if we need to switch to random event screen for player choice
-        if self.game_data.random_events.player_choice_pending && 
-           !matches!(self.screen, Screen::RandomEvent) {
-            self.sound_effects.play(SoundType::RandomEvent);
-            self.screen = Screen::RandomEvent;
-            self.selected_menu_item = 0; // Reset selection
-        } else if !self.game_data.random_events.player_choice_pending && 
-                  matches!(self.screen, Screen::RandomEvent) {
-            // Return to dashboard if event is resolved
-            self.screen = Screen::Dashboard;
-            self.selected_menu_item = 0;
-        }
+
+    fn listing_index(&self, retailer: &str, denomination: u32) -> Option<usize> {
+        self.listings.iter().position(|listing| listing.retailer == retailer && listing.denomination == denomination)
     }
-    
-    fn check_for_audio_events(&mut self) {
-        // Check for recent achievement unlocks
-        if let Some(_achievement_name) = self.game_data.achievements.get_recent_unlock() {
-            self.sound_effects.play(SoundType::LevelUp);
-        }
-        
-        // Check for new orders (simple detection by counting recent activities with order keywords)
-        if let Some(recent_activity) = self.game_data.recent_activities.first() {
-            if recent_activity.contains("New customer order") || recent_activity.contains("📝 Order from") {
-                self.sound_effects.play(SoundType::NewOrder);
+
+    fn lifetime_units(&self, retailer: &str) -> u32 {
+        self.lifetime_units.get(retailer).copied().unwrap_or(0)
+    }
+
+    // Every card bought from a retailer counts, whatever its denomination
+    fn relationship_discount(&self, retailer: &str) -> f32 {
+        let units = self.lifetime_units(retailer);
+        RELATIONSHIP_TIERS.iter()
+            .filter(|(min_units, _)| units >= *min_units)
+            .map(|(_, discount)| *discount)
+            .fold(0.0, f32::max)
+    }
+
+    fn next_relationship_tier(&self, retailer: &str) -> Option<(u32, f32)> {
+        let units = self.lifetime_units(retailer);
+        RELATIONSHIP_TIERS.iter().copied().find(|(min_units, _)| units < *min_units)
+    }
+
+    fn restock(&mut self, conditions: &MarketConditions) {
+        for listing in &mut self.listings {
+            if listing.stock >= listing.max_stock {
+                listing.restock_progress = 0.0;
+                continue;
             }
+            listing.restock_progress += listing.daily_restock(conditions);
+            let arrived = listing.restock_progress.floor() as u32;
+            listing.restock_progress -= arrived as f32;
+            listing.stock = (listing.stock + arrived).min(listing.max_stock);
         }
     }
 
-    fn toggle_pause(&mut self) {
-        if !matches!(self.screen, Screen::MainMenu) {
-            self.paused = !self.paused;
-            self.sound_effects.play(SoundType::Paused);
-            let status = if self.paused { "⏸️ Paused" } else { "▶️ Resumed" };
-            self.game_data.recent_activities.insert(0, status.to_string());
-            if self.game_data.recent_activities.len() > 10 {
-                self.game_data.recent_activities.truncate(10);
+    // Stock bought by a rival: takes up to `quantity` units and doesn't count toward our
+    // relationship with the retailer. Returns how many were taken.
+    fn take_competitor_stock(&mut self, index: usize, quantity: u32) -> u32 {
+        match self.listings.get_mut(index) {
+            Some(listing) => {
+                let taken = quantity.min(listing.stock);
+                listing.stock -= taken;
+                taken
             }
+            None => 0,
         }
     }
 
-    fn purchase_from_market(&mut self) {
-        if !matches!(self.screen, Screen::Market) {
-            return;
+    // Take `quantity` units out of stock; fails without changes if not enough are available
+    fn take_stock(&mut self, index: usize, quantity: u32) -> bool {
+        match self.listings.get_mut(index) {
+            Some(listing) if listing.stock >= quantity => {
+                listing.stock -= quantity;
+                *self.lifetime_units.entry(listing.retailer.clone()).or_insert(0) += quantity;
+                true
+            }
+            _ => false,
         }
+    }
+}
 
-        // Base market items with dynamic pricing
-        let base_market_items = vec![
-            ("Amazon", 25, 20, 50),     // (retailer, value, base_cost, stock)
-            ("Starbucks", 10, 8, 30),
-            ("Target", 50, 42, 15),
-            ("iTunes", 15, 12, 25),
-            ("Walmart", 20, 17, 40),
-        ];
-        
-        // Apply market conditions to get actual prices
-        let market_items: Vec<(&str, u32, u32, u32)> = base_market_items.iter()
-            .map(|(retailer, value, base_cost, stock)| {
-                let price_multiplier = self.game_data.market_conditions.get_price_multiplier_with_random_events(retailer, &self.game_data.random_events);
-                let actual_cost = (*base_cost as f32 * price_multiplier).round() as u32;
-                (*retailer, *value, actual_cost, *stock)
-            })
-            .collect();
-
-        if let Some((retailer, denomination, cost, _stock)) = market_items.get(self.selected_menu_item) {
-            let purchase_cost = *cost;
-            
-            if self.game_data.can_afford(purchase_cost) {
-                if self.game_data.spend_money(purchase_cost) {
-                    // Play purchase success sound
-                    self.sound_effects.play(SoundType::Purchase);
-                    
-                    // Create the gift card with random expiration (30-90 days)
-                    let expiration_days = 30 + (self.game_data.day % 60); // Simple randomization
-                    let card = GiftCard::new(retailer, *denomination, *cost, expiration_days);
-                    
-                    self.game_data.add_to_inventory(card, 1);
-                    
-                    // Record purchase in analytics
-                    self.game_data.analytics.record_purchase(purchase_cost);
-                    
-                    // Check market purchase achievements
-                    let price_multiplier = self.game_data.market_conditions.get_price_multiplier_with_random_events(retailer, &self.game_data.random_events);
-                    self.game_data.achievements.record_market_purchase(price_multiplier, self.game_data.day, &mut self.game_data.recent_activities);
-                    
-                    // Add activity log
-                    let activity = format!(
-                        "💰 Purchased {} ${} card for ${}", 
-                        retailer, denomination, cost
-                    );
-                    self.game_data.recent_activities.insert(0, activity);
-                    if self.game_data.recent_activities.len() > 10 {
-                        self.game_data.recent_activities.truncate(10);
-                    }
-                }
-            } else {
-                // Not enough money
-                self.sound_effects.play(SoundType::Error);
-                let activity = format!(
-                    "❌ Insufficient funds for {} ${} (need ${})", 
-                    retailer, denomination, cost
-                );
-                self.game_data.recent_activities.insert(0, activity);
-                if self.game_data.recent_activities.len() > 10 {
-                    self.game_data.recent_activities.truncate(10);
-                }
-            }
+impl Achievement {
+    fn new(achievement_type: AchievementType, name: &str, description: &str, target: u32, reward: u32) -> Self {
+        Self {
+            achievement_type,
+            name: name.to_string(),
+            description: description.to_string(),
+            unlocked: false,
+            unlock_date: None,
+            progress: 0,
+            target,
+            reward_cash: reward,
         }
     }
 
-    fn fulfill_customer_order(&mut self) {
-        if !matches!(self.screen, Screen::Orders) {
-            return;
+    #[allow(dead_code)]
+    fn update_progress(&mut self, new_progress: u32) -> bool {
+        self.progress = new_progress;
+        if !self.unlocked && self.progress >= self.target {
+            self.unlocked = true;
+            true // Achievement unlocked
+        } else {
+            false
         }
+    }
 
-        if self.game_data.customer_orders.is_empty() {
-            return;
+    fn unlock(&mut self, day: u32) {
+        if !self.unlocked {
+            self.unlocked = true;
+            self.unlock_date = Some(day);
         }
+    }
 
-        // Ensure selected item is within bounds
-        let order_index = self.selected_menu_item.min(self.game_data.customer_orders.len() - 1);
-        
-        // Attempt to fulfill the order
-        let success = self.game_data.fulfill_order(order_index);
-        
-        // Play appropriate sound
-        if success {
-            self.sound_effects.play(SoundType::Sale);
+    fn progress_percentage(&self) -> f32 {
+        if self.target == 0 {
+            0.0
         } else {
-            self.sound_effects.play(SoundType::Error);
+            (self.progress as f32 / self.target as f32 * 100.0).min(100.0)
         }
+    }
+}
+
+impl AchievementTracker {
+    fn new() -> Self {
+        let mut tracker = Self {
+            achievements: Vec::new(),
+            total_unlocked: 0,
+            recent_unlock: None,
+            reward_ledger: Vec::new(),
+            consecutive_perfect_days: 0,
+            consecutive_efficiency_days: 0,
+            orders_today: 0,
+            orders_expired_today: 0,
+            favorable_market_purchases: 0,
+            seasonal_winter_profit: 0,
+            seasons_survived: Vec::new(),
+            events_survived: 0,
+        };
         
-        // Adjust selection if we're now beyond the list
-        if self.selected_menu_item >= self.game_data.customer_orders.len() && !self.game_data.customer_orders.is_empty() {
-            self.selected_menu_item = self.game_data.customer_orders.len() - 1;
-        } else if self.game_data.customer_orders.is_empty() {
-            self.selected_menu_item = 0;
-        }
+        tracker.initialize_achievements();
+        tracker
     }
-    
-    fn handle_random_event_choice(&mut self) {
-        // Make choice on active random event
-        if let Some((cash, reputation, modifiers)) = self.game_data.random_events.make_choice(self.selected_menu_item) {
-            // Apply impacts immediately
-            if cash != 0 {
-                if cash > 0 {
-                    self.game_data.cash = self.game_data.cash.saturating_add(cash as u32);
-                } else {
-                    self.game_data.cash = self.game_data.cash.saturating_sub((-cash) as u32);
-                }
-            }
+
+    fn initialize_achievements(&mut self) {
+        self.achievements = vec![
+            // Progress milestones
+            Achievement::new(AchievementType::FirstSale, "First Sale", "Complete your first customer order", 1, 100),
+            Achievement::new(AchievementType::EarlyBird, "Early Bird", "Complete your first 10 orders", 10, 500),
+            Achievement::new(AchievementType::Entrepreneur, "Entrepreneur", "Accumulate $10,000 in cash", 10000, 1000),
+            Achievement::new(AchievementType::BusinessMogul, "Business Mogul", "Accumulate $50,000 in cash", 50000, 5000),
+            Achievement::new(AchievementType::Millionaire, "Millionaire", "Accumulate $1,000,000 in cash", 1000000, 50000),
             
-            if reputation != 0 {
-                if reputation > 0 {
-                    self.game_data.reputation = (self.game_data.reputation.saturating_add(reputation as u8)).min(5);
-                } else {
-                    self.game_data.reputation = self.game_data.reputation.saturating_sub((-reputation) as u8).max(1);
-                }
-            }
+            // Performance achievements
+            Achievement::new(AchievementType::PerfectWeek, "Perfect Week", "7 consecutive days with 100% order completion", 7, 2000),
+            Achievement::new(AchievementType::SpeedDemon, "Speed Demon", "Fulfill 5 orders in a single day", 5, 1500),
+            Achievement::new(AchievementType::Efficiency, "Efficiency Expert", "Maintain 90%+ success rate for 30 days", 30, 3000),
+            Achievement::new(AchievementType::MarketMaster, "Market Master", "Make purchases during 5 favorable market events", 5, 2500),
+            Achievement::new(AchievementType::MarketPlayer, "Market Player", "Hold 10% of the local market over 30 days", 10, 1500),
+            Achievement::new(AchievementType::MarketLeader, "Market Leader", "Hold 25% of the local market over 30 days", 25, 5000),
             
-            // Add temporary modifiers
-            self.game_data.random_events.temp_modifiers.extend(modifiers);
+            // Reputation achievements
+            Achievement::new(AchievementType::LegendaryStatus, "Legendary Status", "Reach maximum 5-star reputation", 5, 2000),
+            Achievement::new(AchievementType::CustomerFavorite, "Customer Favorite", "Complete 100 customer orders", 100, 3000),
+            Achievement::new(AchievementType::TrustedSeller, "Trusted Seller", "Complete 500 customer orders", 500, 10000),
             
-            // Log the choice result
-            let activity = "✅ Made choice on random event".to_string();
-            self.game_data.recent_activities.insert(0, activity);
-            if self.game_data.recent_activities.len() > 10 {
-                self.game_data.recent_activities.truncate(10);
-            }
+            // Seasonal achievements
+            Achievement::new(AchievementType::WinterWinner, "Winter Winner", "Earn $5,000 profit during Winter season", 5000, 2000),
+            Achievement::new(AchievementType::SeasonVeteran, "Season Veteran", "Experience all 4 seasons", 4, 3000),
+            Achievement::new(AchievementType::EventSurvivor, "Event Survivor", "Survive 10 market or random events", 10, 2500),
             
-            // Event will be automatically cleared and screen switched in check_for_active_events
-        }
+            // Inventory achievements
+            Achievement::new(AchievementType::Collector, "Collector", "Own 100+ gift cards simultaneously", 100, 2000),
+            Achievement::new(AchievementType::DiversifiedPortfolio, "Diversified Portfolio", &format!("Own cards from all {} retailers", RETAILERS.len()), RETAILERS.len() as u32, 1000),
+            Achievement::new(AchievementType::QuickTurnaround, "Quick Turnaround", "Sell inventory within 3 days of purchase", 1, 1500),
+        ];
     }
 
-    fn sell_inventory_item(&mut self) {
-        if !matches!(self.screen, Screen::Inventory) {
-            return;
-        }
+    fn check_cash_achievements(&mut self, cash: u32, day: u32, activities: &mut Vec<String>) {
+        self.check_and_unlock(&AchievementType::Entrepreneur, cash, day, activities);
+        self.check_and_unlock(&AchievementType::BusinessMogul, cash, day, activities);
+        self.check_and_unlock(&AchievementType::Millionaire, cash, day, activities);
+    }
 
-        if self.game_data.inventory.is_empty() {
-            return;
-        }
+    fn check_order_achievements(&mut self, total_orders: u32, reputation: u8, day: u32, activities: &mut Vec<String>) {
+        self.check_and_unlock(&AchievementType::FirstSale, total_orders, day, activities);
+        self.check_and_unlock(&AchievementType::EarlyBird, total_orders, day, activities);
+        self.check_and_unlock(&AchievementType::CustomerFavorite, total_orders, day, activities);
+        self.check_and_unlock(&AchievementType::TrustedSeller, total_orders, day, activities);
+        self.check_and_unlock(&AchievementType::LegendaryStatus, reputation as u32, day, activities);
+    }
 
-        // Ensure selected item is within bounds
-        let inventory_index = self.selected_menu_item.min(self.game_data.inventory.len() - 1);
-        
-        // Get the selected inventory item
-        let item = &self.game_data.inventory[inventory_index];
-        
-        // Calculate market value (sell at slightly below retail value)
-        let retail_value = item.card.denomination;
-        let market_value = (retail_value as f32 * 0.85) as u32; // Sell at 85% of face value
-        let total_value = market_value * item.quantity;
-        
-        // Calculate profit/loss
-        let total_cost = item.card.purchase_price * item.quantity;
-        let profit = total_value as i32 - total_cost as i32;
-        
-        // Add cash to player
-        self.game_data.cash += total_value;
-        
-        // Record the sale in analytics
-        self.game_data.analytics.cards_sold += item.quantity;
-        self.game_data.analytics.total_revenue += total_value;
-        
-        // Play success sound
-        self.sound_effects.play(SoundType::Sale);
+    fn check_inventory_achievements(&mut self, inventory: &[InventoryItem], day: u32, activities: &mut Vec<String>) {
+        // Check collector achievement
+        let total_cards: u32 = inventory.iter().map(|item| item.quantity).sum();
+        self.check_and_unlock(&AchievementType::Collector, total_cards, day, activities);
         
-        // Add activity log
-        let activity = format!(
-            "💰 Sold {}x {} ${} cards for ${} ({}${} profit)",
-            item.quantity,
-            item.card.retailer,
-            item.card.denomination,
-            total_value,
-            if profit >= 0 { "+" } else { "" },
-            profit
-        );
-        self.game_data.recent_activities.insert(0, activity);
-        if self.game_data.recent_activities.len() > 10 {
-            self.game_data.recent_activities.truncate(10);
+        // Check diversified portfolio
+        let retailers: std::collections::HashSet<&str> = inventory.iter()
+            .map(|item| item.card.retailer.as_str())
+            .filter(|id| retailer(id).is_some())
+            .collect();
+        self.check_and_unlock(&AchievementType::DiversifiedPortfolio, retailers.len() as u32, day, activities);
+    }
+
+    fn check_seasonal_achievements(&mut self, season: &Season, winter_profit: i32, day: u32, activities: &mut Vec<String>) {
+        // Track seasons survived
+        if !self.seasons_survived.contains(season) {
+            self.seasons_survived.push(season.clone());
         }
+        self.check_and_unlock(&AchievementType::SeasonVeteran, self.seasons_survived.len() as u32, day, activities);
         
-        // Remove the sold item from inventory
-        self.game_data.inventory.remove(inventory_index);
-        
-        // Adjust selection if we're now beyond the list
-        if self.selected_menu_item >= self.game_data.inventory.len() && !self.game_data.inventory.is_empty() {
-            self.selected_menu_item = self.game_data.inventory.len() - 1;
-        } else if self.game_data.inventory.is_empty() {
-            self.selected_menu_item = 0;
+        // Check winter winner
+        if matches!(season, Season::Winter) && winter_profit >= 5000 {
+            self.check_and_unlock(&AchievementType::WinterWinner, winter_profit as u32, day, activities);
         }
     }
 
-    fn next_menu_item(&mut self) {
-        let menu_items = match self.screen {
-            Screen::MainMenu => 4, // New Game, Continue, Tutorial, Quit
-            Screen::Dashboard => 8, // Market, Orders, Inventory, Analytics, Achievements, Settings, Save Game, Quit
-            Screen::Market => 5, // 5 market items
-            Screen::Orders => self.game_data.customer_orders.len().max(1), // Number of orders
-            Screen::Inventory => self.game_data.inventory.len().max(1), // Number of inventory items
-            Screen::RandomEvent => {
-                // Get number of choices for active event
-                if let Some(event) = &self.game_data.random_events.active_event {
-                    event.get_choices().len().max(1)
-                } else {
-                    1
-                }
-            },
-            _ => 1, // Other screens typically have minimal navigation
-        };
-        self.selected_menu_item = (self.selected_menu_item + 1) % menu_items;
-    }
-
-    fn previous_menu_item(&mut self) {
-        let menu_items = match self.screen {
-            Screen::MainMenu => 4,
-            Screen::Dashboard => 8,
-            Screen::Market => 5,
-            Screen::Orders => self.game_data.customer_orders.len().max(1),
-            Screen::Inventory => self.game_data.inventory.len().max(1),
-            Screen::RandomEvent => {
-                // Get number of choices for active event
-                if let Some(event) = &self.game_data.random_events.active_event {
-                    event.get_choices().len().max(1)
-                } else {
-                    1
-                }
-            },
-            _ => 1,
-        };
-        if self.selected_menu_item > 0 {
-            self.selected_menu_item -= 1;
+    // Profit only counts toward Winter Winner while it is winter; each winter starts from zero
+    fn record_sale_profit(&mut self, season: &Season, profit: i32) {
+        if matches!(season, Season::Winter) {
+            self.seasonal_winter_profit += profit;
         } else {
-            self.selected_menu_item = menu_items - 1;
+            self.seasonal_winter_profit = 0;
         }
     }
 
-    fn select_menu_item(&mut self) {
-        let previous_screen = self.screen.clone();
+    fn record_order_completion(&mut self, day: u32, activities: &mut Vec<String>) {
+        self.orders_today += 1;
         
-        match self.screen {
-            Screen::MainMenu => {
-                match self.selected_menu_item {
-                    0 => self.screen = Screen::Dashboard, // New Game
-                    1 => { 
-                        // Only load if save file exists
-                        if App::save_file_exists() {
-                            self.load_game(); 
-                        }
-                    }, // Continue Game
-                    2 => {}, // Tutorial (not implemented yet)
-                    3 => self.should_quit = true, // Quit
-                    _ => {}
-                }
-            }
-            Screen::Dashboard => {
-                match self.selected_menu_item {
-                    0 => self.screen = Screen::Market,       // [1] Market
-                    1 => self.screen = Screen::Orders,       // [2] Orders  
-                    2 => self.screen = Screen::Inventory,    // [3] Inventory
-                    3 => self.screen = Screen::Analytics,    // [4] Analytics
-                    4 => self.screen = Screen::Achievements, // [5] Achievements
-                    5 => self.screen = Screen::Settings,     // [6] Settings
-                    6 => { self.save_game(); },              // [7] Save Game
-                    7 => self.screen = Screen::MainMenu,     // [8] Quit to Menu
-                    _ => {}
-                }
-            }
-            Screen::Market => {
-                // Purchase item from market (stay on market screen)
-                self.purchase_from_market();
-                return; // Don't reset selection
-            }
-            Screen::Orders => {
-                // Fulfill customer order (stay on orders screen)
-                self.fulfill_customer_order();
-                return; // Don't reset selection
-            }
-            Screen::Inventory => {
-                // Sell inventory item (stay on inventory screen)
-                self.sell_inventory_item();
-                return; // Don't reset selection
-            }
-            Screen::RandomEvent => {
-                // Handle random event choice
-                self.handle_random_event_choice();
-                return; // Don't reset selection
-            }
-            _ => {
-                // Other screens return to dashboard
-                self.screen = Screen::Dashboard;
-            }
-        }
-        
-        // Reset selection when changing screens
-        if !matches!((previous_screen, &self.screen), (Screen::Market, Screen::Market)) {
-            self.selected_menu_item = 0;
+        // Check speed demon (5 orders in one day)
+        self.check_and_unlock(&AchievementType::SpeedDemon, self.orders_today, day, activities);
+    }
+
+    fn record_order_expiry(&mut self) {
+        self.orders_expired_today += 1;
+    }
+
+    fn record_market_purchase(&mut self, price_multiplier: f32, day: u32, activities: &mut Vec<String>) {
+        // Favorable market = prices 10%+ below normal
+        if price_multiplier <= 0.9 {
+            self.favorable_market_purchases += 1;
+            self.check_and_unlock(&AchievementType::MarketMaster, self.favorable_market_purchases, day, activities);
         }
     }
 
-    fn go_back(&mut self) {
-        match self.screen {
-            Screen::MainMenu => self.should_quit = true,
-            Screen::Dashboard => self.screen = Screen::MainMenu,
-            _ => self.screen = Screen::Dashboard,
+    // Cards sold `days_held` days after they were bought
+    fn record_sale_turnaround(&mut self, days_held: u32, day: u32, activities: &mut Vec<String>) {
+        if days_held <= 3 {
+            self.check_and_unlock(&AchievementType::QuickTurnaround, 1, day, activities);
         }
-        self.selected_menu_item = 0;
     }
 
-    fn save_game(&mut self) -> bool {
-        const SAVE_FILE: &str = "savegame.json";
-        
-        match self.game_data.save_game(SAVE_FILE) {
-            Ok(()) => {
-                self.game_data.recent_activities.insert(0, "💾 Game saved successfully!".to_string());
-                if self.game_data.recent_activities.len() > 10 {
-                    self.game_data.recent_activities.truncate(10);
-                }
-                true
-            }
-            Err(_) => {
-                self.game_data.recent_activities.insert(0, "❌ Failed to save game".to_string());
-                if self.game_data.recent_activities.len() > 10 {
-                    self.game_data.recent_activities.truncate(10);
-                }
-                false
+    // Closes out the day that just ended using the per-day order counters
+    fn process_daily_achievements(&mut self, analytics: &BusinessAnalytics, day: u32, activities: &mut Vec<String>) {
+        // Track perfect days: any expiry breaks the streak, a quiet day leaves it alone
+        if self.orders_expired_today > 0 {
+            self.consecutive_perfect_days = 0;
+        } else if self.orders_today > 0 {
+            self.consecutive_perfect_days += 1;
+        }
+
+        // Reset daily counters
+        self.orders_today = 0;
+        self.orders_expired_today = 0;
+
+        // Check perfect week
+        self.check_and_unlock(&AchievementType::PerfectWeek, self.consecutive_perfect_days, day, activities);
+
+        // Track efficiency
+        let total_orders = analytics.orders_completed + analytics.orders_expired;
+        if total_orders > 0 {
+            let success_rate = analytics.orders_completed as f32 / total_orders as f32;
+            if success_rate >= 0.9 {
+                self.consecutive_efficiency_days += 1;
+            } else {
+                self.consecutive_efficiency_days = 0;
             }
         }
+
+        // Check efficiency achievement
+        self.check_and_unlock(&AchievementType::Efficiency, self.consecutive_efficiency_days, day, activities);
+
+        // Market share achievements need the full 30 days of market days on record
+        if analytics.market_history.len() >= MARKET_SHARE_WINDOW_DAYS {
+            let share_percent = (analytics.market_share(MARKET_SHARE_WINDOW_DAYS) * 100.0) as u32;
+            self.check_and_unlock(&AchievementType::MarketPlayer, share_percent, day, activities);
+            self.check_and_unlock(&AchievementType::MarketLeader, share_percent, day, activities);
+        }
     }
 
-    fn load_game(&mut self) -> bool {
-        const SAVE_FILE: &str = "savegame.json";
-        
-        match GameData::load_game(SAVE_FILE) {
-            Ok(loaded_game_data) => {
-                self.game_data = loaded_game_data;
-                self.game_data.recent_activities.insert(0, "📂 Game loaded successfully!".to_string());
-                if self.game_data.recent_activities.len() > 10 {
-                    self.game_data.recent_activities.truncate(10);
-                }
-                self.screen = Screen::Dashboard;
-                true
+    fn record_event_survival(&mut self, day: u32, activities: &mut Vec<String>) {
+        self.events_survived += 1;
+        self.check_and_unlock(&AchievementType::EventSurvivor, self.events_survived, day, activities);
+    }
+
+    fn check_and_unlock(&mut self, achievement_type: &AchievementType, progress: u32, day: u32, activities: &mut Vec<String>) {
+        if let Some(achievement) = self.achievements.iter_mut()
+            .find(|a| a.achievement_type == *achievement_type && !a.unlocked) {
+            
+            achievement.progress = achievement.progress.max(progress);
+            
+            if progress >= achievement.target {
+                achievement.unlock(day);
+                self.total_unlocked += 1;
+                self.recent_unlock = Some(achievement.name.clone());
+                self.reward_ledger.push(RewardLedgerEntry {
+                    achievement: achievement.name.clone(),
+                    amount: achievement.reward_cash,
+                    unlocked_day: day,
+                    claimed_day: None,
+                });
+                
+                activities.insert(0, format!(
+                    "🏆 Achievement Unlocked: {} (+${} reward)", 
+                    achievement.name, achievement.reward_cash
+                ));
             }
-            Err(_) => {
-                // Create error message in current game_data
-                self.game_data.recent_activities.insert(0, "❌ Failed to load game".to_string());
-                if self.game_data.recent_activities.len() > 10 {
-                    self.game_data.recent_activities.truncate(10);
-                }
-                false
+        }
+    }
+
+    fn pending_rewards(&self) -> u32 {
+        self.reward_ledger.iter()
+            .filter(|entry| entry.claimed_day.is_none())
+            .map(|entry| entry.amount)
+            .sum()
+    }
+
+    fn is_reward_pending(&self, achievement: &str) -> bool {
+        self.reward_ledger.iter().any(|entry| entry.achievement == achievement && entry.claimed_day.is_none())
+    }
+
+    // Marks every pending reward as claimed and returns the total to pay out
+    fn claim_rewards(&mut self, day: u32) -> u32 {
+        let mut total = 0;
+        for entry in self.reward_ledger.iter_mut().filter(|entry| entry.claimed_day.is_none()) {
+            entry.claimed_day = Some(day);
+            total += entry.amount;
+        }
+        total
+    }
+
+    // Achievements unlocked before the ledger existed were never paid; queue them now
+    fn backfill_reward_ledger(&mut self) {
+        for achievement in self.achievements.iter().filter(|a| a.unlocked) {
+            if !self.reward_ledger.iter().any(|entry| entry.achievement == achievement.name) {
+                self.reward_ledger.push(RewardLedgerEntry {
+                    achievement: achievement.name.clone(),
+                    amount: achievement.reward_cash,
+                    unlocked_day: achievement.unlock_date.unwrap_or(0),
+                    claimed_day: None,
+                });
             }
         }
     }
 
-    fn save_file_exists() -> bool {
-        const SAVE_FILE: &str = "savegame.json";
-        GameData::save_file_exists(SAVE_FILE)
+    // Saves from before an achievement existed get it added, still locked
+    fn add_missing_achievements(&mut self) {
+        let mut current = AchievementTracker::new().achievements;
+        current.retain(|achievement| !self.achievements.iter()
+            .any(|existing| existing.achievement_type == achievement.achievement_type));
+        self.achievements.extend(current);
     }
-}
 
-fn main() -> Result<(), Box<dyn Error>> {
-    // setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    // Saves made before a retailer was added still ask for the old count
+    fn sync_with_retailers(&mut self) {
+        if let Some(achievement) = self.achievements.iter_mut()
+            .find(|a| a.achievement_type == AchievementType::DiversifiedPortfolio && !a.unlocked) {
+            achievement.target = RETAILERS.len() as u32;
+            achievement.description = format!("Own cards from all {} retailers", RETAILERS.len());
+        }
+    }
 
-    // create app and run it
-    let app = App::new();
-    let res = run_app(&mut terminal, app);
+    fn get_recent_unlock(&mut self) -> Option<String> {
+        self.recent_unlock.take()
+    }
 
-    // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    fn get_unlocked_achievements(&self) -> Vec<&Achievement> {
+        self.achievements.iter().filter(|a| a.unlocked).collect()
+    }
 
-    if let Err(err) = res {
-        println!("{err:?}");
+    fn get_in_progress_achievements(&self) -> Vec<&Achievement> {
+        self.achievements.iter().filter(|a| !a.unlocked && a.progress > 0).collect()
     }
 
-    Ok(())
+    fn calculate_total_rewards(&self) -> u32 {
+        self.achievements.iter()
+            .filter(|a| a.unlocked)
+            .map(|a| a.reward_cash)
+            .sum()
+    }
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
-    loop {
-        // Update game time
-        app.update_time();
-        
-        // Check for active random events that need player choice
-        app.check_for_active_events();
-        
-        // Check for audio events (achievements, new orders, etc.)
-        app.check_for_audio_events();
-        
-        terminal.draw(|f| ui(f, &app))?;
+impl RandomEvent {
+    fn apply_choice(&mut self, choice: usize) -> EventOutcome {
+        let effect = self.choice_effects.get(choice).cloned().unwrap_or_default();
 
-        // Use poll instead of read to avoid blocking
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') => return Ok(()),
-                    KeyCode::Esc => app.go_back(),
-                    KeyCode::Down => app.next_menu_item(),
-                    KeyCode::Up => app.previous_menu_item(),
-                    KeyCode::Enter => app.select_menu_item(),
-                    KeyCode::Char(' ') => app.toggle_pause(), // Spacebar to pause
-                    // Number key quick access for dashboard
-                    KeyCode::Char('1') if matches!(app.screen, Screen::Dashboard) => {
-                        app.selected_menu_item = 0;
-                        app.select_menu_item();
-                    },
-                    KeyCode::Char('2') if matches!(app.screen, Screen::Dashboard) => {
-                        app.selected_menu_item = 1;
-                        app.select_menu_item();
-                    },
-                    KeyCode::Char('3') if matches!(app.screen, Screen::Dashboard) => {
-                        app.selected_menu_item = 2;
-                        app.select_menu_item();
-                    },
-                    KeyCode::Char('4') if matches!(app.screen, Screen::Dashboard) => {
-                        app.selected_menu_item = 3;
-                        app.select_menu_item();
-                    },
-                    KeyCode::Char('5') if matches!(app.screen, Screen::Dashboard) => {
-                        app.selected_menu_item = 4;
-                        app.select_menu_item();
-                    },
-                    KeyCode::Char('6') if matches!(app.screen, Screen::Dashboard) => {
-                        app.selected_menu_item = 5;
-                        app.select_menu_item();
-                    },
-                    KeyCode::Char('7') if matches!(app.screen, Screen::Dashboard) => {
-                        app.selected_menu_item = 6;
-                        app.select_menu_item();
-                    },
-                    KeyCode::Char('8') if matches!(app.screen, Screen::Dashboard) => {
-                        app.selected_menu_item = 7;
-                        app.select_menu_item();
-                    },
-                    KeyCode::Char('s') | KeyCode::Char('S') => {
-                        // Toggle sound effects
-                        app.sound_effects.toggle();
-                        let status = if app.sound_effects.is_enabled() { 
-                            "🔊 Sound effects enabled" 
-                        } else { 
-                            "🔇 Sound effects disabled" 
-                        };
-                        app.game_data.recent_activities.insert(0, status.to_string());
-                        if app.game_data.recent_activities.len() > 10 {
-                            app.game_data.recent_activities.truncate(10);
-                        }
-                    },
-                    _ => {}
-                }
-            }
-        }
+        self.cash_impact = effect.cash;
+        self.reputation_impact = effect.reputation;
+        self.inventory_impact = effect.inventory.iter()
+            .map(|change| (change.retailer.clone(), change.quantity))
+            .collect();
 
-        if app.should_quit {
-            return Ok(());
+        EventOutcome {
+            title: self.title.clone(),
+            cash: self.cash_impact,
+            reputation: self.reputation_impact,
+            inventory: self.inventory_impact.clone(),
+            modifiers: effect.modifiers.iter().map(ModifierTemplate::instantiate).collect(),
         }
     }
-}
-
-fn ui(f: &mut Frame, app: &App) {
-    match app.screen {
-        Screen::MainMenu => draw_main_menu(f, app),
-        Screen::Dashboard => draw_dashboard(f, app),
-        Screen::Market => draw_market(f, app),
-        Screen::Orders => draw_orders(f, app),
-        Screen::Inventory => draw_inventory(f, app),
-        Screen::Analytics => draw_analytics(f, app),
-        Screen::Achievements => draw_achievements_screen(f, app),
-        Screen::Settings => draw_placeholder(f, "Settings", "Game configuration"),
-        Screen::RandomEvent => draw_random_event(f, app),
+    
+    fn get_choices(&self) -> Vec<&str> {
+        let mut choices = Vec::new();
+        if let Some(choice) = &self.choice_a {
+            choices.push(choice.as_str());
+        }
+        if let Some(choice) = &self.choice_b {
+            choices.push(choice.as_str());
+        }
+        if let Some(choice) = &self.choice_c {
+            choices.push(choice.as_str());
+        }
+        choices
     }
 }
 
-fn draw_main_menu(f: &mut Frame, app: &App) {
-    let size = f.area();
 
-    let block = Block::default()
-        .title("GIFT CARD EMPIRE")
-        .borders(Borders::ALL)
-        .style(Style::default().fg(Color::White));
+impl RandomEventManager {
+    fn new() -> Self {
+        Self {
+            active_event: None,
+            next_event_in_days: 4, // First event on day 5
+            event_history: Vec::new(),
+            player_choice_pending: false,
+            choice_deadline: 0,
+            temp_modifiers: Vec::new(),
+        }
+    }
 
-    let save_available = App::save_file_exists();
-    let continue_text = if save_available {
-        "Continue Game"
-    } else {
-        "Continue (No save file found)"
-    };
-    
-    let menu_items = vec![
-        "New Game",
-        continue_text,
-        "Tutorial", 
-        "Quit",
-    ];
+    // Returns the outcomes of every event resolved today (auto events and forced choices)
+    fn process_daily_events(&mut self, catalog: &EventCatalog, context: &EventContext, rng: &mut GameRng, activities: &mut Vec<String>) -> Vec<EventOutcome> {
+        let day = context.day;
+        let mut outcomes = Vec::new();
 
-    let items: Vec<ListItem> = menu_items
-        .iter()
-        .enumerate()
-        .map(|(i, item)| {
-            let style = if i == app.selected_menu_item {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
-            
-            let prefix = if i == app.selected_menu_item { "► " } else { "  " };
-            ListItem::new(Line::from(Span::styled(format!("{}{}", prefix, item), style)))
-        })
-        .collect();
-
-    let list = List::new(items)
-        .block(block)
-        .style(Style::default().fg(Color::White));
+        // Age temporary modifiers
+        self.temp_modifiers.retain_mut(|modifier| {
+            modifier.age_day();
+            if modifier.is_expired() {
+                activities.insert(0, format!("📅 {} effect has ended", modifier.name));
+                false
+            } else {
+                true
+            }
+        });
 
-    let area = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(100)])
-        .split(size)[0];
+        // Check for choice deadline
+        if self.player_choice_pending && day >= self.choice_deadline {
+            // Force auto-resolve if player didn't choose
+            if let Some(ref mut event) = self.active_event {
+                outcomes.push(event.apply_choice(0)); // Default to first choice
+                activities.insert(0, format!("⏰ {} auto-resolved (no choice made)", event.title));
+                
+                self.player_choice_pending = false;
+                self.active_event = None;
+            }
+        }
 
-    f.render_widget(list, area);
+        // Check for new events
+        if self.active_event.is_none() && self.next_event_in_days > 0 {
+            self.next_event_in_days -= 1;
+        } else if self.active_event.is_none() && self.next_event_in_days == 0 {
+            if let Some(mut new_event) = self.generate_random_event(catalog, context, rng) {
+                activities.insert(0, format!("🎲 Random event: {}", new_event.title));
+                
+                if new_event.auto_resolve {
+                    // Auto-resolve immediate events
+                    outcomes.push(new_event.apply_choice(0));
+                } else {
+                    // Set up choice event (generate_random_event stored it as active)
+                    self.player_choice_pending = true;
+                    self.choice_deadline = day + 2; // 2 days to choose
+                }
+            }
+            self.next_event_in_days = rng.range(3, 7); // Schedule next event
+        }
 
-    // Instructions at the bottom
-    let instructions = Paragraph::new("Use ↑↓ to navigate, Enter to select, Q to quit")
-        .style(Style::default().fg(Color::Gray))
-        .alignment(Alignment::Center);
+        outcomes
+    }
 
-    let instruction_area = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(1)])
-        .split(size)[1];
+    fn generate_random_event(&mut self, catalog: &EventCatalog, context: &EventContext, rng: &mut GameRng) -> Option<RandomEvent> {
+        let event = catalog.pick_random_event(context, rng)?.instantiate();
 
-    f.render_widget(instructions, instruction_area);
-}
+        // Schedule next event
+        self.next_event_in_days = rng.range(7, 20); // Next event in 7-20 days
+        
+        // Record in history
+        self.event_history.push(format!("Day {}: {}", context.day, event.title));
+        if self.event_history.len() > 10 {
+            self.event_history.remove(0); // Keep only last 10 events
+        }
 
-fn draw_dashboard(f: &mut Frame, app: &App) {
-    let size = f.area();
-    
-    // Create layout: Header, Main content, Footer
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Main content
-            Constraint::Length(3), // Footer
-        ])
-        .split(size);
+        // Set as active event
+        if !event.auto_resolve {
+            // Store for player choice (auto events are processed immediately)
+            self.active_event = Some(event.clone());
+        }
+        Some(event)
+    }
 
-    // Header with game stats including season
-    let season = app.game_data.market_conditions.current_season.display();
-    let active_events = app.game_data.market_conditions.active_events.len();
-    let events_info = if active_events > 0 {
-        format!(" • {} events", active_events)
-    } else {
-        String::new()
-    };
-    
-    // Add animated time progression indicator
-    let time_indicator = if app.paused {
-        "⏸️"
-    } else {
-        // Cycle through different clock symbols for visual time progression
-        match (app.game_data.minute / 10) % 6 {
-            0 => "🕐",
-            1 => "🕑",
-            2 => "🕒",
-            3 => "🕓",
-            4 => "🕔",
-            _ => "🕕",
+    // Saves from before the event catalog stored no effects on a pending event; look them up
+    // again by title, or drop the event if the catalog no longer has it
+    fn relink_active_event(&mut self, catalog: &EventCatalog) {
+        let Some(event) = &mut self.active_event else {
+            return;
+        };
+        if !event.choice_effects.is_empty() {
+            return;
         }
-    };
-    
-    // Add random event indicator if active
-    let random_event_status = if app.game_data.random_events.player_choice_pending {
-        " 🎲❗"
-    } else if app.game_data.random_events.temp_modifiers.len() > 0 {
-        " 🎲✨"
-    } else {
-        ""
-    };
-    
-    let header_text = format!(
-        "Cash: ${}    Rep: {} ({})    Day: {}    Time: {} {}    Season: {}{}{}",
-        app.game_data.cash,
-        app.game_data.reputation_stars(),
-        app.game_data.reputation_description(),
-        app.game_data.day,
-        app.game_data.time_display(),
-        time_indicator,
-        season,
-        events_info,
-        random_event_status
-    );
-    
-    let header = Paragraph::new(header_text)
-        .block(Block::default()
-            .title("Gift Card Empire")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Green))
-        .alignment(Alignment::Center);
-    
-    f.render_widget(header, chunks[0]);
+        match catalog.random_events.iter().find(|def| def.title == event.title) {
+            Some(def) => event.choice_effects = def.instantiate().choice_effects,
+            None => {
+                self.active_event = None;
+                self.player_choice_pending = false;
+            }
+        }
+    }
 
-    // Main content area split into menu and activity
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(50), // Menu
-            Constraint::Percentage(50), // Activity feed
-        ])
-        .split(chunks[1]);
+    fn make_choice(&mut self, choice: usize) -> Option<EventOutcome> {
+        if let Some(ref mut event) = self.active_event {
+            let result = event.apply_choice(choice);
+            self.player_choice_pending = false;
+            self.active_event = None;
+            Some(result)
+        } else {
+            None
+        }
+    }
 
-    // Menu options
-    let menu_items = vec![
-        "[1] Market",
-        "[2] Orders", 
-        "[3] Inventory",
-        "[4] Analytics",
-        "[5] Achievements",
-        "[6] Settings",
-        "[7] Save Game",
-        "[8] Quit to Menu",
-    ];
+    #[allow(dead_code)]
+    fn get_active_choice_event(&self) -> Option<&RandomEvent> {
+        if self.player_choice_pending {
+            self.active_event.as_ref()
+        } else {
+            None
+        }
+    }
 
-    let menu_list_items: Vec<ListItem> = menu_items
-        .iter()
-        .enumerate()
-        .map(|(i, item)| {
-            let style = if i == app.selected_menu_item {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
-            
-            let prefix = if i == app.selected_menu_item { "► " } else { "  " };
-            ListItem::new(Line::from(Span::styled(format!("{}{}", prefix, item), style)))
-        })
-        .collect();
+    #[allow(dead_code)]
+    fn get_total_price_multiplier(&self) -> f32 {
+        self.temp_modifiers.iter()
+            .map(|m| m.price_multiplier)
+            .product()
+    }
 
-    let menu_list = List::new(menu_list_items)
-        .block(Block::default()
-            .title("Quick Actions")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::White));
+    fn get_total_demand_multiplier(&self) -> f32 {
+        self.temp_modifiers.iter()
+            .map(|m| m.demand_multiplier)
+            .product()
+    }
 
-    f.render_widget(menu_list, main_chunks[0]);
+    fn has_reputation_protection(&self) -> bool {
+        self.temp_modifiers.iter()
+            .any(|m| m.reputation_protection)
+    }
 
-    // Recent activity feed
-    let activity_items: Vec<ListItem> = app.game_data.recent_activities
-        .iter()
-        .map(|activity| {
-            ListItem::new(Line::from(Span::styled(
-                format!("• {}", activity),
-                Style::default().fg(Color::Cyan)
-            )))
-        })
-        .collect();
+    #[allow(dead_code)]
+    fn get_active_modifiers(&self) -> &[TempModifier] {
+        &self.temp_modifiers
+    }
+}
 
-    let activity_list = List::new(activity_items)
-        .block(Block::default()
-            .title("Recent Activity")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::White));
+impl GameData {
+    fn new() -> Self {
+        Self::with_options(Difficulty::Normal, GameRng::from_entropy())
+    }
 
-    f.render_widget(activity_list, main_chunks[1]);
+    fn with_seed(seed: u64) -> Self {
+        Self::with_options(Difficulty::Normal, GameRng::new(seed))
+    }
 
-    // Footer with controls and pause status
-    let pause_indicator = if app.paused { " ⏸️ PAUSED" } else { "" };
-    let sound_indicator = if app.sound_effects.is_enabled() { " 🔊" } else { " 🔇" };
-    let footer_text = format!(
-        "↑↓ Navigate  Enter Select  [1-8] Quick Access  Space Pause  S Sound{}  Esc Back  Q Quit{}",
-        sound_indicator,
-        pause_indicator
-    );
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Gray))
-        .alignment(Alignment::Center);
+    fn with_options(difficulty: Difficulty, rng: GameRng) -> Self {
+        let day = 1;
+        // Create some sample inventory for testing, expiring 15-120 days from the start
+        let sample_inventory = vec![
+            InventoryItem::new(
+                GiftCard::new("Amazon", 25, 20, day + 45),
+                12
+            ),
+            InventoryItem::new(
+                GiftCard::new("Target", 50, 42, day + 30),
+                8
+            ),
+            InventoryItem::new(
+                GiftCard::new("Starbucks", 10, 8, day + 120),
+                15
+            ),
+            InventoryItem::new(
+                GiftCard::new("iTunes", 15, 12, day + 15),
+                3
+            ),
+            InventoryItem::new(
+                GiftCard::new("Walmart", 20, 17, day + 60),
+                6
+            ),
+        ];
 
-    f.render_widget(footer, chunks[2]);
-}
+        let starting_cash = difficulty.starting_cash();
+        let mut game_data = Self {
+            cash: starting_cash,
+            reputation: 3,
+            day,
+            hour: 9,
+            minute: 0,
+            recent_activities: vec![
+                "Welcome to Gift Card Empire!".to_string(),
+                format!("Starting with ${} capital ({} mode)", starting_cash, difficulty.display()),
+                "Visit the Market to buy your first cards".to_string(),
+            ],
+            inventory: sample_inventory,
+            customer_orders: VecDeque::new(),
+            next_order_id: 1000,
+            analytics: BusinessAnalytics::new(),
+            market_conditions: MarketConditions::new(),
+            achievements: AchievementTracker::new(),
+            random_events: RandomEventManager::new(),
+            rng,
+            difficulty,
+            event_catalog: EventCatalog::default(),
+            cost_basis_policy: CostBasisPolicy::default(),
+            market: Market::new(),
+            decline_strain: 0,
+            customers: Vec::new(),
+            auto_claim_rewards: false,
+            storage_level: 0,
+            day_start_cash: Some(starting_cash),
+            active_goals: default_active_goals(),
+            goals_reached: Vec::new(),
+            low_reputation_days: 0,
+            game_over: None,
+            competitors: Competitor::initial_rivals(),
+            retail_prices: Vec::new(),
+            price_history: BTreeMap::new(),
+        };
+        game_data.customers = Customer::initial_base(&mut game_data.rng);
+        game_data.record_price_history();
 
-fn draw_market(f: &mut Frame, app: &App) {
-    let size = f.area();
-    
-    // Create layout: Header, Market table, Footer
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Market content
-            Constraint::Length(3), // Footer
-        ])
-        .split(size);
+        // Generate some initial customer orders
+        game_data.generate_random_order();
+        game_data.generate_random_order();
+        
+        game_data
+    }
 
-    // Header showing budget
-    let header_text = format!("Your Budget: ${}", app.game_data.cash);
-    let header = Paragraph::new(header_text)
-        .block(Block::default()
-            .title("Wholesale Market")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Green))
-        .alignment(Alignment::Center);
-    
-    f.render_widget(header, chunks[0]);
+    fn advance_time(&mut self, minutes: u8) {
+        let previous_hour = self.hour;
+        self.minute += minutes;
+        if self.minute >= 60 {
+            self.hour += self.minute / 60;
+            self.minute %= 60;
+        }
 
-    // Market items table with dynamic pricing
-    let base_market_items = vec![
-        ("Amazon", 25, 20, 50),     // (retailer, value, base_cost, stock)
-        ("Starbucks", 10, 8, 30),
-        ("Target", 50, 42, 15),
-        ("iTunes", 15, 12, 25),
-        ("Walmart", 20, 17, 40),
-    ];
-    
-    let market_items: Vec<(String, u32, u32, u32, String)> = base_market_items.iter()
-        .map(|(retailer, value, base_cost, stock)| {
-            let price_multiplier = app.game_data.market_conditions.get_price_multiplier_with_random_events(retailer, &app.game_data.random_events);
-            let actual_cost = (*base_cost as f32 * price_multiplier).round() as u32;
-            // More detailed animated trend indicators
-            let trend = if price_multiplier > 1.2 {
-                match (app.game_data.minute / 5) % 3 {
-                    0 => "🔥↗".to_string(),
-                    1 => "🚀↗".to_string(), 
-                    _ => "📈↗".to_string(),
-                }
-            } else if price_multiplier > 1.1 {
-                "📈↗".to_string() // Rising
-            } else if price_multiplier < 0.8 {
-                match (app.game_data.minute / 5) % 3 {
-                    0 => "💎↘".to_string(),
-                    1 => "🎯↘".to_string(),
-                    _ => "📉↘".to_string(),
-                }
-            } else if price_multiplier < 0.9 {
-                "📉↘".to_string() // Falling  
+        // Walk-in customers shop through each business hour
+        if self.hour != previous_hour && BUSINESS_HOURS.contains(&previous_hour) {
+            self.process_walk_in_sales();
+        }
+        
+        if self.hour >= 24 {
+            self.day += (self.hour / 24) as u32;
+            self.hour %= 24;
+            
+            // Process daily events when a new day starts
+            self.process_daily_events();
+        }
+    }
+
+    fn process_daily_events(&mut self) {
+        // Remove expired cards and calculate losses
+        let mut expired_value = 0;
+        let mut expired_count = 0;
+        let today = self.day;
+        
+        self.inventory.retain(|item| {
+            if item.card.is_expired(today) {
+                expired_value += item.total_cost();
+                expired_count += item.quantity;
+                false
             } else {
-                "➡️".to_string() // Stable
-            };
-            (retailer.to_string(), *value, actual_cost, *stock, trend)
-        })
-        .collect();
+                true
+            }
+        });
+
+        if expired_count > 0 {
+            // Record expired cards in analytics
+            self.analytics.record_expired_cards(expired_count);
+            
+            self.recent_activities.insert(0, format!(
+                "❌ Lost {} cards worth ${} to expiration", 
+                expired_count, expired_value
+            ));
+            
+            // Keep only the last 10 activities
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+        }
+
+        // Close yesterday's local market, under the conditions it ran in, before today's orders come in
+        let market_size = RETAILERS.iter()
+            .map(|retailer| (retailer.id.to_string(), self.local_market_size(retailer.id)))
+            .collect();
+        self.analytics.close_market_day(self.day.saturating_sub(1), market_size);
+
+        // Process customer orders aging
+        self.process_order_aging();
+
+        // Rent, wages, upkeep and fees, then close the books on yesterday's cash flow
+        self.charge_operating_expenses();
+        if let Some(day_start_cash) = self.day_start_cash {
+            self.analytics.record_daily_cash_flow(self.cash as i32 - day_start_cash as i32);
+        }
+        self.day_start_cash = Some(self.cash);
+
+        // Start new day in analytics
+        self.analytics.start_new_day();
+
+        // Update market conditions and process events
+        self.market_conditions.update_season(self.day);
+        let event_context = self.event_context();
+        let ended_events = self.market_conditions.process_daily_events(&self.event_catalog, &event_context, &mut self.rng, &mut self.recent_activities);
+        for _ in 0..ended_events {
+            self.achievements.record_event_survival(self.day, &mut self.recent_activities);
+        }
+        self.market.restock(&self.market_conditions);
+        self.process_competitors();
+        self.record_price_history();
+
+        // Process daily achievements
+        self.achievements.process_daily_achievements(&self.analytics, self.day, &mut self.recent_activities);
+
+        // Check cash and inventory achievements
+        self.achievements.check_cash_achievements(self.cash, self.day, &mut self.recent_activities);
+        self.achievements.check_inventory_achievements(&self.inventory, self.day, &mut self.recent_activities);
+        self.achievements.check_seasonal_achievements(&self.market_conditions.current_season, self.achievements.seasonal_winter_profit, self.day, &mut self.recent_activities);
+
+        // Process random events and apply whatever resolved today
+        let event_context = self.event_context();
+        let outcomes = self.random_events.process_daily_events(&self.event_catalog, &event_context, &mut self.rng, &mut self.recent_activities);
+        for outcome in outcomes {
+            self.apply_event_outcome(outcome);
+        }
+
+        // See whether the run has been won or lost
+        self.evaluate_game_conditions();
+
+        // Add daily startup message
+        let season = self.market_conditions.current_season.display();
+        self.recent_activities.insert(0, format!("🌅 Day {} begins ({} season)", self.day, season));
+        if self.recent_activities.len() > 10 {
+            self.recent_activities.truncate(10);
+        }
+    }
+
+    fn reputation_stars(&self) -> String {
+        let filled = "★".repeat(self.reputation as usize);
+        let empty = "☆".repeat(5 - self.reputation as usize);
+        format!("{}{}", filled, empty)
+    }
+
+    fn reputation_description(&self) -> &str {
+        match self.reputation {
+            5 => "Legendary",
+            4 => "Excellent", 
+            3 => "Good",
+            2 => "Fair",
+            1 => "Poor",
+            _ => "Unknown",
+        }
+    }
+
+    fn time_display(&self) -> String {
+        let period = if self.hour < 12 { "AM" } else { "PM" };
+        let display_hour = if self.hour == 0 { 12 } else if self.hour > 12 { self.hour - 12 } else { self.hour };
+        format!("{}:{:02} {}", display_hour, self.minute, period)
+    }
+
+    fn total_inventory_value(&self) -> u32 {
+        self.inventory.iter().map(|item| item.total_value()).sum()
+    }
+
+    fn total_inventory_cost(&self) -> u32 {
+        self.inventory.iter().map(|item| item.total_cost()).sum()
+    }
+
+    // Paper gains on cards still held: market value minus what we paid
+    fn unrealized_gains(&self) -> i32 {
+        self.total_inventory_value() as i32 - self.total_inventory_cost() as i32
+    }
+
+    fn inventory_count(&self) -> u32 {
+        self.inventory.iter().map(|item| item.quantity).sum()
+    }
+
+    fn net_worth(&self) -> u32 {
+        self.cash + self.total_inventory_value()
+    }
+
+    fn competitor_orders_won(&self) -> u32 {
+        self.competitors.iter().map(|competitor| competitor.orders_won).sum()
+    }
+
+    fn competitor_share(&self, competitor: &Competitor) -> f32 {
+        self.analytics.rival_market_share(&competitor.name, MARKET_SHARE_WINDOW_DAYS)
+    }
+
+    // Our share of the local gift card market over the last 30 days
+    fn market_share(&self) -> f32 {
+        self.analytics.market_share(MARKET_SHARE_WINDOW_DAYS)
+    }
+
+    fn goal_reached(&self, goal: GameGoal) -> Option<u32> {
+        self.goals_reached.iter().find(|(reached, _)| *reached == goal).map(|(_, day)| *day)
+    }
+
+    fn is_bankrupt(&self) -> bool {
+        self.cash == 0
+            && self.inventory_count() == 0
+            && self.achievements.pending_rewards() == 0
+            && self.analytics.recent_cash_flow(7) < 0
+    }
+
+    fn is_inventory_crisis(&self) -> bool {
+        let expired = self.analytics.recent_expired_cards(30);
+        expired >= INVENTORY_CRISIS_MIN_CARDS
+            && expired as f32 >= (expired + self.inventory_count()) as f32 * INVENTORY_CRISIS_SHARE
+    }
+
+    // Run once a day: records newly reached goals and ends the run on a failure or
+    // once every active goal has been reached
+    fn evaluate_game_conditions(&mut self) {
+        if self.game_over.is_some() {
+            return;
+        }
+
+        if self.reputation < 2 {
+            self.low_reputation_days += 1;
+        } else {
+            self.low_reputation_days = 0;
+        }
+
+        for goal in GameGoal::ALL {
+            if self.goal_reached(goal).is_none() && goal.is_met(self) {
+                self.goals_reached.push((goal, self.day));
+                self.recent_activities.insert(0, format!("🎯 Goal reached: {}", goal.display()));
+            }
+        }
+
+        let outcome = if self.is_bankrupt() {
+            Some(GameOutcome::Bankruptcy)
+        } else if self.low_reputation_days >= REPUTATION_LOSS_DAYS {
+            Some(GameOutcome::ReputationLoss)
+        } else if self.is_inventory_crisis() {
+            Some(GameOutcome::InventoryCrisis)
+        } else if !self.active_goals.is_empty()
+            && self.active_goals.iter().all(|goal| self.goal_reached(*goal).is_some()) {
+            Some(GameOutcome::Victory)
+        } else {
+            None
+        };
+
+        if let Some(outcome) = outcome {
+            self.game_over = Some(GameOver { outcome, day: self.day });
+            self.recent_activities.insert(0, format!("🏁 Game over on day {}: {}", self.day, outcome.display()));
+        }
+        if self.recent_activities.len() > 10 {
+            self.recent_activities.truncate(10);
+        }
+    }
+
+    fn toggle_goal(&mut self, goal: GameGoal) {
+        if let Some(index) = self.active_goals.iter().position(|active| *active == goal) {
+            self.active_goals.remove(index);
+        } else {
+            self.active_goals.push(goal);
+        }
+    }
+
+    #[allow(dead_code)]
+    fn expiring_items_count(&self) -> usize {
+        self.inventory.iter().filter(|item| item.card.is_expiring_soon(self.day)).count()
+    }
+
+    fn add_to_inventory(&mut self, card: GiftCard, quantity: u32) {
+        // Check if we already have this type of card
+        for item in &mut self.inventory {
+            if item.card.retailer == card.retailer && 
+               item.card.denomination == card.denomination &&
+               item.card.purchase_price == card.purchase_price &&
+               item.card.purchase_day == card.purchase_day &&
+               item.card.expiration_day == card.expiration_day {
+                item.quantity += quantity;
+                return;
+            }
+        }
+        
+        // Add new inventory item if not found
+        self.inventory.push(InventoryItem::new(card, quantity));
+    }
+
+    fn storage_tier(&self) -> &'static StorageTier {
+        &STORAGE_TIERS[self.storage_level.min(STORAGE_TIERS.len() - 1)]
+    }
+
+    fn next_storage_tier(&self) -> Option<&'static StorageTier> {
+        STORAGE_TIERS.get(self.storage_level + 1)
+    }
+
+    fn storage_capacity(&self) -> u32 {
+        self.storage_tier().capacity
+    }
+
+    // Room left for purchased cards (gifts and returns can still push stock past capacity)
+    fn free_storage(&self) -> u32 {
+        self.storage_capacity().saturating_sub(self.inventory_count())
+    }
+
+    fn storage_utilization(&self) -> f32 {
+        self.inventory_count() as f32 / self.storage_capacity() as f32 * 100.0
+    }
+
+    // "45/100 (45%)"
+    fn storage_display(&self) -> String {
+        format!("{}/{} ({:.0}%)", self.inventory_count(), self.storage_capacity(), self.storage_utilization())
+    }
+
+    fn upgrade_storage(&mut self) -> bool {
+        let Some(next) = self.next_storage_tier() else {
+            self.recent_activities.insert(0, "❌ Storage is already at the largest tier".to_string());
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+            return false;
+        };
+        if !self.spend_money(next.upgrade_cost) {
+            self.recent_activities.insert(0, format!(
+                "❌ {} costs ${} (cash ${})", next.name, next.upgrade_cost, self.cash
+            ));
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+            return false;
+        }
+
+        self.storage_level += 1;
+        self.analytics.record_expense(ExpenseCategory::StorageUpgrades, next.upgrade_cost);
+        self.recent_activities.insert(0, format!(
+            "🏬 Upgraded to {} for ${}: room for {} cards, ${}/day upkeep",
+            next.name, next.upgrade_cost, next.capacity, next.daily_upkeep
+        ));
+        if self.recent_activities.len() > 10 {
+            self.recent_activities.truncate(10);
+        }
+        true
+    }
+
+    // Note today's wholesale prices and demand for every retailer
+    fn record_price_history(&mut self) {
+        for retailer in RETAILERS {
+            let costs = self.market.listings.iter()
+                .filter(|listing| listing.retailer == retailer.id)
+                .map(|listing| (listing.denomination, self.wholesale_price(listing)))
+                .collect();
+            let point = PricePoint { day: self.day, costs, demand_multiplier: self.demand_multiplier(retailer.id) };
+            let history = self.price_history.entry(retailer.id.to_string()).or_default();
+            history.retain(|existing| existing.day != point.day);
+            history.push(point);
+            if history.len() > PRICE_HISTORY_DAYS {
+                history.remove(0);
+            }
+        }
+    }
+
+    // Recorded wholesale prices of one card, oldest first
+    fn price_history_for(&self, retailer: &str, denomination: u32) -> Vec<u32> {
+        self.price_history.get(retailer)
+            .map(|history| history.iter().filter_map(|point| point.costs.get(&denomination).copied()).collect())
+            .unwrap_or_default()
+    }
+
+    fn demand_history_for(&self, retailer: &str) -> Vec<f32> {
+        self.price_history.get(retailer)
+            .map(|history| history.iter().map(|point| point.demand_multiplier).collect())
+            .unwrap_or_default()
+    }
+
+    // Cards per day customers order of a retailer at normal demand. Starts from what order
+    // generation should produce and moves toward what we've actually seen as history builds.
+    fn base_order_rate(&self, retailer: &str) -> f32 {
+        let prior = self.expected_order_cards(retailer);
+
+        let history = &self.analytics.market_history;
+        let observed: u32 = history.iter().map(|market_day| market_day.ordered.get(retailer).copied().unwrap_or(0)).sum();
+        let demand = self.demand_history_for(retailer);
+        let average_demand = if demand.is_empty() { 1.0 } else { demand.iter().sum::<f32>() / demand.len() as f32 };
+        let normalized = observed as f32 / average_demand.max(0.1);
+
+        (prior * FORECAST_PRIOR_DAYS + normalized) / (FORECAST_PRIOR_DAYS + history.len() as f32)
+    }
+
+    // Demand and wholesale price multipliers `offset` days from today: the season that day falls in,
+    // plus market events and temporary modifiers that will still be running
+    fn projected_multipliers(&self, retailer: &str, offset: u32) -> (f32, f32) {
+        let season = Season::from_day(self.day + offset);
+        let mut demand = season.demand_modifier() * season.retailer_bonus(retailer);
+        let mut price = season.retailer_bonus(retailer);
+        for event in &self.market_conditions.active_events {
+            if offset < event.remaining_days && event.affects_retailer(retailer) {
+                demand *= event.demand_multiplier;
+                price *= event.price_multiplier;
+            }
+        }
+        for modifier in &self.random_events.temp_modifiers {
+            if offset < modifier.remaining_days {
+                demand *= modifier.demand_multiplier;
+                price *= modifier.price_multiplier;
+            }
+        }
+        (demand, price)
+    }
+
+    fn forecast(&self, days: u32) -> Vec<RetailerForecast> {
+        RETAILERS.iter()
+            .map(|retailer| {
+                let base_rate = self.base_order_rate(retailer.id);
+                let today_price = self.market_conditions.get_price_multiplier_with_random_events(retailer.id, &self.random_events);
+                let (daily_orders, price_multipliers) = (1..=days)
+                    .map(|offset| {
+                        let (demand, price) = self.projected_multipliers(retailer.id, offset);
+                        (base_rate * demand, price / today_price.max(0.01))
+                    })
+                    .unzip();
+                let mut forecast = RetailerForecast {
+                    retailer: retailer.id.to_string(),
+                    daily_orders,
+                    price_multipliers,
+                    usable_stock: 0,
+                    expiring: 0,
+                };
+
+                // Cards that expire inside the window only count for the orders expected before they do.
+                // Lots sell soonest-to-expire first, so each covers what the earlier ones left over.
+                let mut lots: Vec<(u32, u32)> = self.inventory.iter()
+                    .filter(|item| item.card.retailer == retailer.id)
+                    .map(|item| (item.card.days_until_expiration(self.day), item.quantity))
+                    .collect();
+                lots.sort();
+                let mut covered = 0;
+                for (days_left, quantity) in lots {
+                    if days_left > days {
+                        forecast.usable_stock += quantity;
+                    } else {
+                        forecast.expiring += quantity;
+                        let sellable = forecast.expected_orders(days_left.saturating_sub(1) as usize).floor() as u32;
+                        let sold = quantity.min(sellable.saturating_sub(covered));
+                        covered += sold;
+                        forecast.usable_stock += sold;
+                    }
+                }
+                forecast
+            })
+            .collect()
+    }
+
+    fn retail_price(&self, retailer: &str, denomination: u32) -> u32 {
+        self.retail_prices.iter()
+            .find(|price| price.retailer == retailer && price.denomination == denomination)
+            .map_or(denomination, |price| price.price)
+    }
+
+    // Asking price between $1 and twice face value
+    fn set_retail_price(&mut self, retailer: &str, denomination: u32, price: u32) {
+        let price = price.clamp(1, denomination * 2);
+        match self.retail_prices.iter_mut().find(|existing| existing.retailer == retailer && existing.denomination == denomination) {
+            Some(existing) => existing.price = price,
+            None => self.retail_prices.push(RetailPrice { retailer: retailer.to_string(), denomination, price }),
+        }
+    }
+
+    // Hourly chance a walk-in shopper in town wants a card, wherever they end up buying it:
+    // popular cards and strong demand bring more of them
+    fn walk_in_demand_chance(&self, retailer: &str, denomination: u32) -> f32 {
+        let normal = catalog_product(retailer, denomination)
+            .map_or(WALK_IN_BASE_CHANCE * WALK_IN_SHOPPERS_PER_SALE, walk_in_shopper_chance);
+        (normal * self.demand_multiplier(retailer)).clamp(0.0, 1.0)
+    }
+
+    // Chance a shopper after one of these cards buys it from us: cheaper than its market value
+    // sells faster, and good reputation helps
+    fn walk_in_purchase_chance(&self, card: &GiftCard) -> f32 {
+        let price = self.retail_price(&card.retailer, card.denomination).max(1) as f32;
+        let price_factor = (card.market_value() as f32 / price)
+            .powf(WALK_IN_PRICE_ELASTICITY)
+            .min(WALK_IN_MAX_PRICE_FACTOR);
+        let reputation_factor = self.reputation as f32 / 3.0;
+        (price_factor * reputation_factor / WALK_IN_SHOPPERS_PER_SALE).clamp(0.0, 1.0)
+    }
+
+    // Hourly chance a walk-in buys one of these cards from us
+    fn walk_in_chance(&self, card: &GiftCard) -> f32 {
+        self.walk_in_demand_chance(&card.retailer, card.denomination) * self.walk_in_purchase_chance(card)
+    }
+
+    // One business hour of walk-in trade: shoppers come looking for cards (market demand),
+    // and each one may buy the card from us at our price if we stock it
+    fn process_walk_in_sales(&mut self) {
+        let mut cards_sold = 0;
+        let mut revenue = 0;
+        for product in CARD_CATALOG {
+            if !self.rng.chance(self.walk_in_demand_chance(product.retailer, product.denomination)) {
+                continue;
+            }
+            self.analytics.record_walk_in_demand(product.retailer);
+
+            let Some(item) = self.inventory.iter()
+                .find(|item| item.card.retailer == product.retailer && item.card.denomination == product.denomination)
+            else {
+                continue;
+            };
+            if !self.rng.chance(self.walk_in_purchase_chance(&item.card)) {
+                continue;
+            }
+            let taken = self.take_lots(product.retailer, Some(product.denomination), 1, self.cost_basis_policy);
+            if taken.count == 0 {
+                continue;
+            }
+            let price = self.retail_price(product.retailer, product.denomination);
+            self.cash += price;
+            self.analytics.record_card_sale(price, taken.cost, 1);
+            self.analytics.record_market_sale(product.retailer, 1);
+            self.analytics.walk_in_cards_sold += 1;
+            self.achievements.record_sale_profit(&self.market_conditions.current_season, price as i32 - taken.cost as i32);
+            if let Some(purchase_day) = taken.newest_purchase_day {
+                self.achievements.record_sale_turnaround(self.day - purchase_day, self.day, &mut self.recent_activities);
+            }
+            cards_sold += 1;
+            revenue += price;
+        }
+
+        if cards_sold > 0 {
+            self.achievements.check_cash_achievements(self.cash, self.day, &mut self.recent_activities);
+            self.recent_activities.insert(0, format!("🚶 Walk-in customers bought {} cards for ${}", cards_sold, revenue));
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+        }
+    }
+
+    // Bills that come due as the day rolls over to `self.day`
+    fn operating_expenses_due(&self) -> Vec<(ExpenseCategory, u32)> {
+        let multiplier = self.difficulty.expense_multiplier();
+        let new_week = self.day > 1 && (self.day - 1).is_multiple_of(7);
+        let mut due: Vec<(ExpenseCategory, u32)> = OPERATING_EXPENSES.iter()
+            .filter(|expense| match expense.schedule {
+                ExpenseSchedule::Daily => true,
+                ExpenseSchedule::Weekly => new_week,
+            })
+            .map(|expense| (expense.category, (expense.amount as f32 * multiplier).round() as u32))
+            .collect();
+
+        due.push((ExpenseCategory::StorageUpkeep, self.storage_tier().daily_upkeep));
+
+        // Processing fees on yesterday's sales
+        let yesterday_revenue = self.analytics.daily_revenues.last().copied().unwrap_or(0);
+        due.push((ExpenseCategory::PaymentFees, (yesterday_revenue as f32 * PAYMENT_FEE_RATE).round() as u32));
+
+        due.retain(|(_, amount)| *amount > 0);
+        due
+    }
+
+    fn charge_operating_expenses(&mut self) {
+        let due = self.operating_expenses_due();
+        let billed: u32 = due.iter().map(|(_, amount)| amount).sum();
+        let mut shortfall = 0;
+        for (category, amount) in due {
+            let paid = amount.min(self.cash);
+            self.cash -= paid;
+            self.analytics.record_expense(category, paid);
+            shortfall += amount - paid;
+        }
+
+        if shortfall > 0 {
+            self.analytics.unpaid_expenses += shortfall;
+            self.recent_activities.insert(0, format!(
+                "⚠️ Could only pay ${} of ${} in operating expenses", billed - shortfall, billed
+            ));
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+        }
+    }
+
+    fn can_afford(&self, cost: u32) -> bool {
+        self.cash >= cost
+    }
+
+    fn spend_money(&mut self, amount: u32) -> bool {
+        if self.can_afford(amount) {
+            self.cash -= amount;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Pay every pending achievement reward into cash; returns the amount paid
+    fn claim_achievement_rewards(&mut self) -> u32 {
+        let amount = self.achievements.claim_rewards(self.day);
+        if amount > 0 {
+            self.cash += amount;
+            self.analytics.record_achievement_reward(amount);
+            self.recent_activities.insert(0, format!("🏆 Claimed ${} in achievement rewards", amount));
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+        }
+        amount
+    }
+
+    // With auto-claim on, rewards are paid as soon as they show up in the ledger
+    fn settle_achievement_rewards(&mut self) {
+        if self.auto_claim_rewards && self.achievements.pending_rewards() > 0 {
+            self.claim_achievement_rewards();
+        }
+    }
+
+    // Current wholesale price for a listing after market conditions, events, scarcity,
+    // our relationship with the retailer and difficulty
+    fn wholesale_price(&self, listing: &MarketListing) -> u32 {
+        let multiplier = self.market_price_multiplier(listing) * (1.0 - self.market.relationship_discount(&listing.retailer));
+        (listing.base_cost as f32 * multiplier).round() as u32
+    }
+
+    // What every buyer pays on top of a listing's base cost, before any relationship discount.
+    // Rivals restock at this price too.
+    fn market_price_multiplier(&self, listing: &MarketListing) -> f32 {
+        self.market_conditions.get_price_multiplier_with_random_events(&listing.retailer, &self.random_events)
+            * listing.scarcity_multiplier()
+            * self.difficulty.wholesale_cost_multiplier()
+    }
+
+    // Per-card price when buying `quantity` units at once (volume tier applied)
+    fn bulk_unit_price(&self, listing: &MarketListing, quantity: u32) -> u32 {
+        let unit_price = self.wholesale_price(listing) as f32;
+        (unit_price * (1.0 - listing.volume_discount(quantity))).round().max(1.0) as u32
+    }
+
+    // Largest quantity of a listing we can pay for right now (limited by stock and storage space)
+    fn max_affordable_quantity(&self, listing: &MarketListing) -> u32 {
+        (1..=listing.stock.min(self.free_storage()))
+            .rev()
+            .find(|&quantity| self.bulk_unit_price(listing, quantity) * quantity <= self.cash)
+            .unwrap_or(0)
+    }
+
+    fn generate_random_order(&mut self) {
+        let customer_index = self.pick_ordering_customer();
+        let customer = &self.customers[customer_index];
+
+        // Customers mostly stick to the retailers they like
+        let favourites: Vec<&CardProduct> = CARD_CATALOG.iter()
+            .filter(|product| customer.preferred_retailers.iter().any(|preferred| preferred == product.retailer))
+            .collect();
+
+        let line_count = if self.rng.chance(ORDER_THREE_LINE_CHANCE) {
+            3
+        } else if self.rng.chance(ORDER_TWO_LINE_CHANCE) {
+            2
+        } else {
+            1
+        };
+        let mut requested_cards: Vec<CardRequest> = Vec::new();
+        for _ in 0..line_count {
+            // Popular denominations come up more often (demand weight)
+            let product = if !favourites.is_empty() && self.rng.chance(ORDER_FAVOURITE_CHANCE) {
+                pick_weighted(favourites.iter().map(|product| (*product, product.demand_weight)), &mut self.rng)
+            } else {
+                pick_weighted(CARD_CATALOG.iter().map(|product| (product, product.demand_weight)), &mut self.rng)
+            };
+            let Some(product) = product else {
+                continue;
+            };
+            if requested_cards.iter().any(|line| line.retailer == product.retailer) {
+                continue;
+            }
+            let quantity = self.rng.range(1, product.max_order_quantity);
+            let offered_price = self.customer_offer_price(&self.customers[customer_index], product.retailer, product.denomination);
+            requested_cards.push(CardRequest::new(product.retailer, product.denomination, quantity, offered_price));
+        }
+        let customer_name = self.customers[customer_index].name.clone();
+        let customer_id = self.customers[customer_index].id;
+        let partial_shipments = self.customers[customer_index].partial_shipments;
+        
+        let (min_deadline, max_deadline) = self.difficulty.order_deadline_range();
+        let deadline_days = self.rng.range(min_deadline, max_deadline);
+
+        let mut order = CustomerOrder::from_lines(self.next_order_id, &customer_name, requested_cards, deadline_days);
+        order.patience = self.rng.range(1, 3);
+        order.customer_id = customer_id;
+        order.allow_partial = partial_shipments;
+
+        self.customers[customer_index].record_order(&order, self.day);
+        self.next_order_id += 1;
+        for line in &order.requested_cards {
+            self.analytics.record_order_demand(&line.retailer, line.quantity);
+        }
+
+        // Rivals with the cards on hand may get to the customer first
+        if let Some(rival_index) = self.competitor_claim(&order, customer_index) {
+            let rival = &mut self.competitors[rival_index];
+            rival.fill_order(&order);
+            for line in &order.requested_cards {
+                self.analytics.record_rival_sale(&rival.name, &line.retailer, line.quantity);
+            }
+            self.recent_activities.insert(0, format!(
+                "🏪 {} beat you to {}'s order for {} cards", rival.name, customer_name, order.summary()
+            ));
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+            self.customers[customer_index].record_outcome(&order, OrderOutcome::Lost);
+            return;
+        }
+
+        // Add notification
+        self.recent_activities.insert(0, format!(
+            "📋 New order: {} wants {} cards",
+            customer_name, order.summary()
+        ));
+        if self.recent_activities.len() > 10 {
+            self.recent_activities.truncate(10);
+        }
+
+        self.customer_orders.push_back(order);
+    }
+
+    // Which rival, if any, wins a freshly placed order before it reaches us
+    fn competitor_claim(&mut self, order: &CustomerOrder, customer_index: usize) -> Option<usize> {
+        let customer = &self.customers[customer_index];
+        let chances: Vec<f32> = self.competitors.iter()
+            .map(|competitor| competitor.win_chance(order, customer, self.reputation))
+            .collect();
+        chances.into_iter()
+            .enumerate()
+            .find(|(_, chance)| *chance > 0.0 && self.rng.chance(*chance))
+            .map(|(index, _)| index)
+    }
+
+    fn price_war_active(&self) -> bool {
+        self.random_events.temp_modifiers.iter().any(|modifier| modifier.name == PRICE_WAR_MODIFIER)
+    }
+
+    // Daily turn for the rivals: pay overhead, answer or start price wars and restock from the
+    // same wholesale market we buy from (draining its stock and pushing up scarcity prices)
+    fn process_competitors(&mut self) {
+        let price_war = self.price_war_active();
+        let our_share = self.market_share();
+        let share_on_record = self.analytics.market_history.len() >= MARKET_SHARE_MIN_DAYS;
+        let our_ask = self.average_ask_ratio();
+        let shares: Vec<f32> = self.competitors.iter().map(|competitor| self.competitor_share(competitor)).collect();
+        let mut war_declared_by = None;
+
+        for (index, their_share) in shares.into_iter().enumerate() {
+            let competitor = &mut self.competitors[index];
+            competitor.cash = competitor.cash.saturating_sub(COMPETITOR_DAILY_OVERHEAD);
+            competitor.price_war_days = competitor.price_war_days.saturating_sub(1);
+
+            // Premium shops never cut prices; the rest fight back when we undercut their asks
+            // or take a big slice of the market from them
+            let undercut = our_ask.is_some_and(|ask| ask < competitor.strategy.price_ratio(false));
+            let outsold = share_on_record && our_share >= PRICE_WAR_SHARE_TRIGGER && our_share > their_share * 2.0;
+            let provoked = competitor.strategy != PricingStrategy::Premium && (undercut || outsold);
+            if provoked && war_declared_by.is_none() && self.rng.chance(PRICE_WAR_REACTION_CHANCE) {
+                competitor.price_war_days = PRICE_WAR_DAYS;
+                war_declared_by = Some(competitor.name.clone());
+            } else if price_war && competitor.strategy != PricingStrategy::Premium {
+                competitor.price_war_days = competitor.price_war_days.max(1);
+            }
+
+            let mut budget = (competitor.cash as f32 * COMPETITOR_DAILY_BUDGET) as u32;
+            for _ in 0..COMPETITOR_RESTOCK_PICKS {
+                if self.market.listings.is_empty() {
+                    break;
+                }
+                let listing_index = self.rng.range(0, self.market.listings.len() as u32 - 1) as usize;
+                let listing = &self.market.listings[listing_index];
+                let unit_price = (listing.base_cost as f32 * self.market_price_multiplier(listing)).round().max(1.0) as u32;
+                let (retailer, denomination) = (listing.retailer.clone(), listing.denomination);
+                let wanted = COMPETITOR_RESTOCK_TARGET
+                    .saturating_sub(self.competitors[index].stock_of(&retailer, denomination))
+                    .min(budget / unit_price);
+                let bought = self.market.take_competitor_stock(listing_index, wanted);
+                if bought > 0 {
+                    let competitor = &mut self.competitors[index];
+                    competitor.cash -= bought * unit_price;
+                    budget -= bought * unit_price;
+                    competitor.add_stock(&retailer, denomination, bought);
+                }
+            }
+        }
+
+        if let Some(name) = war_declared_by {
+            let running_war = self.random_events.temp_modifiers.iter_mut()
+                .find(|modifier| modifier.name == PRICE_WAR_MODIFIER);
+            if let Some(war) = running_war {
+                war.remaining_days = war.remaining_days.max(PRICE_WAR_DAYS);
+                self.recent_activities.insert(0, format!("⚔️ {} escalated the price war!", name));
+            } else {
+                // Customers shop around while prices are slashed
+                self.random_events.temp_modifiers.push(TempModifier {
+                    name: PRICE_WAR_MODIFIER.to_string(),
+                    description: format!("{} slashed prices", name),
+                    price_multiplier: 1.0,
+                    demand_multiplier: 0.85,
+                    reputation_protection: false,
+                    remaining_days: PRICE_WAR_DAYS,
+                });
+                self.recent_activities.insert(0, format!("⚔️ {} started a price war!", name));
+            }
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+        }
+    }
+
+    // Our asking price across the cards we stock, as a share of face value (None with nothing to sell)
+    fn average_ask_ratio(&self) -> Option<f32> {
+        let (asked, face_value) = self.inventory.iter().fold((0, 0), |(asked, face_value), item| {
+            (asked + self.retail_price(&item.card.retailer, item.card.denomination) * item.quantity,
+             face_value + item.card.denomination * item.quantity)
+        });
+        if face_value == 0 { None } else { Some(asked as f32 / face_value as f32) }
+    }
+
+    // Pick who places the next order: loyal customers order more often, churned ones not at all,
+    // and now and then someone new shows up
+    fn pick_ordering_customer(&mut self) -> usize {
+        if self.customers.is_empty() {
+            self.customers = Customer::initial_base(&mut self.rng);
+        }
+
+        let weights: Vec<u32> = self.customers.iter()
+            .map(|customer| if customer.has_churned() { 0 } else { customer.loyalty_tier().order_weight() })
+            .collect();
+        let total_weight: u32 = weights.iter().sum();
+
+        let new_customer = total_weight == 0
+            || (self.customers.len() < CUSTOMER_NAMES.len() && self.rng.chance(0.1));
+        if new_customer && self.customers.len() < CUSTOMER_NAMES.len() {
+            let id = self.customers.iter().map(|customer| customer.id).max().unwrap_or(0) + 1;
+            let name = CUSTOMER_NAMES[self.customers.len()];
+            let customer = Customer::new(id, name, &mut self.rng);
+            self.customers.push(customer);
+            return self.customers.len() - 1;
+        }
+        if total_weight == 0 {
+            return self.rng.range(0, self.customers.len() as u32 - 1) as usize;
+        }
+
+        let mut roll = self.rng.range(0, total_weight - 1);
+        for (index, weight) in weights.iter().enumerate() {
+            if roll < *weight {
+                return index;
+            }
+            roll -= weight;
+        }
+        self.customers.len() - 1
+    }
+
+    // Per-card price a customer offers: set by our reputation and demand, raised by loyalty,
+    // lowered for bargain hunters
+    fn customer_offer_price(&self, customer: &Customer, retailer: &str, denomination: u32) -> u32 {
+        // Customers want to buy at a discount from face value (that's the business model)
+        // Base offer is 85-95% of face value depending on reputation
+        let discount_percentage: f32 = match self.reputation {
+            5 => 0.95,  // 5% discount for 5-star (customers pay more for reliable service)
+            4 => 0.93,  // 7% discount for 4-star
+            3 => 0.90,  // 10% discount for 3-star
+            2 => 0.87,  // 13% discount for 2-star
+            1 => 0.85,  // 15% discount for 1-star (need deep discounts)
+            _ => 0.85,
+        };
+        
+        // Apply market demand multiplier
+        let demand_multiplier = self.market_conditions.get_demand_multiplier(retailer);
+        let demand_adjustment = if demand_multiplier > 1.2 {
+            0.02  // High demand = customers pay 2% more
+        } else if demand_multiplier < 0.8 {
+            -0.03  // Low demand = customers want 3% more discount
+        } else {
+            0.0  // Normal demand = no adjustment
+        };
+
+        let loyalty_premium = customer.loyalty_tier().price_premium();
+        let sensitivity_adjustment = (0.5 - customer.price_sensitivity) * 0.04; // +/-2%
+        
+        let final_discount = (discount_percentage + demand_adjustment + loyalty_premium + sensitivity_adjustment).clamp(0.80, 0.99);
+        (denomination as f32 * final_discount) as u32
+    }
+
+    fn customer_mut(&mut self, customer_id: u32) -> Option<&mut Customer> {
+        self.customers.iter_mut().find(|customer| customer.id == customer_id)
+    }
+
+    fn record_customer_outcome(&mut self, order: &CustomerOrder, outcome: OrderOutcome) {
+        if let Some(customer) = self.customer_mut(order.customer_id) {
+            customer.record_outcome(order, outcome);
+        }
+    }
+
+    fn process_order_aging(&mut self) {
+        // Age all orders by 1 day
+        for order in &mut self.customer_orders {
+            if order.deadline_days > 0 {
+                order.deadline_days -= 1;
+            }
+        }
+
+        // Remove expired orders and damage reputation
+        let (expired, active): (Vec<CustomerOrder>, Vec<CustomerOrder>) = self.customer_orders.drain(..)
+            .partition(|order| order.is_expired());
+        self.customer_orders = active.into();
+        let expired_count = expired.len() as u32;
+        let mut unserved_count = 0;
+        for order in &expired {
+            if order.backordered {
+                self.record_customer_outcome(order, OrderOutcome::PartiallyFulfilled);
+            } else {
+                self.record_customer_outcome(order, OrderOutcome::Expired);
+            }
+            // A backorder that missed after most of the order arrived disappoints rather than burns
+            if order.backordered && order.fill_rate() >= 0.5 {
+                self.strain_reputation("backorder_missed");
+            } else {
+                unserved_count += 1;
+            }
+        }
+
+        if expired_count > 0 {
+            // Record expired orders in analytics
+            for _ in 0..expired_count {
+                self.analytics.record_expired_order();
+                self.achievements.record_order_expiry();
+            }
+            
+            self.recent_activities.insert(0, format!(
+                "⏰ {} customer orders expired", expired_count
+            ));
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+            
+            // Damage reputation for expired orders (scaled by customer forgiveness)
+            for _ in 0..self.difficulty.expired_order_penalty(unserved_count) {
+                self.decrease_reputation("order_expired");
+            }
+        }
+
+        // Generate new orders based on reputation and market conditions
+        let demand_boost = self.random_events.get_total_demand_multiplier();
+        let base_order_chance = self.rng.chance(Self::daily_order_chance(self.reputation) * demand_boost);
+        
+        // Apply market demand modifier for additional orders
+        let market_boost = self.market_conditions.base_demand_modifier > 1.0;
+        let extra_market_chance = market_boost && self.rng.chance(0.5); // Additional orders about half the days during good markets
+        let order_chance = base_order_chance || extra_market_chance;
+        
+        if order_chance {
+            self.generate_random_order();
+        }
+    }
+
+    // Chance of a new customer order on a normal day. Higher reputation = more frequent orders
+    fn daily_order_chance(reputation: u8) -> f32 {
+        match reputation {
+            5 => 1.0,   // Every day (highest reputation)
+            4 => 0.5,   // Every other day on average
+            3 => 0.5,   // Every other day on average (default - more frequent now)
+            2 => 0.33,  // Every 3 days on average
+            1 => 0.25,  // Every 4 days on average
+            _ => 0.0,
+        }
+    }
+
+    // Cards of a retailer customers order on an average day at normal demand
+    fn expected_order_cards(&self, retailer: &str) -> f32 {
+        Self::daily_order_chance(self.reputation) * self.expected_cards_per_order(retailer)
+    }
+
+    // Cards of a retailer on an average order, worked out from the odds new orders are generated
+    // with: who places it, how many lines it has and which cards those lines ask for. A retailer
+    // appears on an order at most once.
+    fn expected_cards_per_order(&self, retailer: &str) -> f32 {
+        let products: Vec<&CardProduct> = CARD_CATALOG.iter().filter(|product| product.retailer == retailer).collect();
+        let retailer_weight: u32 = products.iter().map(|product| product.demand_weight).sum();
+        if retailer_weight == 0 {
+            return 0.0;
+        }
+        let cards_per_line = products.iter()
+            .map(|product| product.demand_weight as f32 * (1 + product.max_order_quantity) as f32 / 2.0)
+            .sum::<f32>() / retailer_weight as f32;
+
+        let mut on_order = 0.0;
+        let mut total_weight = 0;
+        for customer in self.customers.iter().filter(|customer| !customer.has_churned()) {
+            let line_chance = customer.order_line_chance(retailer);
+            let appears = |lines: i32| 1.0 - (1.0 - line_chance).powi(lines);
+            let weight = customer.loyalty_tier().order_weight();
+            on_order += weight as f32 * (ORDER_THREE_LINE_CHANCE * appears(3)
+                + (1.0 - ORDER_THREE_LINE_CHANCE) * (ORDER_TWO_LINE_CHANCE * appears(2) + (1.0 - ORDER_TWO_LINE_CHANCE) * appears(1)));
+            total_weight += weight;
+        }
+        on_order / total_weight.max(1) as f32 * cards_per_line
+    }
+
+    // Cards of a retailer the whole town wants today: the orders an average shop would see plus
+    // the walk-in shoppers out looking, scaled by the season, market events and modifiers
+    fn local_market_size(&self, retailer: &str) -> u32 {
+        let orders = Self::daily_order_chance(MARKET_SIZE_REPUTATION) * self.expected_cards_per_order(retailer);
+        let walk_ins: f32 = CARD_CATALOG.iter()
+            .filter(|product| product.retailer == retailer)
+            .map(|product| walk_in_shopper_chance(product) * BUSINESS_HOURS.len() as f32)
+            .sum();
+        ((orders + walk_ins) * self.demand_multiplier(retailer)).round() as u32
+    }
+
+    fn available_cards(&self, retailer: &str, denomination: u32) -> u32 {
+        self.inventory.iter()
+            .filter(|item| item.card.retailer == retailer && item.card.denomination == denomination)
+            .map(|item| item.quantity)
+            .sum()
+    }
+
+    fn can_fulfill_line(&self, line: &CardRequest) -> bool {
+        // Check if we have enough cards total across all inventory items
+        self.available_cards(&line.retailer, line.denomination) >= line.outstanding()
+    }
+
+    fn can_fulfill_order(&self, order: &CustomerOrder) -> bool {
+        order.requested_cards.iter().all(|line| self.can_fulfill_line(line))
+    }
+
+    // Some outstanding card could ship right now (only matters for partial-OK orders)
+    fn can_ship_any_line(&self, order: &CustomerOrder) -> bool {
+        order.requested_cards.iter()
+            .any(|line| line.outstanding() > 0 && self.available_cards(&line.retailer, line.denomination) > 0)
+    }
+
+    // Ship the order. Normally everything goes out together; if partial shipment is allowed,
+    // whatever is in stock goes out now and the remainder becomes a backorder with its own deadline.
+    fn fulfill_order(&mut self, order_index: usize) -> bool {
+        if order_index >= self.customer_orders.len() {
+            return false;
+        }
+
+        let mut order = self.customer_orders[order_index].clone();
+        let can_ship = self.can_fulfill_order(&order) || (order.allow_partial && self.can_ship_any_line(&order));
+        
+        if !can_ship {
+            // Add failure message
+            self.recent_activities.insert(0, format!(
+                "❌ Cannot fulfill order #{} - insufficient inventory", 
+                order.id
+            ));
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+            return false;
+        }
+
+        // Pull cards from cost lots under the selected policy, line by line
+        let mut total_earnings = 0;
+        let mut cost_basis = 0;
+        let mut cards_shipped = 0;
+        for line_index in 0..order.requested_cards.len() {
+            let line = &order.requested_cards[line_index];
+            let quantity = line.outstanding().min(self.available_cards(&line.retailer, line.denomination));
+            if quantity == 0 {
+                continue;
+            }
+            let (retailer, denomination) = (line.retailer.clone(), line.denomination);
+            let taken = self.take_lots(&retailer, Some(denomination), quantity, self.cost_basis_policy);
+            if let Some(purchase_day) = taken.newest_purchase_day {
+                self.achievements.record_sale_turnaround(self.day - purchase_day, self.day, &mut self.recent_activities);
+            }
+
+            self.analytics.record_market_sale(&retailer, quantity);
+
+            let line = &mut order.requested_cards[line_index];
+            total_earnings += line.price_per_card * quantity;
+            cost_basis += taken.cost;
+            cards_shipped += quantity;
+            line.shipped += quantity;
+        }
+
+        // Calculate earnings and profit from the real cost of goods sold
+        let profit = total_earnings as i32 - cost_basis as i32;
+        self.achievements.record_sale_profit(&self.market_conditions.current_season, profit);
+        
+        // Add money to cash
+        self.cash += total_earnings;
+
+        if !order.is_complete() {
+            // Partial shipment: the rest becomes a backorder on the customer's backorder clock
+            if !order.backordered {
+                order.backordered = true;
+                order.deadline_days = self.customers.iter()
+                    .find(|customer| customer.id == order.customer_id)
+                    .map(|customer| customer.backorder_days)
+                    .unwrap_or(DEFAULT_BACKORDER_DAYS);
+            }
+            self.analytics.record_card_sale(total_earnings, cost_basis, cards_shipped);
+            self.achievements.check_cash_achievements(self.cash, self.day, &mut self.recent_activities);
+            self.recent_activities.insert(0, format!(
+                "📦 Shipped {} cards on order #{} for ${} (profit: ${}), {} backordered (due in {} days)",
+                cards_shipped, order.id, total_earnings, profit, order.outstanding_cards(), order.deadline_days
+            ));
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+            self.customer_orders[order_index] = order;
+            return true;
+        }
+        
+        // Record sale in analytics
+        self.analytics.record_sale(total_earnings, cost_basis, cards_shipped);
+
+        // Check achievements
+        self.achievements.record_order_completion(self.day, &mut self.recent_activities);
+        self.achievements.check_order_achievements(self.analytics.orders_completed, self.reputation, self.day, &mut self.recent_activities);
+        self.achievements.check_cash_achievements(self.cash, self.day, &mut self.recent_activities);
+        
+        // Remove the completed order
+        self.customer_orders.remove(order_index);
+        self.record_customer_outcome(&order, OrderOutcome::Fulfilled);
+        
+        // Add success message
+        self.recent_activities.insert(0, format!(
+            "✅ Completed order #{}: {} cards for ${} (profit: ${})",
+            order.id, order.summary(), order.total_offered(), profit
+        ));
+        if self.recent_activities.len() > 10 {
+            self.recent_activities.truncate(10);
+        }
+
+        // Improve reputation for timely fulfillment
+        // Extra bonus for fast fulfillment (more than half deadline remaining)
+        if order.backordered {
+            self.improve_reputation("backorder_fulfilled");
+        } else if order.deadline_days > order.initial_deadline_days / 2 {
+            self.improve_reputation("fast_fulfillment");
+        } else {
+            self.improve_reputation("order_fulfilled");
+        }
+        
+        true
+    }
+
+    // Turn an order down instead of letting it expire
+    fn decline_order(&mut self, order_index: usize) -> bool {
+        let Some(order) = self.customer_orders.remove(order_index) else {
+            return false;
+        };
+        self.analytics.record_declined_order();
+        self.record_customer_outcome(&order, OrderOutcome::Declined);
+
+        self.recent_activities.insert(0, format!(
+            "🙅 Declined order #{} from {}", order.id, order.customer_name
+        ));
+        if self.recent_activities.len() > 10 {
+            self.recent_activities.truncate(10);
+        }
+
+        self.strain_reputation("order_declined");
+        true
+    }
+
+    // Minor letdowns (declines, missed backorders) only cost a star every few times
+    fn strain_reputation(&mut self, reason: &str) {
+        self.decline_strain += 1;
+        if self.decline_strain >= DECLINES_PER_REPUTATION_POINT {
+            self.decline_strain = 0;
+            self.decrease_reputation(reason);
+        }
+    }
+
+    // Combined demand for a retailer from market conditions and active random events
+    fn demand_multiplier(&self, retailer: &str) -> f32 {
+        self.market_conditions.get_demand_multiplier(retailer) * self.random_events.get_total_demand_multiplier()
+    }
+
+    // Average demand across the retailers an order asks for
+    fn order_demand_multiplier(&self, order: &CustomerOrder) -> f32 {
+        let lines = order.requested_cards.len().max(1) as f32;
+        order.requested_cards.iter()
+            .map(|line| self.demand_multiplier(&line.retailer))
+            .sum::<f32>() / lines
+    }
+
+    fn counter_offer_acceptance(&self, order: &CustomerOrder, asked_total: u32) -> f32 {
+        order.counter_offer_acceptance(asked_total, self.reputation, self.order_demand_multiplier(order))
+    }
+
+    // Ask the customer for a different total price; the order is revised or removed depending on their answer.
+    // Partly shipped orders can't be renegotiated.
+    fn counter_offer(&mut self, order_index: usize, asked_total: u32) -> Option<NegotiationOutcome> {
+        let order = self.customer_orders.get(order_index)?.clone();
+        if order.has_shipped() {
+            return None;
+        }
+        let demand = self.order_demand_multiplier(&order);
+        let outcome = order.respond_to_counter_offer(asked_total, self.reputation, demand, &mut self.rng);
+
+        let message = match &outcome {
+            NegotiationOutcome::Accepted(revised) => {
+                let message = format!(
+                    "🤝 {} accepted ${} for order #{}", revised.customer_name, revised.total_offered(), revised.id
+                );
+                self.customer_orders[order_index] = revised.clone();
+                message
+            }
+            NegotiationOutcome::Countered(revised) => {
+                let message = format!(
+                    "💬 {} countered at ${} for order #{}", revised.customer_name, revised.total_offered(), revised.id
+                );
+                self.customer_orders[order_index] = revised.clone();
+                message
+            }
+            NegotiationOutcome::WalkedAway => {
+                self.customer_orders.remove(order_index);
+                self.analytics.record_declined_order();
+                self.record_customer_outcome(&order, OrderOutcome::Declined);
+                format!("🚪 {} walked away from order #{}", order.customer_name, order.id)
+            }
+        };
+
+        self.recent_activities.insert(0, message);
+        if self.recent_activities.len() > 10 {
+            self.recent_activities.truncate(10);
+        }
+        Some(outcome)
+    }
+
+    fn improve_reputation(&mut self, reason: &str) {
+        if self.reputation < 5 {
+            self.reputation += 1;
+            let message = match reason {
+                "order_fulfilled" => "⭐ Reputation improved for excellent service!",
+                "fast_fulfillment" => "⭐ Reputation boosted for lightning-fast delivery!",
+                "backorder_fulfilled" => "⭐ Reputation improved - backorder delivered as promised!",
+                _ => "⭐ Reputation improved!",
+            };
+            self.recent_activities.insert(0, message.to_string());
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+        }
+    }
+
+    fn decrease_reputation(&mut self, reason: &str) {
+        if self.reputation > 1 {
+            self.reputation -= 1;
+            let message = match reason {
+                "order_expired" => "💔 Reputation damaged - customers disappointed by expired orders",
+                "order_declined" => "💔 Reputation slipped - too many customers turned away",
+                "backorder_missed" => "💔 Reputation slipped - backorders keep arriving late",
+                "slow_service" => "💔 Reputation declined due to slow service",
+                _ => "💔 Reputation decreased!",
+            };
+            self.recent_activities.insert(0, message.to_string());
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+        }
+    }
+
+    fn event_context(&self) -> EventContext {
+        let mut retailers_in_stock: Vec<String> = self.inventory.iter()
+            .map(|item| item.card.retailer.clone())
+            .collect();
+        retailers_in_stock.sort();
+        retailers_in_stock.dedup();
+
+        EventContext {
+            day: self.day,
+            season: self.market_conditions.current_season.clone(),
+            reputation: self.reputation,
+            cash: self.cash,
+            retailers_in_stock,
+        }
+    }
+
+    fn apply_event_outcome(&mut self, outcome: EventOutcome) {
+        let mut effects = Vec::new();
+
+        if outcome.cash > 0 {
+            self.cash = self.cash.saturating_add(outcome.cash as u32);
+            effects.push(format!("+${}", outcome.cash));
+        } else if outcome.cash < 0 {
+            let loss = outcome.cash.unsigned_abs().min(self.cash);
+            self.cash -= loss;
+            effects.push(format!("-${}", loss));
+        }
+
+        if outcome.reputation > 0 {
+            let new_reputation = (self.reputation + outcome.reputation as u8).min(5);
+            effects.push(format!("+{}★", new_reputation - self.reputation));
+            self.reputation = new_reputation;
+        } else if outcome.reputation < 0 {
+            if self.random_events.has_reputation_protection() {
+                effects.push("reputation protected".to_string());
+            } else {
+                let new_reputation = self.reputation.saturating_sub(outcome.reputation.unsigned_abs()).max(1);
+                effects.push(format!("-{}★", self.reputation - new_reputation));
+                self.reputation = new_reputation;
+            }
+        }
+
+        for (retailer_id, change) in &outcome.inventory {
+            if *change < 0 {
+                let removed = self.remove_cards(retailer_id, change.unsigned_abs());
+                effects.push(format!("-{} {} cards", removed, retailer_id));
+            } else if *change > 0 {
+                // Extra cards join an existing stack for that retailer, or start one with its best seller
+                if let Some(item) = self.inventory.iter_mut().find(|item| item.card.retailer == *retailer_id) {
+                    item.quantity += *change as u32;
+                    effects.push(format!("+{} {} cards", change, retailer_id));
+                } else if let Some(product) = retailer(retailer_id).and_then(Retailer::flagship_product) {
+                    let card = GiftCard::new(retailer_id, product.denomination, product.base_cost(), self.day + 60);
+                    self.inventory.push(InventoryItem::new(card, *change as u32));
+                    effects.push(format!("+{} {} cards", change, retailer_id));
+                }
+            }
+        }
+
+        for modifier in &outcome.modifiers {
+            effects.push(format!("{} for {} days", modifier.name, modifier.remaining_days));
+        }
+        self.random_events.temp_modifiers.extend(outcome.modifiers);
+
+        if !effects.is_empty() {
+            self.recent_activities.insert(0, format!("🎲 {}: {}", outcome.title, effects.join(", ")));
+            if self.recent_activities.len() > 10 {
+                self.recent_activities.truncate(10);
+            }
+        }
+
+        // A resolved random event is over, whichever way it went
+        self.achievements.record_event_survival(self.day, &mut self.recent_activities);
+    }
+
+    // Remove up to `quantity` cards of a retailer, soonest-expiring first. Returns how many were removed.
+    fn remove_cards(&mut self, retailer: &str, quantity: u32) -> u32 {
+        self.take_cards(retailer, None, quantity, CostBasisPolicy::SoonestExpiring).0
+    }
+
+    // Consume cards from matching cost lots in the order given by `policy`.
+    // Returns (cards taken, cost of those cards).
+    fn take_cards(&mut self, retailer: &str, denomination: Option<u32>, quantity: u32, policy: CostBasisPolicy) -> (u32, u32) {
+        let taken = self.take_lots(retailer, denomination, quantity, policy);
+        (taken.count, taken.cost)
+    }
+
+    fn take_lots(&mut self, retailer: &str, denomination: Option<u32>, quantity: u32, policy: CostBasisPolicy) -> TakenLots {
+        let mut lots: Vec<usize> = (0..self.inventory.len())
+            .filter(|&i| self.inventory[i].card.retailer == retailer
+                && denomination.is_none_or(|d| self.inventory[i].card.denomination == d))
+            .collect();
+        match policy {
+            CostBasisPolicy::Fifo | CostBasisPolicy::WeightedAverage => {}, // Inventory is kept in purchase order
+            CostBasisPolicy::Lifo => lots.reverse(),
+            CostBasisPolicy::SoonestExpiring => lots.sort_by_key(|&i| self.inventory[i].card.expiration_day),
+        }
+
+        let lot_units: u32 = lots.iter().map(|&i| self.inventory[i].quantity).sum();
+        let lot_cost: u32 = lots.iter().map(|&i| self.inventory[i].total_cost()).sum();
+
+        let mut remaining = quantity;
+        let mut cost = 0;
+        let mut newest_purchase_day = None;
+        for i in lots {
+            if remaining == 0 {
+                break;
+            }
+            let taken = remaining.min(self.inventory[i].quantity);
+            self.inventory[i].quantity -= taken;
+            cost += taken * self.inventory[i].card.purchase_price;
+            newest_purchase_day = newest_purchase_day.max(self.inventory[i].card.purchase_day);
+            remaining -= taken;
+        }
+        self.inventory.retain(|item| item.quantity > 0);
+
+        let taken = quantity - remaining;
+        if policy == CostBasisPolicy::WeightedAverage && lot_units > 0 {
+            cost = (lot_cost as f32 / lot_units as f32 * taken as f32).round() as u32;
+        }
+        TakenLots { count: taken, cost, newest_purchase_day }
+    }
+
+    fn save_game(&self, filename: &str) -> Result<(), Box<dyn Error>> {
+        let save_data = serde_json::to_string_pretty(self)?;
+        fs::write(filename, save_data)?;
+        Ok(())
+    }
+
+    fn load_game(filename: &str) -> Result<Self, Box<dyn Error>> {
+        let save_data = fs::read_to_string(filename)?;
+        let mut save: serde_json::Value = serde_json::from_str(&save_data)?;
+        Self::migrate_card_expiration(&mut save);
+        Self::migrate_relationship_volume(&mut save);
+        let game_data: GameData = serde_json::from_value(save)?;
+        Ok(game_data)
+    }
+
+    // Older saves stored a days-left countdown per card; turn it into an absolute expiration day
+    fn migrate_card_expiration(save: &mut serde_json::Value) {
+        let day = save["day"].as_u64().unwrap_or(1);
+        let Some(inventory) = save["inventory"].as_array_mut() else {
+            return;
+        };
+        for item in inventory {
+            let card = &mut item["card"];
+            if card.get("expiration_day").is_none()
+                && let Some(days_left) = card.get("days_until_expiration").and_then(|days| days.as_u64()) {
+                card["expiration_day"] = (day + days_left).into();
+            }
+        }
+    }
+
+    // Older saves tracked relationship volume per listing; pool it by retailer
+    fn migrate_relationship_volume(save: &mut serde_json::Value) {
+        let Some(market) = save.get_mut("market") else {
+            return;
+        };
+        if market.get("lifetime_units").is_some() {
+            return;
+        }
+        let mut lifetime_units = serde_json::Map::new();
+        for listing in market["listings"].as_array().into_iter().flatten() {
+            if let (Some(retailer), Some(units)) = (listing["retailer"].as_str(), listing["lifetime_units"].as_u64()) {
+                let total = lifetime_units.get(retailer).and_then(|total| total.as_u64()).unwrap_or(0) + units;
+                lifetime_units.insert(retailer.to_string(), total.into());
+            }
+        }
+        market["lifetime_units"] = lifetime_units.into();
+    }
+
+    fn save_file_exists(filename: &str) -> bool {
+        std::path::Path::new(filename).exists()
+    }
+}
+
+// Cards pulled out of inventory by GameData::take_lots
+struct TakenLots {
+    count: u32,
+    cost: u32,
+    newest_purchase_day: Option<u32>, // Most recently bought card among them, if known
+}
+
+// One line of the Market shopping cart
+#[derive(Debug, Clone, PartialEq)]
+struct CartLine {
+    listing_index: usize,
+    quantity: u32,
+}
+
+// Priced cart line as it would be charged right now
+#[derive(Debug, Clone)]
+struct CartQuote {
+    listing_index: usize,
+    retailer: String,
+    denomination: u32,
+    quantity: u32,
+    unit_price: u32,  // After market multipliers, scarcity and volume discount
+    volume_discount: f32,
+    in_stock: u32,
+}
+
+impl CartQuote {
+    fn line_total(&self) -> u32 {
+        self.unit_price * self.quantity
+    }
+}
+
+#[derive(Debug)]
+struct App {
+    screen: Screen,
+    selected_menu_item: usize,
+    should_quit: bool,
+    game_data: GameData,
+    last_time_update: Instant,
+    game_speed: Duration, // How often to advance time
+    paused: bool,
+    sound_effects: SoundEffects,
+    seed: Option<u64>, // From --seed; reused for every new game
+    event_catalog: EventCatalog,
+    purchase_quantity: u32,  // Units bought per purchase on the Market screen
+    quantity_entry: String,  // Digits typed for the purchase quantity
+    cart: Vec<CartLine>,     // Pending Market purchases, committed together at checkout
+    counter_offer: Option<CounterOffer>, // Price being negotiated on the Orders screen
+    customer_detail: Option<(u32, usize)>, // Customer being viewed and the Orders row to return to
+    inventory_sort_by_expiry: bool,        // Inventory screen lists soonest-expiring stacks first
+    inventory_filter: InventoryFilter,
+}
+
+// Which stacks the Inventory screen shows, by time left before expiration
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum InventoryFilter {
+    #[default]
+    All,
+    ExpiringSoon, // 15 days or less
+    Critical,     // 7 days or less
+}
+
+impl InventoryFilter {
+    fn display(&self) -> &str {
+        match self {
+            InventoryFilter::All => "All",
+            InventoryFilter::ExpiringSoon => "Expiring ≤15d",
+            InventoryFilter::Critical => "Critical ≤7d",
+        }
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            InventoryFilter::All => InventoryFilter::ExpiringSoon,
+            InventoryFilter::ExpiringSoon => InventoryFilter::Critical,
+            InventoryFilter::Critical => InventoryFilter::All,
+        }
+    }
+
+    fn matches(&self, days_left: u32) -> bool {
+        match self {
+            InventoryFilter::All => true,
+            InventoryFilter::ExpiringSoon => days_left <= 15,
+            InventoryFilter::Critical => days_left <= 7,
+        }
+    }
+}
+
+// Counter-offer being drafted for an order
+#[derive(Debug, Clone, PartialEq)]
+struct CounterOffer {
+    order_id: u32,
+    total_price: u32, // Asked for the whole order
+    step: u32,        // $1 per card in the order
+}
+
+impl App {
+    fn new(seed: Option<u64>) -> App {
+        let mut game_data = match seed {
+            Some(seed) => GameData::with_seed(seed),
+            None => GameData::new(),
+        };
+
+        let (event_catalog, catalog_warning) = EventCatalog::load_or_builtin(EVENTS_FILE);
+        game_data.event_catalog = event_catalog.clone();
+        if let Some(warning) = catalog_warning {
+            game_data.recent_activities.insert(0, warning);
+        }
+
+        App {
+            screen: Screen::MainMenu,
+            selected_menu_item: 0,
+            should_quit: false,
+            game_data,
+            last_time_update: Instant::now(),
+            game_speed: Duration::from_secs(1), // Advance 20 minutes every 1 second
+            paused: false,
+            sound_effects: SoundEffects::new(),
+            seed,
+            event_catalog,
+            purchase_quantity: 1,
+            quantity_entry: String::new(),
+            cart: Vec::new(),
+            counter_offer: None,
+            customer_detail: None,
+            inventory_sort_by_expiry: false,
+            inventory_filter: InventoryFilter::default(),
+        }
+    }
+
+    // Inventory indices in the order the Inventory screen lists them (after sort and filter)
+    fn inventory_view(&self) -> Vec<usize> {
+        let today = self.game_data.day;
+        let inventory = &self.game_data.inventory;
+        let mut view: Vec<usize> = (0..inventory.len())
+            .filter(|&i| self.inventory_filter.matches(inventory[i].card.days_until_expiration(today)))
+            .collect();
+        if self.inventory_sort_by_expiry {
+            view.sort_by_key(|&i| inventory[i].card.expiration_day);
+        }
+        view
+    }
+
+    fn toggle_inventory_expiry_sort(&mut self) {
+        self.inventory_sort_by_expiry = !self.inventory_sort_by_expiry;
+        self.selected_menu_item = 0;
+    }
+
+    fn cycle_inventory_filter(&mut self) {
+        self.inventory_filter = self.inventory_filter.next();
+        self.selected_menu_item = 0;
+    }
+
+    fn update_time(&mut self) {
+        if self.paused || self.game_data.game_over.is_some()
+            || matches!(self.screen, Screen::MainMenu | Screen::NewGameSetup) {
+            return;
+        }
+
+        let now = Instant::now();
+        if now.duration_since(self.last_time_update) >= self.game_speed {
+            self.game_data.advance_time(20); // Advance 20 minutes
+            self.last_time_update = now;
+        }
+    }
+    
+    fn check_for_active_events(&mut self) {
+        // A finished run shows its summary instead of anything else in the game
+        if self.game_data.game_over.is_some() {
+            if !matches!(self.screen, Screen::GameOver | Screen::MainMenu | Screen::NewGameSetup) {
+                let won = self.game_data.game_over.as_ref().is_some_and(|game_over| game_over.outcome.is_victory());
+                self.sound_effects.play(if won { SoundType::LevelUp } else { SoundType::Warning });
+                self.screen = Screen::GameOver;
+                self.selected_menu_item = 0;
+            }
+            return;
+        }
+
+        // Check if we need to switch to random event screen for player choice
+        if self.game_data.random_events.player_choice_pending && 
+           !matches!(self.screen, Screen::RandomEvent) {
+            self.sound_effects.play(SoundType::RandomEvent);
+            self.screen = Screen::RandomEvent;
+            self.selected_menu_item = 0; // Reset selection
+        } else if !self.game_data.random_events.player_choice_pending && 
+                  matches!(self.screen, Screen::RandomEvent) {
+            // Return to dashboard if event is resolved
+            self.screen = Screen::Dashboard;
+            self.selected_menu_item = 0;
+        }
+    }
+    
+    fn check_for_audio_events(&mut self) {
+        // Check for recent achievement unlocks
+        if let Some(_achievement_name) = self.game_data.achievements.get_recent_unlock() {
+            self.sound_effects.play(SoundType::LevelUp);
+        }
+        
+        // Check for new orders (simple detection by counting recent activities with order keywords)
+        if let Some(recent_activity) = self.game_data.recent_activities.first()
+            && (recent_activity.contains("New customer order") || recent_activity.contains("📝 Order from")) {
+            self.sound_effects.play(SoundType::NewOrder);
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        if !matches!(self.screen, Screen::MainMenu | Screen::NewGameSetup) {
+            self.paused = !self.paused;
+            self.sound_effects.play(SoundType::Paused);
+            let status = if self.paused { "⏸️ Paused" } else { "▶️ Resumed" };
+            self.game_data.recent_activities.insert(0, status.to_string());
+            if self.game_data.recent_activities.len() > 10 {
+                self.game_data.recent_activities.truncate(10);
+            }
+        }
+    }
+
+    fn purchase_from_market(&mut self) {
+        if !matches!(self.screen, Screen::Market) {
+            return;
+        }
+
+        self.quantity_entry.clear();
+        let Some(listing) = self.game_data.market.listings.get(self.selected_menu_item) else {
+            return;
+        };
+        let retailer = retailer_name(&listing.retailer).to_string();
+        let denomination = listing.denomination;
+        let quantity = self.purchase_quantity.max(1);
+        let unit_cost = self.game_data.bulk_unit_price(listing, quantity);
+        let total_cost = unit_cost * quantity;
+        let volume_discount = listing.volume_discount(quantity);
+
+        if listing.stock < quantity {
+            // Not enough supply until the supplier restocks
+            self.sound_effects.play(SoundType::Error);
+            let eta = listing.days_until_full(&self.game_data.market_conditions).unwrap_or(1);
+            let activity = format!(
+                "❌ Only {} {} ${} in stock (restocking, full in {} days)",
+                listing.stock, retailer, denomination, eta
+            );
+            self.game_data.recent_activities.insert(0, activity);
+            if self.game_data.recent_activities.len() > 10 {
+                self.game_data.recent_activities.truncate(10);
+            }
+            return;
+        }
+
+        let free_storage = self.game_data.free_storage();
+        if quantity > free_storage {
+            // Nowhere to put them
+            self.sound_effects.play(SoundType::Error);
+            let activity = format!(
+                "❌ No room for {}x {} ${}: storage {} (space for {})",
+                quantity, retailer, denomination, self.game_data.storage_display(), free_storage
+            );
+            self.game_data.recent_activities.insert(0, activity);
+            if self.game_data.recent_activities.len() > 10 {
+                self.game_data.recent_activities.truncate(10);
+            }
+            return;
+        }
+
+        if self.game_data.can_afford(total_cost) {
+            if self.game_data.spend_money(total_cost) {
+                self.receive_market_purchase(self.selected_menu_item, quantity, unit_cost);
+
+                // Play purchase success sound
+                self.sound_effects.play(SoundType::Purchase);
+                
+                // Add activity log
+                let discount_note = if volume_discount > 0.0 {
+                    format!(" ({:.0}% bulk discount)", volume_discount * 100.0)
+                } else {
+                    String::new()
+                };
+                let activity = format!(
+                    "💰 Purchased {}x {} ${} cards for ${}{}", 
+                    quantity, retailer, denomination, total_cost, discount_note
+                );
+                self.game_data.recent_activities.insert(0, activity);
+                if self.game_data.recent_activities.len() > 10 {
+                    self.game_data.recent_activities.truncate(10);
+                }
+            }
+        } else {
+            // Not enough money
+            self.sound_effects.play(SoundType::Error);
+            let activity = format!(
+                "❌ Insufficient funds for {}x {} ${} (need ${})", 
+                quantity, retailer, denomination, total_cost
+            );
+            self.game_data.recent_activities.insert(0, activity);
+            if self.game_data.recent_activities.len() > 10 {
+                self.game_data.recent_activities.truncate(10);
+            }
+        }
+    }
+
+    // Deliver already-paid cards from a listing: take stock, stock inventory, record analytics
+    fn receive_market_purchase(&mut self, listing_index: usize, quantity: u32, unit_cost: u32) {
+        let Some(listing) = self.game_data.market.listings.get(listing_index) else {
+            return;
+        };
+        let retailer = listing.retailer.clone();
+        let denomination = listing.denomination;
+        self.game_data.market.take_stock(listing_index, quantity);
+
+        // Create the gift cards with random expiration (range depends on difficulty)
+        let (min_expiration, max_expiration) = self.game_data.difficulty.expiration_range();
+        let expiration_days = self.game_data.rng.range(min_expiration, max_expiration);
+        let day = self.game_data.day;
+        let card = GiftCard::new(&retailer, denomination, unit_cost, day + expiration_days).purchased_on(day);
+
+        self.game_data.add_to_inventory(card, quantity);
+
+        // Record purchase in analytics
+        self.game_data.analytics.record_purchase(unit_cost * quantity);
+
+        // Check market purchase achievements
+        let price_multiplier = self.game_data.market_conditions.get_price_multiplier_with_random_events(&retailer, &self.game_data.random_events);
+        self.game_data.achievements.record_market_purchase(price_multiplier, self.game_data.day, &mut self.game_data.recent_activities);
+    }
+
+    fn add_to_cart(&mut self) {
+        self.quantity_entry.clear();
+        let listing_index = self.selected_menu_item;
+        let Some(listing) = self.game_data.market.listings.get(listing_index) else {
+            return;
+        };
+        let activity = format!("🛒 Added {}x {} ${} to cart", self.purchase_quantity, listing.retailer, listing.denomination);
+
+        // Repeated adds of the same listing grow one line so the volume tier applies to the total
+        match self.cart.iter_mut().find(|line| line.listing_index == listing_index) {
+            Some(line) => line.quantity += self.purchase_quantity,
+            None => self.cart.push(CartLine { listing_index, quantity: self.purchase_quantity }),
+        }
+
+        self.game_data.recent_activities.insert(0, activity);
+        if self.game_data.recent_activities.len() > 10 {
+            self.game_data.recent_activities.truncate(10);
+        }
+    }
+
+    fn remove_from_cart(&mut self) {
+        let listing_index = self.selected_menu_item;
+        self.cart.retain(|line| line.listing_index != listing_index);
+    }
+
+    // Price every cart line at current market conditions
+    fn cart_quote(&self) -> Vec<CartQuote> {
+        self.cart.iter()
+            .filter_map(|line| {
+                let listing = self.game_data.market.listings.get(line.listing_index)?;
+                Some(CartQuote {
+                    listing_index: line.listing_index,
+                    retailer: listing.retailer.clone(),
+                    denomination: listing.denomination,
+                    quantity: line.quantity,
+                    unit_price: self.game_data.bulk_unit_price(listing, line.quantity),
+                    volume_discount: listing.volume_discount(line.quantity),
+                    in_stock: listing.stock,
+                })
+            })
+            .collect()
+    }
+
+    fn cart_total(&self) -> u32 {
+        self.cart_quote().iter().map(CartQuote::line_total).sum()
+    }
+
+    // Buy every cart line in one transaction: either all lines are paid and delivered, or nothing changes
+    fn checkout_cart(&mut self) {
+        if self.cart.is_empty() {
+            return;
+        }
+
+        let quotes = self.cart_quote();
+        let total = quotes.iter().map(CartQuote::line_total).sum::<u32>();
+        let short_stock: Vec<String> = quotes.iter()
+            .filter(|quote| quote.in_stock < quote.quantity)
+            .map(|quote| format!("{} ${} ({}/{})", quote.retailer, quote.denomination, quote.in_stock, quote.quantity))
+            .collect();
+
+        let cards: u32 = quotes.iter().map(|quote| quote.quantity).sum();
+        let free_storage = self.game_data.free_storage();
+
+        let error = if !short_stock.is_empty() {
+            Some(format!("❌ Checkout failed: not enough stock for {}", short_stock.join(", ")))
+        } else if cards > free_storage {
+            Some(format!(
+                "❌ Checkout failed: {} cards won't fit, storage {} (space for {})",
+                cards, self.game_data.storage_display(), free_storage
+            ))
+        } else if !self.game_data.spend_money(total) {
+            Some(format!(
+                "❌ Checkout failed: cart total ${} exceeds cash ${} (short ${})",
+                total, self.game_data.cash, total - self.game_data.cash
+            ))
+        } else {
+            None
+        };
+
+        if let Some(error) = error {
+            self.sound_effects.play(SoundType::Error);
+            self.game_data.recent_activities.insert(0, error);
+            if self.game_data.recent_activities.len() > 10 {
+                self.game_data.recent_activities.truncate(10);
+            }
+            return;
+        }
+
+        for quote in &quotes {
+            self.receive_market_purchase(quote.listing_index, quote.quantity, quote.unit_price);
+        }
+        self.cart.clear();
+        self.sound_effects.play(SoundType::Purchase);
+
+        let activity = format!("🛒 Checked out {} lines ({} cards) for ${}", quotes.len(), cards, total);
+        self.game_data.recent_activities.insert(0, activity);
+        if self.game_data.recent_activities.len() > 10 {
+            self.game_data.recent_activities.truncate(10);
+        }
+    }
+
+    fn adjust_purchase_quantity(&mut self, delta: i32) {
+        self.quantity_entry.clear();
+        self.purchase_quantity = (self.purchase_quantity as i32 + delta).clamp(1, 999) as u32;
+    }
+
+    fn type_purchase_quantity_digit(&mut self, digit: char) {
+        if self.quantity_entry.len() >= 3 {
+            return;
+        }
+        self.quantity_entry.push(digit);
+        self.purchase_quantity = self.quantity_entry.parse::<u32>().unwrap_or(1).max(1);
+    }
+
+    fn erase_purchase_quantity_digit(&mut self) {
+        self.quantity_entry.pop();
+        self.purchase_quantity = self.quantity_entry.parse::<u32>().unwrap_or(1).max(1);
+    }
+
+    fn set_max_affordable_quantity(&mut self) {
+        self.quantity_entry.clear();
+        if let Some(listing) = self.game_data.market.listings.get(self.selected_menu_item) {
+            self.purchase_quantity = self.game_data.max_affordable_quantity(listing).max(1);
+        }
+    }
+
+    fn view_order_customer(&mut self) {
+        if !matches!(self.screen, Screen::Orders) || self.game_data.customer_orders.is_empty() {
+            return;
+        }
+
+        let order_index = self.selected_menu_item.min(self.game_data.customer_orders.len() - 1);
+        let customer_id = self.game_data.customer_orders[order_index].customer_id;
+        if self.game_data.customers.iter().any(|customer| customer.id == customer_id) {
+            self.counter_offer = None;
+            self.customer_detail = Some((customer_id, order_index));
+            self.screen = Screen::CustomerDetail;
+        }
+    }
+
+    // Per-order switch between all-or-nothing and ship-now-backorder-the-rest
+    fn toggle_order_partial_shipment(&mut self) {
+        if !matches!(self.screen, Screen::Orders) || self.game_data.customer_orders.is_empty() {
+            return;
+        }
+
+        let order_index = self.selected_menu_item.min(self.game_data.customer_orders.len() - 1);
+        let order = &mut self.game_data.customer_orders[order_index];
+        if order.backordered {
+            return; // Already split; the remainder can only ship as a backorder
+        }
+        order.allow_partial = !order.allow_partial;
+    }
+
+    // Default for this customer's future orders, set from their profile
+    fn toggle_customer_partial_shipments(&mut self) {
+        let Some((customer_id, _)) = self.customer_detail else {
+            return;
+        };
+        if let Some(customer) = self.game_data.customer_mut(customer_id) {
+            customer.partial_shipments = !customer.partial_shipments;
+        }
+    }
+
+    fn decline_customer_order(&mut self) {
+        if !matches!(self.screen, Screen::Orders) || self.game_data.customer_orders.is_empty() {
+            return;
+        }
+
+        let order_index = self.selected_menu_item.min(self.game_data.customer_orders.len() - 1);
+        self.counter_offer = None;
+        self.game_data.decline_order(order_index);
+        self.selected_menu_item = self.selected_menu_item.min(self.game_data.customer_orders.len().saturating_sub(1));
+    }
+
+    // Start drafting a counter-offer for the selected order, opening a bit above the customer's price
+    fn start_counter_offer(&mut self) {
+        if !matches!(self.screen, Screen::Orders) || self.game_data.customer_orders.is_empty() {
+            return;
+        }
+
+        let order_index = self.selected_menu_item.min(self.game_data.customer_orders.len() - 1);
+        let order = &self.game_data.customer_orders[order_index];
+        if order.has_shipped() {
+            let message = format!("🔒 Order #{} has partly shipped; its price can't be renegotiated", order.id);
+            self.sound_effects.play(SoundType::Error);
+            self.game_data.recent_activities.insert(0, message);
+            if self.game_data.recent_activities.len() > 10 {
+                self.game_data.recent_activities.truncate(10);
+            }
+            return;
+        }
+        let step = order.card_count().max(1);
+        self.counter_offer = Some(CounterOffer {
+            order_id: order.id,
+            total_price: order.total_offered() + step,
+            step,
+        });
+    }
+
+    fn adjust_counter_offer(&mut self, delta: i32) {
+        if let Some(counter) = &mut self.counter_offer {
+            counter.total_price = (counter.total_price as i32 + delta * counter.step as i32).max(1) as u32;
+        }
+    }
+
+    fn submit_counter_offer(&mut self) {
+        let Some(counter) = self.counter_offer.take() else {
+            return;
+        };
+        let Some(order_index) = self.game_data.customer_orders.iter().position(|order| order.id == counter.order_id) else {
+            return; // Order expired or was handled while we were haggling
+        };
+
+        match self.game_data.counter_offer(order_index, counter.total_price) {
+            Some(NegotiationOutcome::Accepted(_)) => self.sound_effects.play(SoundType::Sale),
+            Some(NegotiationOutcome::Countered(_)) => self.sound_effects.play(SoundType::NewOrder),
+            Some(NegotiationOutcome::WalkedAway) => {
+                self.sound_effects.play(SoundType::Error);
+                self.selected_menu_item = self.selected_menu_item.min(self.game_data.customer_orders.len().saturating_sub(1));
+            }
+            None => {}
+        }
+    }
+
+    fn fulfill_customer_order(&mut self) {
+        if !matches!(self.screen, Screen::Orders) {
+            return;
+        }
+
+        if self.game_data.customer_orders.is_empty() {
+            return;
+        }
+
+        // Ensure selected item is within bounds
+        let order_index = self.selected_menu_item.min(self.game_data.customer_orders.len() - 1);
+        
+        // Attempt to fulfill the order
+        let success = self.game_data.fulfill_order(order_index);
+        
+        // Play appropriate sound
+        if success {
+            self.sound_effects.play(SoundType::Sale);
+        } else {
+            self.sound_effects.play(SoundType::Error);
+        }
+        
+        // Adjust selection if we're now beyond the list
+        if self.selected_menu_item >= self.game_data.customer_orders.len() && !self.game_data.customer_orders.is_empty() {
+            self.selected_menu_item = self.game_data.customer_orders.len() - 1;
+        } else if self.game_data.customer_orders.is_empty() {
+            self.selected_menu_item = 0;
+        }
+    }
+    
+    fn handle_random_event_choice(&mut self) {
+        // Make choice on active random event
+        if let Some(outcome) = self.game_data.random_events.make_choice(self.selected_menu_item) {
+            // Log the choice, then apply impacts immediately
+            let activity = "✅ Made choice on random event".to_string();
+            self.game_data.recent_activities.insert(0, activity);
+            self.game_data.apply_event_outcome(outcome);
+            if self.game_data.recent_activities.len() > 10 {
+                self.game_data.recent_activities.truncate(10);
+            }
+            
+            // Event will be automatically cleared and screen switched in check_for_active_events
+        }
+    }
+
+    fn sell_inventory_item(&mut self) {
+        if !matches!(self.screen, Screen::Inventory) {
+            return;
+        }
+
+        // Rows follow the screen's sort and filter
+        let view = self.inventory_view();
+        if view.is_empty() {
+            return;
+        }
+
+        // Ensure selected item is within bounds
+        let inventory_index = view[self.selected_menu_item.min(view.len() - 1)];
+        
+        // Get the selected inventory item
+        let item = &self.game_data.inventory[inventory_index];
+        
+        // Calculate market value (sell at slightly below retail value)
+        let retail_value = item.card.denomination;
+        let market_value = (retail_value as f32 * 0.85) as u32; // Sell at 85% of face value
+        let total_value = market_value * item.quantity;
+        
+        // Calculate profit/loss
+        let total_cost = item.card.purchase_price * item.quantity;
+        let profit = total_value as i32 - total_cost as i32;
+        
+        // Add cash to player
+        self.game_data.cash += total_value;
+        
+        // Record the sale in analytics (this stack is the cost lot being sold)
+        // Dumping stock back to the trade isn't a sale to local customers, so it doesn't count toward market share
+        self.game_data.analytics.record_card_sale(total_value, total_cost, item.quantity);
+        self.game_data.achievements.record_sale_profit(&self.game_data.market_conditions.current_season, profit);
+        if let Some(purchase_day) = item.card.purchase_day {
+            let day = self.game_data.day;
+            self.game_data.achievements.record_sale_turnaround(day - purchase_day, day, &mut self.game_data.recent_activities);
+        }
+        
+        // Play success sound
+        self.sound_effects.play(SoundType::Sale);
+        
+        // Add activity log
+        let activity = format!(
+            "💰 Sold {}x {} ${} cards for ${} ({}${} profit)",
+            item.quantity,
+            item.card.retailer,
+            item.card.denomination,
+            total_value,
+            if profit >= 0 { "+" } else { "" },
+            profit
+        );
+        self.game_data.recent_activities.insert(0, activity);
+        if self.game_data.recent_activities.len() > 10 {
+            self.game_data.recent_activities.truncate(10);
+        }
+        
+        // Remove the sold item from inventory
+        self.game_data.inventory.remove(inventory_index);
+        
+        // Adjust selection if we're now beyond the list
+        let rows = view.len() - 1;
+        if self.selected_menu_item >= rows && rows > 0 {
+            self.selected_menu_item = rows - 1;
+        } else if rows == 0 {
+            self.selected_menu_item = 0;
+        }
+    }
+
+    // Raise or lower the walk-in asking price of the selected stack's card type
+    fn adjust_retail_price(&mut self, delta: i32) {
+        let view = self.inventory_view();
+        if view.is_empty() {
+            return;
+        }
+        let card = &self.game_data.inventory[view[self.selected_menu_item.min(view.len() - 1)]].card;
+        let (retailer, denomination) = (card.retailer.clone(), card.denomination);
+        let price = self.game_data.retail_price(&retailer, denomination).saturating_add_signed(delta);
+        self.game_data.set_retail_price(&retailer, denomination, price);
+    }
+
+    fn upgrade_storage(&mut self) {
+        if self.game_data.upgrade_storage() {
+            self.sound_effects.play(SoundType::Purchase);
+        } else {
+            self.sound_effects.play(SoundType::Error);
+        }
+    }
+
+    fn cycle_cost_basis_policy(&mut self) {
+        if !matches!(self.screen, Screen::Inventory) {
+            return;
+        }
+
+        self.game_data.cost_basis_policy = self.game_data.cost_basis_policy.next();
+        let activity = format!("📒 Cost basis policy: {}", self.game_data.cost_basis_policy.display());
+        self.game_data.recent_activities.insert(0, activity);
+        if self.game_data.recent_activities.len() > 10 {
+            self.game_data.recent_activities.truncate(10);
+        }
+    }
+
+    fn claim_achievement_rewards(&mut self) {
+        if self.game_data.claim_achievement_rewards() > 0 {
+            self.sound_effects.play(SoundType::Sale);
+        } else {
+            self.sound_effects.play(SoundType::Error);
+            self.game_data.recent_activities.insert(0, "❌ No achievement rewards waiting to be claimed".to_string());
+            if self.game_data.recent_activities.len() > 10 {
+                self.game_data.recent_activities.truncate(10);
+            }
+        }
+    }
+
+    fn toggle_auto_claim_rewards(&mut self) {
+        self.game_data.auto_claim_rewards = !self.game_data.auto_claim_rewards;
+        let status = if self.game_data.auto_claim_rewards { "on" } else { "off" };
+        self.game_data.recent_activities.insert(0, format!("⚙️ Auto-claim achievement rewards: {}", status));
+        if self.game_data.recent_activities.len() > 10 {
+            self.game_data.recent_activities.truncate(10);
+        }
+    }
+
+    fn toggle_goal(&mut self, goal: GameGoal) {
+        self.game_data.toggle_goal(goal);
+        let status = if self.game_data.active_goals.contains(&goal) { "on" } else { "off" };
+        self.game_data.recent_activities.insert(0, format!("🎯 Goal \"{}\": {}", goal.display(), status));
+        if self.game_data.recent_activities.len() > 10 {
+            self.game_data.recent_activities.truncate(10);
+        }
+    }
+
+    fn start_new_game(&mut self, difficulty: Difficulty) {
+        let rng = self.seed.map(GameRng::new).unwrap_or_else(GameRng::from_entropy);
+        self.game_data = GameData::with_options(difficulty, rng);
+        self.game_data.event_catalog = self.event_catalog.clone();
+        self.cart.clear();
+        self.paused = false;
+        self.last_time_update = Instant::now();
+        self.screen = Screen::Dashboard;
+        self.selected_menu_item = 0;
+    }
+
+    fn next_menu_item(&mut self) {
+        let menu_items = match self.screen {
+            Screen::MainMenu => 4, // New Game, Continue, Tutorial, Quit
+            Screen::NewGameSetup => Difficulty::ALL.len(),
+            Screen::Dashboard => 9, // Market, Orders, Inventory, Analytics, Achievements, Competitors, Settings, Save Game, Quit
+            Screen::Market => self.game_data.market.listings.len().max(1),
+            Screen::Orders => self.game_data.customer_orders.len().max(1), // Number of orders
+            Screen::Inventory => self.inventory_view().len().max(1), // Number of listed inventory items
+            Screen::Settings => 1 + GameGoal::ALL.len(), // Auto-claim, then one row per goal
+            Screen::RandomEvent => {
+                // Get number of choices for active event
+                if let Some(event) = &self.game_data.random_events.active_event {
+                    event.get_choices().len().max(1)
+                } else {
+                    1
+                }
+            },
+            _ => 1, // Other screens typically have minimal navigation
+        };
+        self.selected_menu_item = (self.selected_menu_item + 1) % menu_items;
+    }
+
+    fn previous_menu_item(&mut self) {
+        let menu_items = match self.screen {
+            Screen::MainMenu => 4,
+            Screen::NewGameSetup => Difficulty::ALL.len(),
+            Screen::Dashboard => 9,
+            Screen::Market => self.game_data.market.listings.len().max(1),
+            Screen::Orders => self.game_data.customer_orders.len().max(1),
+            Screen::Inventory => self.inventory_view().len().max(1),
+            Screen::Settings => 1 + GameGoal::ALL.len(),
+            Screen::RandomEvent => {
+                // Get number of choices for active event
+                if let Some(event) = &self.game_data.random_events.active_event {
+                    event.get_choices().len().max(1)
+                } else {
+                    1
+                }
+            },
+            _ => 1,
+        };
+        if self.selected_menu_item > 0 {
+            self.selected_menu_item -= 1;
+        } else {
+            self.selected_menu_item = menu_items - 1;
+        }
+    }
+
+    fn select_menu_item(&mut self) {
+        let previous_screen = self.screen.clone();
+        
+        match self.screen {
+            Screen::MainMenu => {
+                match self.selected_menu_item {
+                    0 => self.screen = Screen::NewGameSetup, // New Game
+                    1 if App::save_file_exists() => {
+                        // Only load if save file exists
+                        self.load_game();
+                    }, // Continue Game
+                    2 => {}, // Tutorial (not implemented yet)
+                    3 => self.should_quit = true, // Quit
+                    _ => {}
+                }
+            }
+            Screen::NewGameSetup => {
+                if let Some(difficulty) = Difficulty::ALL.get(self.selected_menu_item) {
+                    self.start_new_game(*difficulty);
+                }
+            }
+            Screen::Dashboard => {
+                match self.selected_menu_item {
+                    0 => self.screen = Screen::Market,       // [1] Market
+                    1 => self.screen = Screen::Orders,       // [2] Orders  
+                    2 => self.screen = Screen::Inventory,    // [3] Inventory
+                    3 => self.screen = Screen::Analytics,    // [4] Analytics
+                    4 => self.screen = Screen::Achievements, // [5] Achievements
+                    5 => self.screen = Screen::Competitors,  // [6] Competitors
+                    6 => self.screen = Screen::Settings,     // [7] Settings
+                    7 => { self.save_game(); },              // [8] Save Game
+                    8 => self.screen = Screen::MainMenu,     // [9] Quit to Menu
+                    _ => {}
+                }
+            }
+            Screen::Market => {
+                // Purchase item from market (stay on market screen)
+                self.purchase_from_market();
+                return; // Don't reset selection
+            }
+            Screen::Orders => {
+                // Send the counter-offer being drafted, otherwise fulfill the order (stay on orders screen)
+                if self.counter_offer.is_some() {
+                    self.submit_counter_offer();
+                } else {
+                    self.fulfill_customer_order();
+                }
+                return; // Don't reset selection
+            }
+            Screen::Inventory => {
+                // Sell inventory item (stay on inventory screen)
+                self.sell_inventory_item();
+                return; // Don't reset selection
+            }
+            Screen::RandomEvent => {
+                // Handle random event choice
+                self.handle_random_event_choice();
+                return; // Don't reset selection
+            }
+            Screen::Settings => {
+                // Toggle the selected setting (stay on settings screen)
+                match self.selected_menu_item {
+                    0 => self.toggle_auto_claim_rewards(),
+                    row => if let Some(goal) = GameGoal::ALL.get(row - 1) {
+                        self.toggle_goal(*goal);
+                    },
+                }
+                return; // Don't reset selection
+            }
+            Screen::GameOver => self.screen = Screen::MainMenu,
+            _ => {
+                // Other screens return to dashboard
+                self.screen = Screen::Dashboard;
+            }
+        }
+        
+        // Reset selection when changing screens
+        if !matches!((previous_screen, &self.screen), (Screen::Market, Screen::Market)) {
+            self.selected_menu_item = 0;
+        }
+    }
+
+    fn go_back(&mut self) {
+        if matches!(self.screen, Screen::Orders) && self.counter_offer.take().is_some() {
+            return; // Esc cancels the counter-offer but stays on Orders
+        }
+        if matches!(self.screen, Screen::CustomerDetail) {
+            // Back to the order we came from
+            self.screen = Screen::Orders;
+            self.selected_menu_item = self.customer_detail.take().map(|(_, row)| row).unwrap_or(0);
+            return;
+        }
+        match self.screen {
+            Screen::MainMenu => self.should_quit = true,
+            Screen::NewGameSetup => self.screen = Screen::MainMenu,
+            Screen::Dashboard | Screen::GameOver => self.screen = Screen::MainMenu,
+            _ => self.screen = Screen::Dashboard,
+        }
+        self.selected_menu_item = 0;
+    }
+
+    fn save_game(&mut self) -> bool {
+        const SAVE_FILE: &str = "savegame.json";
+        
+        match self.game_data.save_game(SAVE_FILE) {
+            Ok(()) => {
+                self.game_data.recent_activities.insert(0, "💾 Game saved successfully!".to_string());
+                if self.game_data.recent_activities.len() > 10 {
+                    self.game_data.recent_activities.truncate(10);
+                }
+                true
+            }
+            Err(_) => {
+                self.game_data.recent_activities.insert(0, "❌ Failed to save game".to_string());
+                if self.game_data.recent_activities.len() > 10 {
+                    self.game_data.recent_activities.truncate(10);
+                }
+                false
+            }
+        }
+    }
+
+    fn load_game(&mut self) -> bool {
+        const SAVE_FILE: &str = "savegame.json";
+        
+        match GameData::load_game(SAVE_FILE) {
+            Ok(loaded_game_data) => {
+                self.game_data = loaded_game_data;
+                self.game_data.event_catalog = self.event_catalog.clone();
+                self.game_data.random_events.relink_active_event(&self.event_catalog);
+                self.game_data.market.sync_with_catalog();
+                self.game_data.achievements.add_missing_achievements();
+                self.game_data.achievements.sync_with_retailers();
+                self.game_data.achievements.backfill_reward_ledger();
+                if self.game_data.competitors.is_empty() {
+                    self.game_data.competitors = Competitor::initial_rivals();
+                }
+                self.game_data.record_price_history();
+                self.cart.clear();
+                self.game_data.recent_activities.insert(0, "📂 Game loaded successfully!".to_string());
+                if self.game_data.recent_activities.len() > 10 {
+                    self.game_data.recent_activities.truncate(10);
+                }
+                self.screen = Screen::Dashboard;
+                true
+            }
+            Err(_) => {
+                // Create error message in current game_data
+                self.game_data.recent_activities.insert(0, "❌ Failed to load game".to_string());
+                if self.game_data.recent_activities.len() > 10 {
+                    self.game_data.recent_activities.truncate(10);
+                }
+                false
+            }
+        }
+    }
+
+    fn save_file_exists() -> bool {
+        const SAVE_FILE: &str = "savegame.json";
+        GameData::save_file_exists(SAVE_FILE)
+    }
+}
+
+// Parse an optional `--seed <n>` / `--seed=<n>` argument for reproducible runs
+fn parse_seed_arg<I: Iterator<Item = String>>(mut args: I) -> Result<Option<u64>, String> {
+    while let Some(arg) = args.next() {
+        let value = if arg == "--seed" {
+            args.next().ok_or_else(|| "--seed requires a value".to_string())?
+        } else if let Some(value) = arg.strip_prefix("--seed=") {
+            value.to_string()
+        } else {
+            continue;
+        };
+
+        return value.parse::<u64>()
+            .map(Some)
+            .map_err(|_| format!("invalid seed '{}': expected a non-negative integer", value));
+    }
+    Ok(None)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let seed = parse_seed_arg(std::env::args().skip(1))?;
+
+    // setup terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // create app and run it
+    let app = App::new(seed);
+    let res = run_app(&mut terminal, app);
+
+    // restore terminal
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    if let Err(err) = res {
+        println!("{err:?}");
+    }
+
+    Ok(())
+}
+
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
+    loop {
+        // Update game time
+        app.update_time();
+        
+        // Check for active random events that need player choice
+        app.check_for_active_events();
+        
+        // Check for audio events (achievements, new orders, etc.)
+        app.check_for_audio_events();
+
+        // Pay out achievement rewards right away when auto-claim is on
+        app.game_data.settle_achievement_rewards();
+        
+        terminal.draw(|f| ui(f, &app))?;
+
+        // Use poll instead of read to avoid blocking
+        if event::poll(Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Esc => app.go_back(),
+                KeyCode::Down => app.next_menu_item(),
+                KeyCode::Up => app.previous_menu_item(),
+                KeyCode::Enter => app.select_menu_item(),
+                KeyCode::Char(' ') => app.toggle_pause(), // Spacebar to pause
+                // Number key quick access for dashboard
+                KeyCode::Char('1') if matches!(app.screen, Screen::Dashboard) => {
+                    app.selected_menu_item = 0;
+                    app.select_menu_item();
+                },
+                KeyCode::Char('2') if matches!(app.screen, Screen::Dashboard) => {
+                    app.selected_menu_item = 1;
+                    app.select_menu_item();
+                },
+                KeyCode::Char('3') if matches!(app.screen, Screen::Dashboard) => {
+                    app.selected_menu_item = 2;
+                    app.select_menu_item();
+                },
+                KeyCode::Char('4') if matches!(app.screen, Screen::Dashboard) => {
+                    app.selected_menu_item = 3;
+                    app.select_menu_item();
+                },
+                KeyCode::Char('5') if matches!(app.screen, Screen::Dashboard) => {
+                    app.selected_menu_item = 4;
+                    app.select_menu_item();
+                },
+                KeyCode::Char('6') if matches!(app.screen, Screen::Dashboard) => {
+                    app.selected_menu_item = 5;
+                    app.select_menu_item();
+                },
+                KeyCode::Char('7') if matches!(app.screen, Screen::Dashboard) => {
+                    app.selected_menu_item = 6;
+                    app.select_menu_item();
+                },
+                KeyCode::Char('8') if matches!(app.screen, Screen::Dashboard) => {
+                    app.selected_menu_item = 7;
+                    app.select_menu_item();
+                },
+                KeyCode::Char('9') if matches!(app.screen, Screen::Dashboard) => {
+                    app.selected_menu_item = 8;
+                    app.select_menu_item();
+                },
+                // Market purchase quantity
+                KeyCode::Char('+') | KeyCode::Char('=') if matches!(app.screen, Screen::Market) => {
+                    app.adjust_purchase_quantity(1);
+                },
+                KeyCode::Char('-') if matches!(app.screen, Screen::Market) => {
+                    app.adjust_purchase_quantity(-1);
+                },
+                KeyCode::Char('m') | KeyCode::Char('M') if matches!(app.screen, Screen::Market) => {
+                    app.set_max_affordable_quantity();
+                },
+                KeyCode::Char(c) if c.is_ascii_digit() && matches!(app.screen, Screen::Market) => {
+                    app.type_purchase_quantity_digit(c);
+                },
+                KeyCode::Backspace if matches!(app.screen, Screen::Market) => {
+                    app.erase_purchase_quantity_digit();
+                },
+                // Market shopping cart
+                KeyCode::Char('a') | KeyCode::Char('A') if matches!(app.screen, Screen::Market) => {
+                    app.add_to_cart();
+                },
+                KeyCode::Char('x') | KeyCode::Char('X') if matches!(app.screen, Screen::Market) => {
+                    app.remove_from_cart();
+                },
+                KeyCode::Char('c') | KeyCode::Char('C') if matches!(app.screen, Screen::Market) => {
+                    app.checkout_cart();
+                },
+                // Order negotiation
+                KeyCode::Char('i') | KeyCode::Char('I') if matches!(app.screen, Screen::Orders) => {
+                    app.view_order_customer();
+                },
+                KeyCode::Char('b') | KeyCode::Char('B') if matches!(app.screen, Screen::Orders) => {
+                    app.toggle_order_partial_shipment();
+                },
+                KeyCode::Char('b') | KeyCode::Char('B') if matches!(app.screen, Screen::CustomerDetail) => {
+                    app.toggle_customer_partial_shipments();
+                },
+                KeyCode::Char('d') | KeyCode::Char('D') if matches!(app.screen, Screen::Orders) => {
+                    app.decline_customer_order();
+                },
+                KeyCode::Char('n') | KeyCode::Char('N') if matches!(app.screen, Screen::Orders) => {
+                    app.start_counter_offer();
+                },
+                KeyCode::Char('+') | KeyCode::Char('=') if matches!(app.screen, Screen::Orders) => {
+                    app.adjust_counter_offer(1);
+                },
+                KeyCode::Char('-') if matches!(app.screen, Screen::Orders) => {
+                    app.adjust_counter_offer(-1);
+                },
+                // Achievement rewards
+                KeyCode::Char('c') | KeyCode::Char('C') if matches!(app.screen, Screen::Achievements) => {
+                    app.claim_achievement_rewards();
+                },
+                KeyCode::Char('e') | KeyCode::Char('E') if matches!(app.screen, Screen::Inventory) => {
+                    app.toggle_inventory_expiry_sort();
+                },
+                KeyCode::Char('f') | KeyCode::Char('F') if matches!(app.screen, Screen::Inventory) => {
+                    app.cycle_inventory_filter();
+                },
+                KeyCode::Char('+') | KeyCode::Char('=') if matches!(app.screen, Screen::Inventory) => {
+                    app.adjust_retail_price(1);
+                },
+                KeyCode::Char('-') if matches!(app.screen, Screen::Inventory) => {
+                    app.adjust_retail_price(-1);
+                },
+                KeyCode::Char('u') | KeyCode::Char('U') if matches!(app.screen, Screen::Inventory) => {
+                    app.upgrade_storage();
+                },
+                KeyCode::Char('p') | KeyCode::Char('P') if matches!(app.screen, Screen::Inventory) => {
+                    app.cycle_cost_basis_policy();
+                },
+                KeyCode::Char('s') | KeyCode::Char('S') => {
+                    // Toggle sound effects
+                    app.sound_effects.toggle();
+                    let status = if app.sound_effects.is_enabled() { 
+                        "🔊 Sound effects enabled" 
+                    } else { 
+                        "🔇 Sound effects disabled" 
+                    };
+                    app.game_data.recent_activities.insert(0, status.to_string());
+                    if app.game_data.recent_activities.len() > 10 {
+                        app.game_data.recent_activities.truncate(10);
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        if app.should_quit {
+            return Ok(());
+        }
+    }
+}
+
+fn ui(f: &mut Frame, app: &App) {
+    match app.screen {
+        Screen::MainMenu => draw_main_menu(f, app),
+        Screen::NewGameSetup => draw_new_game_setup(f, app),
+        Screen::Dashboard => draw_dashboard(f, app),
+        Screen::Market => draw_market(f, app),
+        Screen::Orders => draw_orders(f, app),
+        Screen::Inventory => draw_inventory(f, app),
+        Screen::Analytics => draw_analytics(f, app),
+        Screen::Achievements => draw_achievements_screen(f, app),
+        Screen::Settings => draw_settings(f, app),
+        Screen::RandomEvent => draw_random_event(f, app),
+        Screen::CustomerDetail => draw_customer_detail(f, app),
+        Screen::Competitors => draw_competitors(f, app),
+        Screen::GameOver => draw_game_over(f, app),
+    }
+}
+
+fn draw_main_menu(f: &mut Frame, app: &App) {
+    let size = f.area();
+
+    let block = Block::default()
+        .title("GIFT CARD EMPIRE")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(Color::White));
+
+    let save_available = App::save_file_exists();
+    let continue_text = if save_available {
+        "Continue Game"
+    } else {
+        "Continue (No save file found)"
+    };
+    
+    let menu_items = [
+        "New Game",
+        continue_text,
+        "Tutorial", 
+        "Quit",
+    ];
+
+    let items: Vec<ListItem> = menu_items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let style = if i == app.selected_menu_item {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            
+            let prefix = if i == app.selected_menu_item { "► " } else { "  " };
+            ListItem::new(Line::from(Span::styled(format!("{}{}", prefix, item), style)))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .style(Style::default().fg(Color::White));
+
+    let area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(100)])
+        .split(size)[0];
+
+    f.render_widget(list, area);
+
+    // Instructions at the bottom
+    let instructions = Paragraph::new("Use ↑↓ to navigate, Enter to select, Q to quit")
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+
+    let instruction_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(size)[1];
+
+    f.render_widget(instructions, instruction_area);
+}
+
+fn draw_new_game_setup(f: &mut Frame, app: &App) {
+    let size = f.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Difficulty list
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    let header = Paragraph::new("Choose your difficulty")
+        .block(Block::default()
+            .title("New Game Setup")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Green))
+        .alignment(Alignment::Center);
+
+    f.render_widget(header, chunks[0]);
+
+    let items: Vec<ListItem> = Difficulty::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, difficulty)| {
+            let selected = i == app.selected_menu_item;
+            let style = if selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+
+            let prefix = if selected { "► " } else { "  " };
+            let lines = vec![
+                Line::from(Span::styled(format!("{}{}", prefix, difficulty.display()), style)),
+                Line::from(Span::styled(
+                    format!("    {}", difficulty.description()),
+                    Style::default().fg(Color::Gray)
+                )),
+            ];
+            ListItem::new(Text::from(lines))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .title("Difficulty")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)));
+
+    f.render_widget(list, chunks[1]);
+
+    let footer = Paragraph::new("↑↓ Select  Enter Start Game  Esc Back")
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(footer, chunks[2]);
+}
+
+fn draw_dashboard(f: &mut Frame, app: &App) {
+    let size = f.area();
+    
+    // Create layout: Header, Main content, Footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Main content
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    // Header with game stats including season
+    let season = app.game_data.market_conditions.current_season.display();
+    let active_events = app.game_data.market_conditions.active_events.len();
+    let events_info = if active_events > 0 {
+        format!(" • {} events", active_events)
+    } else {
+        String::new()
+    };
+    
+    // Add animated time progression indicator
+    let time_indicator = if app.paused {
+        "⏸️"
+    } else {
+        // Cycle through different clock symbols for visual time progression
+        match (app.game_data.minute / 10) % 6 {
+            0 => "🕐",
+            1 => "🕑",
+            2 => "🕒",
+            3 => "🕓",
+            4 => "🕔",
+            _ => "🕕",
+        }
+    };
+    
+    // Add random event indicator if active
+    let random_event_status = if app.game_data.random_events.player_choice_pending {
+        " 🎲❗"
+    } else if !app.game_data.random_events.temp_modifiers.is_empty() {
+        " 🎲✨"
+    } else {
+        ""
+    };
+    
+    let header_text = format!(
+        "Cash: ${}    Rep: {} ({})    Day: {}    Time: {} {}    Season: {}{}{}    Storage: {}    [{}]",
+        app.game_data.cash,
+        app.game_data.reputation_stars(),
+        app.game_data.reputation_description(),
+        app.game_data.day,
+        app.game_data.time_display(),
+        time_indicator,
+        season,
+        events_info,
+        random_event_status,
+        app.game_data.storage_display(),
+        app.game_data.difficulty.display()
+    );
+    
+    let header = Paragraph::new(header_text)
+        .block(Block::default()
+            .title("Gift Card Empire")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Green))
+        .alignment(Alignment::Center);
+    
+    f.render_widget(header, chunks[0]);
+
+    // Main content area split into menu and activity
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(50), // Menu
+            Constraint::Percentage(50), // Activity feed
+        ])
+        .split(chunks[1]);
+
+    // Menu options
+    let menu_items = [
+        "[1] Market",
+        "[2] Orders", 
+        "[3] Inventory",
+        "[4] Analytics",
+        "[5] Achievements",
+        "[6] Competitors",
+        "[7] Settings",
+        "[8] Save Game",
+        "[9] Quit to Menu",
+    ];
+
+    let menu_list_items: Vec<ListItem> = menu_items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let style = if i == app.selected_menu_item {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            
+            let prefix = if i == app.selected_menu_item { "► " } else { "  " };
+            ListItem::new(Line::from(Span::styled(format!("{}{}", prefix, item), style)))
+        })
+        .collect();
+
+    let menu_list = List::new(menu_list_items)
+        .block(Block::default()
+            .title("Quick Actions")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(menu_list, main_chunks[0]);
+
+    // Recent activity feed
+    let activity_items: Vec<ListItem> = app.game_data.recent_activities
+        .iter()
+        .map(|activity| {
+            ListItem::new(Line::from(Span::styled(
+                format!("• {}", activity),
+                Style::default().fg(Color::Cyan)
+            )))
+        })
+        .collect();
+
+    let activity_list = List::new(activity_items)
+        .block(Block::default()
+            .title("Recent Activity")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(activity_list, main_chunks[1]);
+
+    // Footer with controls and pause status
+    let pause_indicator = if app.paused { " ⏸️ PAUSED" } else { "" };
+    let sound_indicator = if app.sound_effects.is_enabled() { " 🔊" } else { " 🔇" };
+    let footer_text = format!(
+        "↑↓ Navigate  Enter Select  [1-9] Quick Access  Space Pause  S Sound{}  Esc Back  Q Quit{}",
+        sound_indicator,
+        pause_indicator
+    );
+    let footer = Paragraph::new(footer_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(footer, chunks[2]);
+}
+
+fn draw_market(f: &mut Frame, app: &App) {
+    let size = f.area();
+    
+    // Create layout: Header, Market table, Purchase details, Footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Market content
+            Constraint::Length(6), // Price history
+            Constraint::Length(5), // Purchase details
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    // Header showing budget
+    let header_text = format!("Your Budget: ${}", app.game_data.cash);
+    let header = Paragraph::new(header_text)
+        .block(Block::default()
+            .title("Wholesale Market")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Green))
+        .alignment(Alignment::Center);
+    
+    f.render_widget(header, chunks[0]);
+
+    // Market items table with dynamic pricing
+    let conditions = &app.game_data.market_conditions;
+    let market_items: Vec<(&MarketListing, u32, String)> = app.game_data.market.listings.iter()
+        .map(|listing| {
+            let price_multiplier = conditions.get_price_multiplier_with_random_events(&listing.retailer, &app.game_data.random_events)
+                * listing.scarcity_multiplier();
+            let actual_cost = app.game_data.wholesale_price(listing);
+            // More detailed animated trend indicators
+            let trend = if price_multiplier > 1.2 {
+                match (app.game_data.minute / 5) % 3 {
+                    0 => "🔥↗".to_string(),
+                    1 => "🚀↗".to_string(), 
+                    _ => "📈↗".to_string(),
+                }
+            } else if price_multiplier > 1.1 {
+                "📈↗".to_string() // Rising
+            } else if price_multiplier < 0.8 {
+                match (app.game_data.minute / 5) % 3 {
+                    0 => "💎↘".to_string(),
+                    1 => "🎯↘".to_string(),
+                    _ => "📉↘".to_string(),
+                }
+            } else if price_multiplier < 0.9 {
+                "📉↘".to_string() // Falling  
+            } else {
+                "➡️".to_string() // Stable
+            };
+            (listing, actual_cost, trend)
+        })
+        .collect();
+
+    // Create table header and rows
+    let table_header = [
+        "Retailer    │ Value │ Cost │ Stock │ Restock      │ Profit │ Trend",
+        "────────────┼───────┼──────┼───────┼──────────────┼────────┼──────",
+    ];
+    let mut listing_rows: Vec<String> = Vec::new();
+
+    for (i, (listing, cost, trend)) in market_items.iter().enumerate() {
+        let profit = listing.denomination as i32 - *cost as i32;
+        let style_char = if i == app.selected_menu_item { "►" } else { " " };
+        let restock = match listing.days_until_full(conditions) {
+            None => "Full".to_string(),
+            Some(days) => format!("+{:.0}/d, {}d", listing.daily_restock(conditions), days),
+        };
+        
+        listing_rows.push(format!(
+            "{} {:10} │ ${:3} │ ${:3}│ {:>2}/{:<2} │ {:12} │ {:+3}    │  {}",
+            style_char, retailer_name(&listing.retailer), listing.denomination, cost, listing.stock, listing.max_stock, restock, profit, trend
+        ));
+    }
+
+    // Cart review panel sits beside the listings while the cart has lines
+    let (listings_area, cart_area) = if app.cart.is_empty() {
+        (chunks[1], None)
+    } else {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(62), Constraint::Percentage(38)])
+            .split(chunks[1]);
+        (columns[0], Some(columns[1]))
+    };
+
+    // Scroll the listings under the fixed header so the selection stays visible
+    let visible_rows = (listings_area.height as usize).saturating_sub(2 + table_header.len()).max(1);
+    let scroll = (app.selected_menu_item + 1).saturating_sub(visible_rows);
+
+    let header_items = table_header.iter()
+        .map(|line| ListItem::new(Line::from(Span::styled(*line, Style::default().fg(Color::Gray)))));
+    let listing_items = listing_rows.iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible_rows)
+        .map(|(i, line)| {
+            let style = if i == app.selected_menu_item {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            
+            ListItem::new(Line::from(Span::styled(line.clone(), style)))
+        });
+    let table_items: Vec<ListItem> = header_items.chain(listing_items).collect();
+
+    let market_list = List::new(table_items)
+        .block(Block::default()
+            .title(format!("Available Cards ({})", listing_rows.len()))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(market_list, listings_area);
+
+    if let Some(cart_area) = cart_area {
+        let quotes = app.cart_quote();
+        let total = app.cart_total();
+        let mut cart_lines: Vec<Line> = quotes.iter()
+            .map(|quote| {
+                let discount = if quote.volume_discount > 0.0 {
+                    format!(" -{:.0}%", quote.volume_discount * 100.0)
+                } else {
+                    String::new()
+                };
+                let style = if quote.in_stock < quote.quantity {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::from(Span::styled(
+                    format!(
+                        "{} ${} x{} @ ${}{} = ${}",
+                        quote.retailer, quote.denomination, quote.quantity, quote.unit_price, discount, quote.line_total()
+                    ),
+                    style,
+                ))
+            })
+            .collect();
+        cart_lines.push(Line::from(""));
+        cart_lines.push(Line::from(Span::styled(
+            format!("Total: ${}", total),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )));
+        cart_lines.push(if app.game_data.can_afford(total) {
+            Line::from(Span::styled(
+                format!("Cash after: ${}", app.game_data.cash - total),
+                Style::default().fg(Color::Green),
+            ))
+        } else {
+            Line::from(Span::styled(
+                format!("Short by ${}", total - app.game_data.cash),
+                Style::default().fg(Color::Red),
+            ))
+        });
+
+        let cart = Paragraph::new(cart_lines)
+            .block(Block::default()
+                .title(format!("Cart ({} lines)", quotes.len()))
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::White)));
+
+        f.render_widget(cart, cart_area);
+    }
+
+    // Purchase details for the selected listing: quantity, bulk tier and retailer relationship
+    let details = match app.game_data.market.listings.get(app.selected_menu_item) {
+        Some(listing) => {
+            let quantity = app.purchase_quantity;
+            let unit_price = app.game_data.bulk_unit_price(listing, quantity);
+            let volume_discount = listing.volume_discount(quantity);
+            let next_tier = match listing.next_volume_tier(quantity) {
+                Some(tier) => format!("  (buy {}+ for {:.0}% off)", tier.min_quantity, tier.discount * 100.0),
+                None => String::new(),
+            };
+            let market = &app.game_data.market;
+            let units = market.lifetime_units(&listing.retailer);
+            let discount = market.relationship_discount(&listing.retailer);
+            let relationship = match market.next_relationship_tier(&listing.retailer) {
+                Some((next_units, next_discount)) => format!(
+                    "{} units bought, {:.0}% off base  (next: {:.0}% at {})",
+                    units, discount * 100.0, next_discount * 100.0, next_units
+                ),
+                None => format!("{} units bought, {:.0}% off base  (top tier)", units, discount * 100.0),
+            };
+            vec![
+                Line::from(format!(
+                    "Qty: {}  @ ${} = ${}  │  Bulk: {:.0}% off{}  │  Max affordable: {}",
+                    quantity, unit_price, unit_price * quantity, volume_discount * 100.0, next_tier,
+                    app.game_data.max_affordable_quantity(listing)
+                )),
+                Line::from(format!("{} relationship: {}", retailer_name(&listing.retailer), relationship)),
+            ]
+        }
+        None => vec![Line::from("No listing selected")],
+    };
+    let details_paragraph = Paragraph::new(details)
+        .block(Block::default()
+            .title("Order")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Cyan));
+
+    f.render_widget(details_paragraph, chunks[3]);
+
+    // Price history for the selected card: wholesale cost and the retailer's demand
+    if let Some(listing) = app.game_data.market.listings.get(app.selected_menu_item) {
+        draw_price_history(f, app, listing, chunks[2]);
+    }
+
+    // Footer with controls
+    let footer_text = "↑↓ Select  +/- Qty  0-9 Type Qty  M Max  Enter Buy  A Add to Cart  X Remove  C Checkout  Esc Back";
+    let footer = Paragraph::new(footer_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(footer, chunks[4]);
+}
+
+fn draw_price_history(f: &mut Frame, app: &App, listing: &MarketListing, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+
+    let prices = app.game_data.price_history_for(&listing.retailer, listing.denomination);
+    let today = app.game_data.wholesale_price(listing);
+    let (title, verdict_color) = match price_range(&prices) {
+        Some((min, average, max)) => {
+            let (verdict, color) = if (today as f32) < average * 0.97 {
+                ("good buy", Color::Green)
+            } else if (today as f32) > average * 1.03 {
+                ("pricey", Color::Red)
+            } else {
+                ("typical", Color::Cyan)
+            };
+            (format!(
+                "{} ${} cost, {}d: min ${} avg ${:.1} max ${} │ today ${} ({})",
+                retailer_name(&listing.retailer), listing.denomination, prices.len(), min, average, max, today, verdict
+            ), color)
+        }
+        None => ("No price history yet".to_string(), Color::Gray),
+    };
+    // Drawn above the cheapest day so small swings stay visible
+    let floor = prices.iter().min().copied().unwrap_or(0).saturating_sub(1);
+    let bars: Vec<u64> = prices.iter().map(|price| (price - floor) as u64).collect();
+    let cost_sparkline = Sparkline::default()
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .data(&bars)
+        .style(Style::default().fg(verdict_color));
+    f.render_widget(cost_sparkline, columns[0]);
+
+    let demand = app.game_data.demand_history_for(&listing.retailer);
+    let demand_title = match demand.iter().copied().reduce(f32::min).zip(demand.iter().copied().reduce(f32::max)) {
+        Some((min, max)) => format!(
+            "Demand: min {:.2}x avg {:.2}x max {:.2}x",
+            min, demand.iter().sum::<f32>() / demand.len() as f32, max
+        ),
+        None => "Demand: no history yet".to_string(),
+    };
+    let demand_bars: Vec<u64> = demand.iter().map(|multiplier| (multiplier * 100.0) as u64).collect();
+    let demand_sparkline = Sparkline::default()
+        .block(Block::default().title(demand_title).borders(Borders::ALL))
+        .data(&demand_bars)
+        .style(Style::default().fg(Color::Magenta));
+    f.render_widget(demand_sparkline, columns[1]);
+}
+
+// Customer name with their loyalty icon, for order lists
+fn customer_label(app: &App, order: &CustomerOrder) -> String {
+    match app.game_data.customers.iter().find(|customer| customer.id == order.customer_id) {
+        Some(customer) => format!("{} {}", customer.loyalty_tier().icon(), order.customer_name),
+        None => order.customer_name.clone(),
+    }
+}
+
+fn draw_orders(f: &mut Frame, app: &App) {
+    let size = f.area();
+    
+    // Create layout: Header, Orders list, Footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Orders content
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    // Header
+    let header_text = format!("Active Orders: {}", app.game_data.customer_orders.len());
+    let header = Paragraph::new(header_text)
+        .block(Block::default()
+            .title("Customer Orders")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Green))
+        .alignment(Alignment::Center);
+    
+    f.render_widget(header, chunks[0]);
+
+    // Orders list
+    if app.game_data.customer_orders.is_empty() {
+        let no_orders = Paragraph::new("No customer orders available\n\nNew orders will appear over time")
+            .block(Block::default()
+                .title("Orders")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::White)))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        
+        f.render_widget(no_orders, chunks[1]);
+    } else {
+        // Create table header and rows; each order gets one row per requested line
+        let mut table_content = vec![
+            ("   Order #  │ Customer │ Item           │ Qty │ Each │ Total │ Days │ Priority".to_string(), None),
+            ("────────────┼──────────┼────────────────┼─────┼──────┼───────┼──────┼────────".to_string(), None),
+        ];
+
+        for (i, order) in app.game_data.customer_orders.iter().enumerate() {
+            let style_char = if i == app.selected_menu_item { "►" } else { " " };
+            let priority_color = match order.priority {
+                OrderPriority::High => "🔴",
+                OrderPriority::Medium => "🟡", 
+                OrderPriority::Low => "🟢",
+            };
+            
+            // Check if order can be fulfilled (📦 = some lines can ship now)
+            let fulfillment_indicator = if app.game_data.can_fulfill_order(order) {
+                "✅"
+            } else if order.allow_partial && app.game_data.can_ship_any_line(order) {
+                "📦"
+            } else {
+                "❌"
+            };
+            
+            for (line_index, line) in order.requested_cards.iter().enumerate() {
+                let line_status = if line.outstanding() == 0 {
+                    "✔"
+                } else if app.game_data.can_fulfill_line(line) {
+                    " "
+                } else if app.game_data.available_cards(&line.retailer, line.denomination) > 0 {
+                    "~"
+                } else {
+                    "✗"
+                };
+                let row = if line_index == 0 {
+                    format!(
+                        "{} {} #{:4} │ {:8} │{}{:8} ${:3}  │ {:>3} │ ${:3} │ ${:4} │  {:2}  │ {} {}{}",
+                        style_char,
+                        fulfillment_indicator,
+                        order.id,
+                        customer_label(app, order),
+                        line_status,
+                        line.retailer,
+                        line.denomination,
+                        line.progress(),
+                        line.price_per_card,
+                        order.total_offered(),
+                        order.deadline_days,
+                        priority_color,
+                        order.priority.display(),
+                        if order.backordered {
+                            " BACKORDER"
+                        } else if order.allow_partial {
+                            " (partial OK)"
+                        } else {
+                            ""
+                        }
+                    )
+                } else {
+                    format!(
+                        "            │          │{}{:8} ${:3}  │ {:>3} │ ${:3} │       │      │",
+                        line_status,
+                        line.retailer,
+                        line.denomination,
+                        line.progress(),
+                        line.price_per_card
+                    )
+                };
+                table_content.push((row, Some(i)));
+            }
+        }
+
+        let table_items: Vec<ListItem> = table_content
+            .iter()
+            .map(|(line, order_index)| {
+                let style = match order_index {
+                    Some(index) if *index == app.selected_menu_item => {
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                    }
+                    Some(_) => Style::default().fg(Color::White),
+                    None => Style::default().fg(Color::Gray),
+                };
+                
+                ListItem::new(Line::from(Span::styled(line.clone(), style)))
+            })
+            .collect();
+
+        let orders_list = List::new(table_items)
+            .block(Block::default()
+                .title("Available Orders")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::White)))
+            .style(Style::default().fg(Color::White));
+
+        f.render_widget(orders_list, chunks[1]);
+    }
+
+    // Footer with controls, or the counter-offer being drafted
+    let negotiating = app.counter_offer.as_ref().and_then(|counter| {
+        let order = app.game_data.customer_orders.iter().find(|order| order.id == counter.order_id)?;
+        Some((counter, order))
+    });
+    let footer_text = match negotiating {
+        Some((counter, order)) => format!(
+            "Counter #{}: ${} (offer ${}, ~{:.0}% accept, patience {})  +/- ${}  Enter Send  Esc Cancel",
+            order.id,
+            counter.total_price,
+            order.total_offered(),
+            app.game_data.counter_offer_acceptance(order, counter.total_price) * 100.0,
+            order.patience,
+            counter.step
+        ),
+        None => "↑↓ Select  Enter Fulfill  B Partial/Backorder  N Counter-offer  D Decline  I Customer  Esc Back".to_string(),
+    };
+    let footer = Paragraph::new(footer_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(footer, chunks[2]);
+}
+
+fn draw_inventory(f: &mut Frame, app: &App) {
+    let size = f.area();
+    
+    // Create layout: Header, Inventory list, Footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Inventory content
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    // Header showing total inventory value
+    let total_value = app.game_data.total_inventory_value();
+    let inventory_count = app.game_data.inventory_count();
+    let header_text = format!(
+        "Total Value: ${}    Items: {}    Storage: {} {}    Unrealized: ${:+}    Realized: ${:+}    Cost Basis: {}",
+        total_value,
+        inventory_count,
+        app.game_data.storage_tier().name,
+        app.game_data.storage_display(),
+        app.game_data.unrealized_gains(),
+        app.game_data.analytics.realized_gains(),
+        app.game_data.cost_basis_policy.display()
+    );
+    let header = Paragraph::new(header_text)
+        .block(Block::default()
+            .title("Inventory Management")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Green))
+        .alignment(Alignment::Center);
+    
+    f.render_widget(header, chunks[0]);
+
+    // Inventory list, in the order and subset chosen with E / F
+    let view = app.inventory_view();
+    let today = app.game_data.day;
+    if view.is_empty() {
+        let message = if app.game_data.inventory.is_empty() {
+            "No inventory available\n\nVisit the Market to purchase gift cards".to_string()
+        } else {
+            format!("No cards match the '{}' filter\n\nPress F to change the filter", app.inventory_filter.display())
+        };
+        let no_inventory = Paragraph::new(message)
+            .block(Block::default()
+                .title("Inventory")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::White)))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        
+        f.render_widget(no_inventory, chunks[1]);
+    } else {
+        // Create table header and rows
+        let mut table_content = vec![
+            "   Card          │ Qty │ Cost │ Days Left │ Expires │ Market Price │ Profit │ Ask (walk-in/h) │ Action".to_string(),
+            "─────────────────┼─────┼──────┼───────────┼─────────┼──────────────┼────────┼─────────────────┼───────".to_string(),
+        ];
+
+        for (i, item) in view.iter().map(|&index| &app.game_data.inventory[index]).enumerate() {
+            let days_left = item.card.days_until_expiration(today);
+            let style_char = if i == app.selected_menu_item { "►" } else { " " };
+            
+            // Calculate profit potential
+            let market_value = item.card.market_value();
+            let profit_per_card = market_value as i32 - item.card.purchase_price as i32;
+            let total_profit = profit_per_card * item.quantity as i32;
+            
+            // Enhanced expiration indicators with animation
+            let expiration_indicator = if days_left <= 3 {
+                // Critical - blinking warning
+                match (app.game_data.minute / 3) % 3 {
+                    0 => "🚨",
+                    1 => "❗",
+                    _ => "⚠️",
+                }
+            } else if days_left <= 7 {
+                // Warning - steady indicator
+                "⚠️"
+            } else if days_left <= 14 {
+                // Caution - mild indicator
+                "⚡"
+            } else {
+                // Fresh - good indicator
+                "✅"
+            };
+            
+            // Format retailer name with padding
+            let retailer_display = format!("{:<10}", retailer_name(&item.card.retailer));
+            
+            table_content.push(format!(
+                "{}{} {} ${:>2} │ {:>3} │ ${:>3} │    {:>3}    │ Day {:>3} │     ${:>3}     │  ${:>4} │  ${:>3} ({:>4.1}%)  │ [Sell]",
+                style_char,
+                expiration_indicator,
+                retailer_display,
+                item.card.denomination,
+                item.quantity,
+                item.card.purchase_price,
+                days_left,
+                item.card.expiration_day,
+                market_value,
+                total_profit,
+                app.game_data.retail_price(&item.card.retailer, item.card.denomination),
+                app.game_data.walk_in_chance(&item.card) * 100.0
+            ));
+        }
+
+        let table_items: Vec<ListItem> = table_content
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let style = if i >= 2 && (i - 2) == app.selected_menu_item {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else if i < 2 {
+                    Style::default().fg(Color::Gray)
+                } else if line.contains("❗") {
+                    Style::default().fg(Color::Red) // Expiring items in red
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                
+                ListItem::new(Line::from(Span::styled(line.clone(), style)))
+            })
+            .collect();
+
+        let order = if app.inventory_sort_by_expiry { "soonest expiry first" } else { "purchase order" };
+        let inventory_list = List::new(table_items)
+            .block(Block::default()
+                .title(format!("Current Stock ({}/{} stacks, {}, filter: {})", view.len(), app.game_data.inventory.len(), order, app.inventory_filter.display()))
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::White)))
+            .style(Style::default().fg(Color::White));
+
+        f.render_widget(inventory_list, chunks[1]);
+    }
+
+    // Footer with controls
+    let upgrade_hint = match app.game_data.next_storage_tier() {
+        Some(next) => format!("U Upgrade ${} → {} cards, ${}/day", next.upgrade_cost, next.capacity, next.daily_upkeep),
+        None => "Storage fully upgraded".to_string(),
+    };
+    let footer_text = format!("↑↓ Select  Enter Sell Item  +/- Ask Price  E Sort by Expiry  F Filter  P Cost Policy  {}  Esc Back  ❗ = Expiring Soon", upgrade_hint);
+    let footer = Paragraph::new(footer_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(footer, chunks[2]);
+}
+
+fn draw_analytics(f: &mut Frame, app: &App) {
+    let size = f.area();
+    
+    // Create layout: Header, Main content (left metrics, right charts), Footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Main content
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    // Header
+    let analytics = &app.game_data.analytics;
+    let total_profit = analytics.total_profit();
+    let profit_color = if total_profit >= 0 { Color::Green } else { Color::Red };
+    
+    let header_text = format!(
+        "Total Profit: ${:+}    Revenue: ${}    Orders: {}    Avg Margin: {:.1}%",
+        total_profit,
+        analytics.total_revenue,
+        analytics.orders_completed,
+        analytics.average_profit_margin()
+    );
+    
+    let header = Paragraph::new(header_text)
+        .block(Block::default()
+            .title("Business Analytics Dashboard")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(profit_color))
+        .alignment(Alignment::Center);
+    
+    f.render_widget(header, chunks[0]);
+
+    // Main content: metrics and trends side by side, demand forecast underneath
+    let content_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),                                // Metrics and trends
+            Constraint::Length(RETAILERS.len() as u16 + 4),    // Forecast
+        ])
+        .split(chunks[1]);
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(50), // Key metrics
+            Constraint::Percentage(50), // Performance data
+        ])
+        .split(content_chunks[0]);
+    draw_forecast(f, app, content_chunks[1]);
+
+    // Left column: Key Business Metrics
+    let key_metrics = vec![
+        format!("💰 Total Revenue:          ${}", analytics.total_revenue),
+        format!("🏆 Achievement Rewards:    ${}", analytics.achievement_rewards),
+        format!("💳 Total Purchases:        ${}", analytics.total_purchases),
+        format!("💸 Operating Expenses:     ${}", analytics.total_expenses()),
+        format!("📈 Net Profit:            ${:+}", total_profit),
+        format!("📦 Cost of Goods Sold:     ${}", analytics.cost_of_goods_sold),
+        format!("✅ Realized Gains:        ${:+}", analytics.realized_gains()),
+        format!("📊 Unrealized Gains:      ${:+}", app.game_data.unrealized_gains()),
+        "".to_string(),
+        format!("📋 Orders Completed:       {}", analytics.orders_completed),
+        format!("⏰ Orders Expired:         {}", analytics.orders_expired),
+        format!("🙅 Orders Declined:        {}", analytics.orders_declined),
+        format!("📊 Success Rate:          {:.1}%", {
+            let total_orders = analytics.orders_completed + analytics.orders_expired;
+            if total_orders > 0 {
+                (analytics.orders_completed as f32 / total_orders as f32) * 100.0
+            } else {
+                0.0
+            }
+        }),
+        "".to_string(),
+        format!("🎯 Cards Sold:            {}", analytics.cards_sold),
+        format!("🚶 Walk-in Sales:         {}", analytics.walk_in_cards_sold),
+        format!("💀 Cards Expired:         {}", analytics.cards_expired),
+        format!("🔄 Card Efficiency:       {:.1}%", {
+            let total_cards = analytics.cards_sold + analytics.cards_expired;
+            if total_cards > 0 {
+                (analytics.cards_sold as f32 / total_cards as f32) * 100.0
+            } else {
+                0.0
+            }
+        }),
+        "".to_string(),
+        format!("⭐ Best Day Revenue:      ${}", analytics.best_day_revenue),
+        format!("📅 Recent Daily Avg:      ${:.0}", analytics.recent_daily_average()),
+        format!("🏪 Market Share (30d):    {:.1}%", app.game_data.market_share() * 100.0),
+    ];
+
+    let metrics_items: Vec<ListItem> = key_metrics
+        .iter()
+        .map(|metric| {
+            let style = if metric.contains("Net Profit") {
+                if total_profit >= 0 {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::Red)
+                }
+            } else if metric.is_empty() {
+                Style::default().fg(Color::Gray)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            
+            ListItem::new(Line::from(Span::styled(metric.clone(), style)))
+        })
+        .collect();
+
+    let metrics_list = List::new(metrics_items)
+        .block(Block::default()
+            .title("Key Metrics")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(metrics_list, main_chunks[0]);
+
+    // Right column: Performance Trends and Daily Revenue
+    let mut performance_data = vec![
+        "📊 RECENT DAILY REVENUES".to_string(),
+        "────────────────────────".to_string(),
+    ];
+
+    // Show last 7 days of revenue (or whatever we have)
+    let recent_days = analytics.daily_revenues.len().min(7);
+    let current_day = app.game_data.day;
+    
+    for (i, &revenue) in analytics.daily_revenues.iter().rev().take(recent_days).enumerate() {
+        let day_num = current_day.saturating_sub(i as u32);
+        let bar_length = if analytics.best_day_revenue > 0 {
+            ((revenue as f32 / analytics.best_day_revenue as f32) * 20.0) as usize
+        } else {
+            0
+        };
+        let bar = "█".repeat(bar_length) + &"░".repeat(20 - bar_length);
+        
+        performance_data.push(format!(
+            "Day {:2} │ ${:4} │ {}",
+            day_num, revenue, bar
+        ));
+    }
+
+    performance_data.push("".to_string());
+    performance_data.push("📈 PROFIT MARGIN TRENDS".to_string());
+    performance_data.push("───────────────────────".to_string());
+
+    // Show recent profit margins
+    let recent_margins = analytics.profit_margins.len().min(5);
+    if recent_margins > 0 {
+        for (i, &margin) in analytics.profit_margins.iter().rev().take(recent_margins).enumerate() {
+            let trend_indicator = if i > 0 && i < analytics.profit_margins.len() {
+                let prev_margin = analytics.profit_margins[analytics.profit_margins.len() - i];
+                if margin > prev_margin { "↗" } 
+                else if margin < prev_margin { "↘" } 
+                else { "→" }
+            } else {
+                "→"
+            };
+            
+            performance_data.push(format!(
+                "Sale {:2} │ {:5.1}% │ {}",
+                analytics.profit_margins.len() - i, margin, trend_indicator
+            ));
+        }
+    } else {
+        performance_data.push("No sales data available yet".to_string());
+    }
+
+    performance_data.push("".to_string());
+    performance_data.push("💸 EXPENSES & CASH FLOW".to_string());
+    performance_data.push("──────────────────────".to_string());
+    if analytics.expenses.is_empty() {
+        performance_data.push("No expenses paid yet".to_string());
+    }
+    for (category, amount) in &analytics.expenses {
+        performance_data.push(format!("{:<17} │ ${}", category.display(), amount));
+    }
+    if analytics.unpaid_expenses > 0 {
+        performance_data.push(format!("⚠️  Unpaid bills: ${}", analytics.unpaid_expenses));
+    }
+    performance_data.push(format!("Net Cash Flow (7d): ${:+}", analytics.recent_cash_flow(7)));
+    performance_data.push(format!("Net Cash Flow (30d): ${:+}", analytics.recent_cash_flow(30)));
+
+    performance_data.push("".to_string());
+    performance_data.push("🏪 MARKET SHARE".to_string());
+    performance_data.push("──────────────".to_string());
+    if analytics.market_history.is_empty() {
+        performance_data.push("No market days closed yet".to_string());
+    }
+    let recent_market: Vec<&MarketDay> = analytics.market_history.iter().rev().take(7).collect();
+    let best_share = recent_market.iter().map(|market_day| market_day.share()).fold(MARKET_SHARE_GOAL, f32::max);
+    for market_day in &recent_market {
+        let bar_length = ((market_day.share() / best_share) * 20.0) as usize;
+        let bar = "█".repeat(bar_length) + &"░".repeat(20 - bar_length);
+        performance_data.push(format!(
+            "Day {:2} │ {:5.1}% of {:3} │ {}",
+            market_day.day, market_day.share() * 100.0, market_day.total_demand(), bar
+        ));
+    }
+    if !analytics.market_history.is_empty() {
+        for retailer in RETAILERS {
+            let (share, demand) = analytics.retailer_market_share(retailer.id, MARKET_SHARE_WINDOW_DAYS);
+            performance_data.push(format!("{:<10} │ {:5.1}% of {} cards (30d)", retailer.name, share * 100.0, demand));
+        }
+        let rivals: f32 = app.game_data.competitors.iter()
+            .map(|competitor| app.game_data.competitor_share(competitor))
+            .sum();
+        performance_data.push(format!("Rivals hold {:.1}% • goal {:.0}%", rivals * 100.0, MARKET_SHARE_GOAL * 100.0));
+    }
+
+    performance_data.push("".to_string());
+    performance_data.push("🎯 STRATEGIC INSIGHTS".to_string());
+    performance_data.push("──────────────────".to_string());
+
+    // Add some strategic insights based on the data
+    if let Some(avg_revenue_per_order) = analytics.total_revenue.checked_div(analytics.orders_completed) {
+        performance_data.push(format!("Avg Revenue/Order: ${}", avg_revenue_per_order));
+    }
+    
+    if total_profit < 0 {
+        performance_data.push("⚠️  Operating at a loss".to_string());
+        performance_data.push("   Focus on higher margins".to_string());
+    } else if analytics.average_profit_margin() < 15.0 {
+        performance_data.push("⚠️  Low profit margins".to_string());
+        performance_data.push("   Seek better deals".to_string());
+    } else {
+        performance_data.push("✅ Healthy profit margins".to_string());
+    }
+
+    let performance_items: Vec<ListItem> = performance_data
+        .iter()
+        .map(|item| {
+            let style = if item.contains("REVENUES") || item.contains("TRENDS") || item.contains("CASH FLOW") || item.contains("MARKET SHARE") || item.contains("INSIGHTS") {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else if item.contains("─") {
+                Style::default().fg(Color::Gray)
+            } else if item.contains("⚠️") {
+                Style::default().fg(Color::Red)
+            } else if item.contains("✅") {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            
+            ListItem::new(Line::from(Span::styled(item.clone(), style)))
+        })
+        .collect();
+
+    let performance_list = List::new(performance_items)
+        .block(Block::default()
+            .title("Performance Analysis")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(performance_list, main_chunks[1]);
+
+    // Footer with controls
+    let footer_text = "View comprehensive business metrics and trends • Esc Back";
+    let footer = Paragraph::new(footer_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(footer, chunks[2]);
+}
+
+fn draw_forecast(f: &mut Frame, app: &App, area: Rect) {
+    let today = app.game_data.day;
+    let mut rows = vec![
+        format!("{:<10} │ Orders 7d │ Orders 14d │ Price 7d │ Price 14d │ Stock │ Suggestion", "Retailer"),
+    ];
+    for forecast in app.game_data.forecast(FORECAST_DAYS) {
+        let price_change = |day: usize| (forecast.price_multipliers[day - 1] - 1.0) * 100.0;
+        let expiring = if forecast.expiring > 0 { format!(" ({}⌛)", forecast.expiring) } else { String::new() };
+        rows.push(format!(
+            "{:<10} │ {:>9.1} │ {:>10.1} │ {:>+7.0}% │ {:>+8.0}% │ {:>5}{} │ {}",
+            retailer_name(&forecast.retailer),
+            forecast.expected_orders(7),
+            forecast.expected_orders(FORECAST_DAYS as usize),
+            price_change(7),
+            price_change(FORECAST_DAYS as usize),
+            forecast.usable_stock,
+            expiring,
+            forecast.recommendation(today)
+        ));
+    }
+
+    let items: Vec<ListItem> = rows.iter().enumerate()
+        .map(|(i, row)| {
+            let style = if i == 0 {
+                Style::default().fg(Color::Gray)
+            } else if row.contains("Stocked") {
+                Style::default().fg(Color::Green)
+            } else if row.contains("rising") {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(Span::styled(row.clone(), style)))
+        })
+        .collect();
+    let season_change = (1..=FORECAST_DAYS)
+        .find(|offset| Season::from_day(today + offset) != app.game_data.market_conditions.current_season)
+        .map(|offset| format!(", {} starts day {}", Season::from_day(today + offset).display(), today + offset))
+        .unwrap_or_default();
+    let list = List::new(items)
+        .block(Block::default()
+            .title(format!("🔮 Demand Forecast (next {} days{}) • ⌛ = expires before then", FORECAST_DAYS, season_change))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)));
+    f.render_widget(list, area);
+}
+
+fn draw_achievements_screen(f: &mut Frame, app: &App) {
+    let size = f.area();
+    
+    // Create layout: Header, Main content (left unlocked, right in-progress), Footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Main content
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    // Header with achievement stats
+    let total_achievements = app.game_data.achievements.achievements.len();
+    let unlocked_count = app.game_data.achievements.total_unlocked;
+    let total_rewards = app.game_data.achievements.calculate_total_rewards();
+    let pending_rewards = app.game_data.achievements.pending_rewards();
+    
+    let completion_percentage = if total_achievements == 0 {
+        0.0
+    } else {
+        (unlocked_count as f32 / total_achievements as f32) * 100.0
+    };
+    
+    let header_text = format!(
+        "Achievements: {}/{}    Total Rewards: ${} (${} unclaimed)    Completion: {:.1}%",
+        unlocked_count,
+        total_achievements,
+        total_rewards,
+        pending_rewards,
+        completion_percentage
+    );
+    
+    let header = Paragraph::new(header_text)
+        .block(Block::default()
+            .title("Achievement Gallery")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Green))
+        .alignment(Alignment::Center);
+    
+    f.render_widget(header, chunks[0]);
+
+    // Main content area split into two columns
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(50), // Unlocked achievements
+            Constraint::Percentage(50), // In-progress achievements
+        ])
+        .split(chunks[1]);
+
+    // Left column: Unlocked Achievements
+    let unlocked = app.game_data.achievements.get_unlocked_achievements();
+    let unlocked_items: Vec<ListItem> = if unlocked.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "No achievements unlocked yet!",
+            Style::default().fg(Color::Gray)
+        )))]
+    } else {
+        unlocked.iter().map(|achievement| {
+            let unlock_day = achievement.unlock_date.unwrap_or(0);
+            let claim_note = if app.game_data.achievements.is_reward_pending(&achievement.name) { " - unclaimed" } else { "" };
+            let lines = vec![
+                Line::from(Span::styled(
+                    format!("[*] {} (+${}{})", achievement.name, achievement.reward_cash, claim_note),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                )),
+                Line::from(Span::styled(
+                    format!("    {}", achievement.description),
+                    Style::default().fg(Color::Yellow)
+                )),
+                Line::from(Span::styled(
+                    format!("    Unlocked: Day {}", unlock_day),
+                    Style::default().fg(Color::Yellow)
+                )),
+            ];
+            
+            ListItem::new(Text::from(lines))
+        }).collect()
+    };
+
+    let unlocked_list = List::new(unlocked_items)
+        .block(Block::default()
+            .title(format!("* Unlocked ({}/{})", unlocked_count, total_achievements))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(unlocked_list, main_chunks[0]);
+
+    // Right column: In-Progress Achievements
+    let in_progress = app.game_data.achievements.get_in_progress_achievements();
+    let mut remaining_achievements: Vec<&Achievement> = app.game_data.achievements.achievements.iter()
+        .filter(|a| !a.unlocked && a.progress == 0)
+        .take(5) // Show top 5 remaining
+        .collect();
+
+    // Combine in-progress and some remaining achievements
+    let mut progress_items: Vec<ListItem> = Vec::new();
+    
+    // Add in-progress achievements first
+    for achievement in in_progress {
+        let progress_bar_length = ((achievement.progress_percentage() / 100.0 * 20.0) as usize).min(20);
+        let progress_bar = "=".repeat(progress_bar_length) + &"-".repeat(20_usize.saturating_sub(progress_bar_length));
+        
+        let lines = vec![
+            Line::from(Span::styled(
+                format!("[>] {} ({}/{})", achievement.name, achievement.progress, achievement.target),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+            )),
+            Line::from(Span::styled(
+                format!("    {}", achievement.description),
+                Style::default().fg(Color::Cyan)
+            )),
+            Line::from(Span::styled(
+                format!("    [{}] {:.1}%", progress_bar, achievement.progress_percentage()),
+                Style::default().fg(Color::Cyan)
+            )),
+        ];
+        
+        progress_items.push(ListItem::new(Text::from(lines)));
+    }
+
+    // Add some locked achievements (only if we have room for more)
+    let remaining_slots = if progress_items.len() >= 5 { 0 } else { 5 - progress_items.len() };
+    remaining_achievements.truncate(remaining_slots);
+    for achievement in remaining_achievements {
+        let lines = vec![
+            Line::from(Span::styled(
+                format!("[-] {} (Reward: ${})", achievement.name, achievement.reward_cash),
+                Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD)
+            )),
+            Line::from(Span::styled(
+                format!("    {}", achievement.description),
+                Style::default().fg(Color::Gray)
+            )),
+            Line::from(Span::styled(
+                format!("    Progress: {}/{}", achievement.progress, achievement.target),
+                Style::default().fg(Color::Gray)
+            )),
+        ];
+        
+        progress_items.push(ListItem::new(Text::from(lines)));
+    }
+
+    if progress_items.is_empty() {
+        let lines = vec![
+            Line::from(Span::styled(
+                "All achievements unlocked!",
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            )),
+            Line::from(Span::styled(
+                "Congratulations!",
+                Style::default().fg(Color::Green)
+            )),
+        ];
+        progress_items.push(ListItem::new(Text::from(lines)));
+    }
+
+    let progress_list = List::new(progress_items)
+        .block(Block::default()
+            .title("> Progress & Locked")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(progress_list, main_chunks[1]);
+
+    // Footer with controls
+    let footer_text = if pending_rewards > 0 {
+        format!("C Claim ${} in rewards • Esc Back", pending_rewards)
+    } else {
+        "View your accomplishments and track progress • Esc Back".to_string()
+    };
+    let footer = Paragraph::new(footer_text)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(footer, chunks[2]);
+}
+
+fn draw_customer_detail(f: &mut Frame, app: &App) {
+    let size = f.area();
+    let customer = app.customer_detail
+        .and_then(|(customer_id, _)| app.game_data.customers.iter().find(|customer| customer.id == customer_id));
+    let Some(customer) = customer else {
+        draw_placeholder(f, "Customer", "Customer not found");
+        return;
+    };
+
+    // Create layout: Header, Profile, Order history, Footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(9), // Profile
+            Constraint::Min(0),    // Order history
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    let tier = customer.loyalty_tier();
+    let header = Paragraph::new(format!("{} {}  ({} customer)", tier.icon(), customer.name, tier.display()))
+        .block(Block::default()
+            .title("Customer Profile")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Green))
+        .alignment(Alignment::Center);
+
+    f.render_widget(header, chunks[0]);
+
+    let sensitivity = if customer.price_sensitivity > 0.66 {
+        "High - hunts for bargains"
+    } else if customer.price_sensitivity > 0.33 {
+        "Medium"
+    } else {
+        "Low - pays for convenience"
+    };
+    let premium = tier.price_premium();
+    let profile_lines = vec![
+        Line::from(format!("Preferred retailers:  {}", customer.preferred_retailers.join(", "))),
+        Line::from(format!("Price sensitivity:    {}", sensitivity)),
+        Line::from(format!("Loyalty bonus:        +{:.0}% on offers", premium * 100.0)),
+        Line::from(format!(
+            "Partial shipments:    {} (backorders wait {} days)",
+            if customer.partial_shipments { "Yes" } else { "No - all or nothing" },
+            customer.backorder_days
+        )),
+        Line::from(format!(
+            "Orders:               {} placed, {} fulfilled, {} partial, {} expired, {} declined",
+            customer.orders_placed, customer.orders_fulfilled, customer.orders_partial, customer.orders_expired, customer.orders_declined
+        )),
+        Line::from(format!("Lifetime spend:       ${}", customer.total_spent)),
+        if customer.has_churned() {
+            Line::from(Span::styled("⚠️ Stopped ordering after too many expired orders", Style::default().fg(Color::Red)))
+        } else {
+            Line::from("")
+        },
+    ];
+    let profile = Paragraph::new(profile_lines)
+        .block(Block::default()
+            .title("Profile")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::White));
+
+    f.render_widget(profile, chunks[1]);
+
+    let mut history_content = vec![
+        "  Order # │ Day │ Outcome   │ Total │ Items".to_string(),
+        "──────────┼─────┼───────────┼───────┼──────────────────────────────".to_string(),
+    ];
+    for entry in customer.history.iter().rev() {
+        history_content.push(format!(
+            "  #{:5} │ {:3} │ {:9} │ ${:4} │ {}",
+            entry.order_id, entry.day, entry.outcome.display(), entry.total_price, entry.items
+        ));
+    }
+    let history_items: Vec<ListItem> = history_content
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let style = if i < 2 {
+                Style::default().fg(Color::Gray)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(Span::styled(line.clone(), style)))
+        })
+        .collect();
+    let history = List::new(history_items)
+        .block(Block::default()
+            .title("Order History")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)));
+
+    f.render_widget(history, chunks[2]);
+
+    let footer = Paragraph::new("B Toggle Partial Shipments  Esc Back to Orders")
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(footer, chunks[3]);
+}
+
+fn draw_game_over(f: &mut Frame, app: &App) {
+    let size = f.area();
+    let game = &app.game_data;
+    let Some(game_over) = &game.game_over else {
+        draw_placeholder(f, "Game Over", "The game is still running");
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // Outcome
+            Constraint::Min(0),    // Run summary
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    let outcome_color = if game_over.outcome.is_victory() { Color::Green } else { Color::Red };
+    let header = Paragraph::new(format!(
+        "{} on day {} ({} mode)\n{}",
+        game_over.outcome.display(), game_over.day, game.difficulty.display(), game_over.outcome.description()
+    ))
+        .block(Block::default()
+            .title("Gift Card Empire - Game Over")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(outcome_color).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center);
+    f.render_widget(header, chunks[0]);
+
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(50), // Business results
+            Constraint::Percentage(50), // Goals and achievements
+        ])
+        .split(chunks[1]);
+
+    let analytics = &game.analytics;
+    let total_profit = analytics.total_profit();
+    let results = vec![
+        format!("💼 Net Worth:             ${}", game.net_worth()),
+        format!("💵 Cash:                  ${}", game.cash),
+        format!("📦 Inventory Value:       ${} ({} cards)", game.total_inventory_value(), game.inventory_count()),
+        format!("⭐ Reputation:            {} ({})", game.reputation_stars(), game.reputation_description()),
+        "".to_string(),
+        format!("💰 Total Revenue:         ${}", analytics.total_revenue),
+        format!("💳 Total Purchases:       ${}", analytics.total_purchases),
+        format!("💸 Operating Expenses:    ${}", analytics.total_expenses()),
+        format!("🏆 Achievement Rewards:   ${}", analytics.achievement_rewards),
+        format!("📈 Net Profit:           ${:+}", total_profit),
+        format!("⭐ Best Day Revenue:      ${}", analytics.best_day_revenue),
+        "".to_string(),
+        format!("📋 Orders Completed:      {}", analytics.orders_completed),
+        format!("⏰ Orders Expired:        {}", analytics.orders_expired),
+        format!("🙅 Orders Declined:       {}", analytics.orders_declined),
+        format!("🎯 Cards Sold:            {}", analytics.cards_sold),
+        format!("💀 Cards Expired:         {}", analytics.cards_expired),
+    ];
+    let result_items: Vec<ListItem> = results.iter()
+        .map(|line| {
+            let style = if line.contains("Net Profit") {
+                Style::default().fg(if total_profit >= 0 { Color::Green } else { Color::Red })
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(Span::styled(line.clone(), style)))
+        })
+        .collect();
+    let results_list = List::new(result_items)
+        .block(Block::default()
+            .title("Run Summary")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)));
+    f.render_widget(results_list, main_chunks[0]);
+
+    let mut milestones = vec![
+        "🎯 GOALS".to_string(),
+        "────────────────────────".to_string(),
+    ];
+    for goal in GameGoal::ALL {
+        let active = if game.active_goals.contains(&goal) { "" } else { " (not active)" };
+        milestones.push(match game.goal_reached(goal) {
+            Some(day) => format!("✅ {}{}: day {}", goal.display(), active, day),
+            None => format!("⬜ {}{}: {}", goal.display(), active, goal.progress(game)),
+        });
+    }
+
+    let unlocked = game.achievements.get_unlocked_achievements();
+    milestones.push("".to_string());
+    milestones.push(format!("🏆 ACHIEVEMENTS ({}/{})", unlocked.len(), game.achievements.achievements.len()));
+    milestones.push("────────────────────────".to_string());
+    if unlocked.is_empty() {
+        milestones.push("None unlocked".to_string());
+    }
+    for achievement in unlocked {
+        milestones.push(format!("🏅 {} (day {})", achievement.name, achievement.unlock_date.unwrap_or(0)));
+    }
+    let milestone_items: Vec<ListItem> = milestones.iter()
+        .map(|line| {
+            let style = if line.contains("GOALS") || line.contains("ACHIEVEMENTS") {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else if line.starts_with('✅') {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(Span::styled(line.clone(), style)))
+        })
+        .collect();
+    let milestones_list = List::new(milestone_items)
+        .block(Block::default()
+            .title("Goals & Achievements")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)));
+    f.render_widget(milestones_list, main_chunks[1]);
+
+    let footer = Paragraph::new("Enter/Esc Main Menu • Q Quit")
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+    f.render_widget(footer, chunks[2]);
+}
+
+fn draw_competitors(f: &mut Frame, app: &App) {
+    let size = f.area();
+    let game = &app.game_data;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Standings
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    let price_war = if game.price_war_active() { "    ⚔️ PRICE WAR" } else { "" };
+    let header = Paragraph::new(format!(
+        "Our Market Share: {:.1}%    Orders Lost to Rivals: {}    Rivals: {}{}",
+        game.market_share() * 100.0, game.competitor_orders_won(), game.competitors.len(), price_war
+    ))
+        .block(Block::default()
+            .title("Gift Card Empire - Competitors")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::Cyan))
+        .alignment(Alignment::Center);
+    f.render_widget(header, chunks[0]);
+
+    let mut rows = vec![
+        format!("{:<16} {:<11} {:<6} {:>8} {:>6} {:>6} {:>6}  {}", "Company", "Strategy", "Rep", "Cash", "Stock", "Won", "Share", "Status"),
+        format!(
+            "{:<16} {:<11} {:<6} {:>8} {:>6} {:>6} {:>5.1}%  {}",
+            "You", "-", game.reputation_stars(), format!("${}", game.cash), game.inventory_count(),
+            game.analytics.orders_completed, game.market_share() * 100.0, ""
+        ),
+    ];
+    for competitor in &game.competitors {
+        let status = if competitor.in_price_war() {
+            format!("⚔️ Price war ({}d)", competitor.price_war_days)
+        } else if competitor.cash < COMPETITOR_DAILY_OVERHEAD * 7 && competitor.card_count() == 0 {
+            "Struggling".to_string()
+        } else {
+            "".to_string()
+        };
+        let stars = "★".repeat(competitor.reputation as usize) + &"☆".repeat(5 - competitor.reputation as usize);
+        rows.push(format!(
+            "{:<16} {:<11} {:<6} {:>8} {:>6} {:>6} {:>5.1}%  {}",
+            competitor.name, competitor.strategy.display(), stars, format!("${}", competitor.cash),
+            competitor.card_count(), competitor.orders_won, game.competitor_share(competitor) * 100.0, status
+        ));
+    }
+
+    let items: Vec<ListItem> = rows.iter().enumerate()
+        .map(|(i, row)| {
+            let style = match i {
+                0 => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                1 => Style::default().fg(Color::Green),
+                _ => Style::default().fg(Color::White),
+            };
+            ListItem::new(Line::from(Span::styled(row.clone(), style)))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default()
+            .title("Standings")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)));
+    f.render_widget(list, chunks[1]);
+
+    let footer = Paragraph::new("Rivals buy from the same market and can win orders before you see them • Esc Back")
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+    f.render_widget(footer, chunks[2]);
+}
+
+fn draw_settings(f: &mut Frame, app: &App) {
+    let size = f.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Settings
+            Constraint::Length(3), // Footer
+        ])
+        .split(size);
+
+    let on_off = |enabled: bool| if enabled { "On" } else { "Off" };
+    let mut settings = vec![
+        format!("Auto-claim achievement rewards: {}", on_off(app.game_data.auto_claim_rewards)),
+    ];
+    for goal in GameGoal::ALL {
+        let status = match app.game_data.goal_reached(goal) {
+            Some(day) => format!("reached day {}", day),
+            None => goal.progress(&app.game_data),
+        };
+        settings.push(format!(
+            "Goal: {:<28} {:<4} ({})",
+            goal.display(), on_off(app.game_data.active_goals.contains(&goal)), status
+        ));
+    }
+    if app.game_data.active_goals.is_empty() {
+        settings.push("".to_string());
+        settings.push("No goals active: play continues until the business fails or you quit".to_string());
+    }
+    let items: Vec<ListItem> = settings.iter().enumerate()
+        .map(|(i, setting)| {
+            let style = if i == app.selected_menu_item {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(Span::styled(setting.clone(), style)))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .title("Gift Card Empire - Settings")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)));
+    f.render_widget(list, chunks[0]);
+
+    let footer = Paragraph::new("Enter Toggle • Win by reaching every active goal • Esc Back")
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center);
+    f.render_widget(footer, chunks[1]);
+}
+
+fn draw_placeholder(f: &mut Frame, title: &str, description: &str) {
+    let size = f.area();
+
+    let content = format!("{}\n\n{}\n\nPress Esc to return to dashboard", title, description);
+    let placeholder = Paragraph::new(content)
+        .block(Block::default()
+            .title(format!("Gift Card Empire - {}", title))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White)))
+        .style(Style::default().fg(Color::White))
+        .alignment(Alignment::Center);
+
+    f.render_widget(placeholder, size);
+}
+
+fn draw_random_event(f: &mut Frame, app: &App) {
+    let size = f.area();
+    
+    if let Some(event) = &app.game_data.random_events.active_event {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(3),  // Header
+                Constraint::Min(6),     // Event content
+                Constraint::Length(3),  // Instructions
+            ])
+            .split(size);
+        
+        // Header
+        let header = Paragraph::new("🎲 Random Event - Choose Your Response")
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Yellow)))
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        f.render_widget(header, chunks[0]);
+        
+        // Event content
+        let event_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),  // Title and description
+                Constraint::Min(2),     // Choices
+            ])
+            .split(chunks[1]);
+        
+        // Event title and description
+        let event_content = format!("{}\n\n{}", event.title, event.description);
+        let event_text = Paragraph::new(event_content)
+            .block(Block::default()
+                .title("Event Details")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::White)))
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: true });
+        f.render_widget(event_text, event_chunks[0]);
+        
+        // Choices
+        let choice_items: Vec<ListItem> = event.get_choices().iter().enumerate()
+            .map(|(i, choice)| {
+                let style = if i == app.selected_menu_item {
+                    Style::default().fg(Color::Black).bg(Color::Yellow)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                ListItem::new(choice.to_string()).style(style)
+            })
+            .collect();
+        
+        let choices_list = List::new(choice_items)
+            .block(Block::default()
+                .title("Your Choices")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::White)))
+            .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+        
+        f.render_widget(choices_list, event_chunks[1]);
+        
+        // Instructions
+        let instructions = Paragraph::new("↑/↓: Navigate  Enter: Select Choice  Time remaining: ⏰")
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Gray)))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(instructions, chunks[2]);
+        
+    } else {
+        // No active event - this shouldn't happen but handle gracefully
+        let content = "No active random event.\n\nReturning to dashboard...";
+        let placeholder = Paragraph::new(content)
+            .block(Block::default()
+                .title("Random Events")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::White)))
+            .style(Style::default().fg(Color::White))
+            .alignment(Alignment::Center);
+        f.render_widget(placeholder, size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A seeded game with no open orders and no events due for a while, so days play out predictably
+    fn quiet_game(seed: u64) -> GameData {
+        let mut game = GameData::with_seed(seed);
+        game.customer_orders.clear();
+        game.random_events.next_event_in_days = 100;
+        game.market_conditions.next_event_in_days = 100;
+        game
+    }
+
+    #[test]
+    fn test_game_data_initialization() {
+        let game_data = GameData::new();
+        
+        assert_eq!(game_data.cash, 5000);
+        assert_eq!(game_data.reputation, 3);
+        assert_eq!(game_data.day, 1);
+        assert_eq!(game_data.hour, 9);
+        assert_eq!(game_data.minute, 0);
+        assert!(!game_data.inventory.is_empty());
+        assert!(!game_data.customer_orders.is_empty());
+    }
+
+    #[test]
+    fn test_purchase_mechanics() {
+        let mut game_data = GameData::new();
+        let initial_cash = game_data.cash;
+        let initial_inventory_count = game_data.inventory.len();
+        
+        // Test successful purchase
+        assert!(game_data.can_afford(100));
+        assert!(game_data.spend_money(100));
+        assert_eq!(game_data.cash, initial_cash - 100);
+        
+        // Test insufficient funds
+        assert!(!game_data.can_afford(10000));
+        assert!(!game_data.spend_money(10000));
+        assert_eq!(game_data.cash, initial_cash - 100); // Should not change
+        
+        // Test inventory addition
+        let card = GiftCard::new("TestCard", 25, 20, 30);
+        game_data.add_to_inventory(card, 2);
+        assert_eq!(game_data.inventory.len(), initial_inventory_count + 1);
+    }
+
+    #[test]
+    fn test_time_progression() {
+        let mut game_data = GameData::new();
+        let initial_day = game_data.day;
+        let initial_hour = game_data.hour;
+        let initial_minute = game_data.minute;
+        
+        // Test minute advancement
+        game_data.advance_time(30);
+        assert_eq!(game_data.minute, initial_minute + 30);
+        
+        // Test hour rollover
+        game_data.advance_time(35); // Should go to next hour
+        assert_eq!(game_data.hour, initial_hour + 1);
+        assert_eq!(game_data.minute, 5); // 30 + 35 = 65, 65 % 60 = 5
+        
+        // Test day advancement (would need to advance many hours)
+        game_data.hour = 23;
+        game_data.minute = 50;
+        game_data.advance_time(20); // Should trigger new day
+        assert_eq!(game_data.day, initial_day + 1);
+        assert_eq!(game_data.hour, 0);
+        assert_eq!(game_data.minute, 10);
+    }
+
+    #[test]
+    fn test_customer_order_system() {
+        let mut game_data = GameData::new();
+        let initial_order_count = game_data.customer_orders.len();
+        
+        // Test order generation
+        game_data.generate_random_order();
+        assert_eq!(game_data.customer_orders.len(), initial_order_count + 1);
+        
+        // Test order properties
+        if let Some(order) = game_data.customer_orders.back() {
+            assert!(order.id >= 1000);
+            assert!(!order.customer_name.is_empty());
+            assert!(!order.requested_cards.is_empty());
+            for line in &order.requested_cards {
+                assert!(!line.retailer.is_empty());
+                assert!(line.denomination > 0);
+                assert!(line.quantity > 0);
+                assert!(line.price_per_card > 0);
+            }
+            assert!(order.deadline_days > 0);
+        }
+    }
+
+    #[test]
+    fn test_gift_card_pricing() {
+        let amazon_card = GiftCard::new("Amazon", 25, 20, 30);
+        assert_eq!(amazon_card.market_value(), 32); // 25 * 1.30 = 32.5 -> 32
+        assert_eq!(amazon_card.potential_profit(), 12); // 32 - 20 = 12
+        
+        let starbucks_card = GiftCard::new("Starbucks", 10, 8, 60);
+        assert_eq!(starbucks_card.market_value(), 12); // 10 * 1.25 = 12.5 -> 12
+        assert_eq!(starbucks_card.potential_profit(), 4); // 12 - 8 = 4
+        
+        // Test expiration detection
+        let expiring_card = GiftCard::new("Target", 50, 42, 10);
+        assert!(expiring_card.is_expiring_soon(1)); // <= 15 days
+        
+        let fresh_card = GiftCard::new("iTunes", 15, 12, 30);
+        assert!(!fresh_card.is_expiring_soon(1)); // > 15 days
+
+        // Aging is computed from the absolute expiration day, not counted down
+        assert_eq!(fresh_card.days_until_expiration(12), 18);
+        assert!(!fresh_card.is_expired(29));
+        assert!(fresh_card.is_expired(30));
+    }
+
+    #[test] 
+    fn test_app_initialization() {
+        let app = App::new(None);
+        
+        assert!(matches!(app.screen, Screen::MainMenu));
+        assert_eq!(app.selected_menu_item, 0);
+        assert!(!app.should_quit);
+        assert!(!app.paused);
+        assert_eq!(app.game_data.cash, 5000);
+    }
+
+    #[test]
+    fn test_order_fulfillment() {
+        let mut game_data = GameData::new();
+        let initial_cash = game_data.cash;
+        
+        // Find existing Amazon inventory to know the starting amount
+        let initial_amazon_quantity = game_data.inventory.iter()
+            .find(|item| item.card.retailer == "Amazon" && item.card.denomination == 25)
+            .map(|item| item.quantity)
+            .unwrap_or(0);
+        
+        // Create a test order
+        let order = CustomerOrder::new(
+            9999,
+            "TestCustomer",
+            "Amazon", 
+            25,
+            2,
+            30, // $30 per card
+            3,
+            OrderPriority::High
+        );
+        
+        game_data.customer_orders.push_back(order.clone());
+        let order_count = game_data.customer_orders.len();
+        
+        // Test fulfillment capability (should work with sample inventory)
+        assert!(game_data.can_fulfill_order(&order));
+        
+        // Fulfill the order
+        assert!(game_data.fulfill_order(order_count - 1));
+        
+        // Verify results
+        assert_eq!(game_data.customer_orders.len(), order_count - 1); // Order removed
+        assert_eq!(game_data.cash, initial_cash + 60); // 2 cards * $30 = $60
+        
+        // Check inventory was reduced by 2
+        let final_amazon_quantity = game_data.inventory.iter()
+            .find(|item| item.card.retailer == "Amazon" && item.card.denomination == 25)
+            .map(|item| item.quantity)
+            .unwrap_or(0);
+        assert_eq!(final_amazon_quantity, initial_amazon_quantity - 2);
+    }
+
+    #[test]
+    fn test_order_fulfillment_failure() {
+        let mut game_data = GameData::new();
+        
+        // Create an order we can't fulfill
+        let order = CustomerOrder::new(
+            9999,
+            "TestCustomer", 
+            "NonExistent",
+            100,
+            5,
+            200,
+            3,
+            OrderPriority::High
+        );
+        
+        game_data.customer_orders.push_back(order.clone());
+        let order_count = game_data.customer_orders.len();
+        let initial_cash = game_data.cash;
+        
+        // Test that we can't fulfill it
+        assert!(!game_data.can_fulfill_order(&order));
+        
+        // Attempt fulfillment should fail
+        assert!(!game_data.fulfill_order(order_count - 1));
+        
+        // Order should still be there, cash unchanged
+        assert_eq!(game_data.customer_orders.len(), order_count);
+        assert_eq!(game_data.cash, initial_cash);
+    }
+
+    #[test]
+    fn test_reputation_system() {
+        let mut game_data = GameData::new();
+        let initial_reputation = game_data.reputation;
+        
+        // Test reputation improvement
+        game_data.improve_reputation("order_fulfilled");
+        assert_eq!(game_data.reputation, initial_reputation + 1);
+        
+        // Test reputation decrease
+        game_data.decrease_reputation("order_expired");
+        assert_eq!(game_data.reputation, initial_reputation);
+        
+        // Test reputation bounds (can't go above 5)
+        game_data.reputation = 5;
+        game_data.improve_reputation("order_fulfilled");
+        assert_eq!(game_data.reputation, 5);
+        
+        // Test reputation bounds (can't go below 1)
+        game_data.reputation = 1;
+        game_data.decrease_reputation("order_expired");
+        assert_eq!(game_data.reputation, 1);
+        
+        // Test reputation descriptions
+        game_data.reputation = 5;
+        assert_eq!(game_data.reputation_description(), "Legendary");
+        game_data.reputation = 3;
+        assert_eq!(game_data.reputation_description(), "Good");
+        game_data.reputation = 1;
+        assert_eq!(game_data.reputation_description(), "Poor");
+    }
+
+    #[test]
+    fn test_reputation_affects_pricing() {
+        let mut game_data = GameData::new();
+        
+        // Test different reputation levels affect order generation
+        // We'll test the pricing logic by examining the bonus calculation
+        
+        // High reputation (5 stars)
+        game_data.reputation = 5;
+        let denomination = 25;
+        let bonus_5_star = denomination / 3; // Should be 8
+        assert_eq!(bonus_5_star, 8);
+        
+        // Medium reputation (3 stars)  
+        game_data.reputation = 3;
+        let bonus_3_star = denomination / 5; // Should be 5
+        assert_eq!(bonus_3_star, 5);
+        
+        // Low reputation (1 star)
+        game_data.reputation = 1;
+        let bonus_1_star = 0; // Should be 0
+        assert_eq!(bonus_1_star, 0);
+    }
+
+    #[test]
+    fn test_analytics_tracking() {
+        let mut game_data = GameData::new();
+        let initial_purchases = game_data.analytics.total_purchases;
+        let initial_revenue = game_data.analytics.total_revenue;
+        
+        // Test purchase tracking
+        game_data.analytics.record_purchase(100);
+        assert_eq!(game_data.analytics.total_purchases, initial_purchases + 100);
+        
+        // Test sale tracking
+        game_data.analytics.record_sale(150, 100, 2);
+        assert_eq!(game_data.analytics.total_revenue, initial_revenue + 150);
+        assert_eq!(game_data.analytics.orders_completed, 1);
+        assert_eq!(game_data.analytics.cards_sold, 2);
+        
+        // Test profit calculation
+        let total_profit = game_data.analytics.total_profit();
+        assert_eq!(total_profit, 150i32 - (initial_purchases + 100) as i32);
+        
+        // Test profit margin calculation
+        let avg_margin = game_data.analytics.average_profit_margin();
+        assert!(avg_margin > 0.0);
+        assert!(avg_margin < 100.0);
+        
+        // Test expired order tracking
+        game_data.analytics.record_expired_order();
+        assert_eq!(game_data.analytics.orders_expired, 1);
+        
+        // Test expired cards tracking
+        game_data.analytics.record_expired_cards(3);
+        assert_eq!(game_data.analytics.cards_expired, 3);
+        
+        // Test daily revenue tracking
+        let initial_days = game_data.analytics.daily_revenues.len();
+        game_data.analytics.start_new_day();
+        assert_eq!(game_data.analytics.daily_revenues.len(), initial_days + 1);
+    }
 
-    // Create table header and rows
-    let mut table_content = vec![
-        "Retailer    │ Value │ Cost │ Stock │ Profit │ Trend".to_string(),
-        "────────────┼───────┼──────┼───────┼────────┼──────".to_string(),
-    ];
+    #[test]
+    fn test_save_load_functionality() {
+        use std::fs;
+        
+        let test_filename = "test_save.json";
+        
+        // Clean up any existing test file
+        let _ = fs::remove_file(test_filename);
+        
+        // Create test game data
+        let mut original_game_data = GameData::new();
+        original_game_data.cash = 12345;
+        original_game_data.reputation = 4;
+        original_game_data.day = 42;
+        original_game_data.hour = 15;
+        original_game_data.minute = 30;
+        original_game_data.recent_activities.push("Test activity".to_string());
+        
+        // Test save
+        let save_result = original_game_data.save_game(test_filename);
+        assert!(save_result.is_ok());
+        assert!(GameData::save_file_exists(test_filename));
+        
+        // Test load
+        let load_result = GameData::load_game(test_filename);
+        assert!(load_result.is_ok());
+        
+        let loaded_game_data = load_result.unwrap();
+        
+        // Verify data integrity
+        assert_eq!(loaded_game_data.cash, 12345);
+        assert_eq!(loaded_game_data.reputation, 4);
+        assert_eq!(loaded_game_data.day, 42);
+        assert_eq!(loaded_game_data.hour, 15);
+        assert_eq!(loaded_game_data.minute, 30);
+        assert!(loaded_game_data.recent_activities.contains(&"Test activity".to_string()));
+        
+        // Clean up test file
+        let _ = fs::remove_file(test_filename);
+        assert!(!GameData::save_file_exists(test_filename));
+    }
 
-    for (i, (retailer, value, cost, stock, trend)) in market_items.iter().enumerate() {
-        let profit = value - cost;
-        let style_char = if i == app.selected_menu_item { "►" } else { " " };
+    #[test]
+    fn test_seasonal_market_system() {
+        let mut game_data = GameData::new();
         
-        table_content.push(format!(
-            "{} {:10} │  ${:2} │ ${:2} │  {:2}+  │ +${:2}   │  {}",
-            style_char, retailer, value, cost, stock, profit, trend
-        ));
+        // Test season progression
+        assert!(matches!(game_data.market_conditions.current_season, Season::Spring));
+        
+        // Test season changes
+        game_data.market_conditions.update_season(100); // Should be Summer
+        assert!(matches!(game_data.market_conditions.current_season, Season::Summer));
+        
+        game_data.market_conditions.update_season(200); // Should be Fall
+        assert!(matches!(game_data.market_conditions.current_season, Season::Fall));
+        
+        game_data.market_conditions.update_season(300); // Should be Winter
+        assert!(matches!(game_data.market_conditions.current_season, Season::Winter));
+        
+        // Test price multipliers
+        let amazon_multiplier = game_data.market_conditions.get_price_multiplier("Amazon");
+        assert!(amazon_multiplier > 1.0); // Winter should boost Amazon
+        
+        let starbucks_multiplier = game_data.market_conditions.get_price_multiplier("Starbucks"); 
+        assert!(starbucks_multiplier > 1.0); // Winter should boost Starbucks
+        
+        // Test demand multipliers
+        let demand_multiplier = game_data.market_conditions.get_demand_multiplier("Amazon");
+        assert!(demand_multiplier > 1.0); // Winter should increase demand
+        
+        // Test market event creation
+        let initial_events = game_data.market_conditions.active_events.len();
+        let event_context = game_data.event_context();
+        game_data.market_conditions.generate_random_event(&game_data.event_catalog, &event_context, &mut game_data.rng, &mut game_data.recent_activities);
+        assert_eq!(game_data.market_conditions.active_events.len(), initial_events + 1);
+        
+        // Test event affects pricing
+        let event = &game_data.market_conditions.active_events[0];
+        if let Some(retailer) = &event.retailer_affected {
+            let multiplier = game_data.market_conditions.get_price_multiplier(retailer);
+            // Should be different from base price due to event
+            assert_ne!(multiplier, 1.5); // 1.5 is winter Amazon base
+        }
     }
 
-    let table_items: Vec<ListItem> = table_content
-        .iter()
-        .enumerate()
-        .map(|(i, line)| {
-            let style = if i >= 2 && (i - 2) == app.selected_menu_item {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-            } else if i < 2 {
-                Style::default().fg(Color::Gray)
-            } else {
-                Style::default().fg(Color::White)
-            };
-            
-            ListItem::new(Line::from(Span::styled(line.clone(), style)))
-        })
-        .collect();
+    #[test]
+    fn test_achievement_system() {
+        let mut game_data = GameData::new();
+        let initial_achievements = game_data.achievements.total_unlocked;
+        
+        // Test cash achievements
+        game_data.cash = 10000;
+        game_data.achievements.check_cash_achievements(game_data.cash, game_data.day, &mut game_data.recent_activities);
+        assert!(game_data.achievements.total_unlocked > initial_achievements);
+        
+        // Test order achievements
+        game_data.analytics.orders_completed = 1;
+        game_data.achievements.check_order_achievements(game_data.analytics.orders_completed, game_data.reputation, game_data.day, &mut game_data.recent_activities);
+        
+        // Test reputation achievement
+        game_data.reputation = 5;
+        game_data.achievements.check_order_achievements(0, game_data.reputation, game_data.day, &mut game_data.recent_activities);
+        
+        // Check if any achievement unlocked for reputation
+        let legendary_unlocked = game_data.achievements.achievements.iter()
+            .find(|a| a.achievement_type == AchievementType::LegendaryStatus)
+            .map(|a| a.unlocked)
+            .unwrap_or(false);
+        assert!(legendary_unlocked);
+        
+        // Test inventory achievements
+        let total_cards: u32 = game_data.inventory.iter().map(|item| item.quantity).sum();
+        assert!(total_cards > 0); // Should have sample inventory
+        
+        // Test progress tracking
+        let in_progress = game_data.achievements.get_in_progress_achievements();
+        assert!(!in_progress.is_empty()); // Should have some progress
+        
+        // Test unlocked achievements
+        let unlocked = game_data.achievements.get_unlocked_achievements();
+        assert!(!unlocked.is_empty()); // Should have unlocked some
+        
+        // Test total rewards calculation
+        let total_rewards = game_data.achievements.calculate_total_rewards();
+        assert!(total_rewards > 0); // Should have earned some rewards
+    }
 
-    let market_list = List::new(table_items)
-        .block(Block::default()
-            .title("Available Cards")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::White));
+    #[test]
+    fn test_seeded_rng_reproducibility() {
+        // Same seed produces the same run
+        let mut game_a = GameData::with_seed(42);
+        let mut game_b = GameData::with_seed(42);
+        for _ in 0..5 {
+            game_a.generate_random_order();
+            game_b.generate_random_order();
+        }
+        for (a, b) in game_a.customer_orders.iter().zip(game_b.customer_orders.iter()) {
+            assert_eq!(a.customer_name, b.customer_name);
+            assert_eq!(a.requested_cards, b.requested_cards);
+            assert_eq!(a.deadline_days, b.deadline_days);
+        }
 
-    f.render_widget(market_list, chunks[1]);
+        // Rng state is saved, so a reloaded game continues identically
+        let json = serde_json::to_string(&game_a).unwrap();
+        let mut loaded: GameData = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.rng.seed, 42);
+        assert_eq!(loaded.rng.next_u64(), game_a.rng.next_u64());
+
+        // Range bounds are inclusive and respected
+        let mut rng = GameRng::new(7);
+        for _ in 0..1000 {
+            let value = rng.range(30, 89);
+            assert!((30..=89).contains(&value));
+        }
+    }
 
-    // Footer with controls
-    let footer_text = "↑↓ Select  Enter Purchase  Esc Back";
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Gray))
-        .alignment(Alignment::Center);
+    #[test]
+    fn test_seed_argument_parsing() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter();
+        assert_eq!(parse_seed_arg(args(&[])), Ok(None));
+        assert_eq!(parse_seed_arg(args(&["--seed", "1234"])), Ok(Some(1234)));
+        assert_eq!(parse_seed_arg(args(&["--seed=99"])), Ok(Some(99)));
+        assert!(parse_seed_arg(args(&["--seed"])).is_err());
+        assert!(parse_seed_arg(args(&["--seed", "abc"])).is_err());
+    }
 
-    f.render_widget(footer, chunks[2]);
-}
+    #[test]
+    fn test_difficulty_scaling() {
+        let easy = GameData::with_options(Difficulty::Easy, GameRng::new(1));
+        let normal = GameData::with_options(Difficulty::Normal, GameRng::new(1));
+        let hard = GameData::with_options(Difficulty::Hard, GameRng::new(1));
+
+        // Starting capital
+        assert_eq!(easy.cash, 10000);
+        assert_eq!(normal.cash, 5000);
+        assert_eq!(hard.cash, 2500);
+
+        // Wholesale costs scale with difficulty
+        let amazon = &normal.market.listings[0];
+        assert!(easy.wholesale_price(amazon) < normal.wholesale_price(amazon));
+        assert!(hard.wholesale_price(amazon) > normal.wholesale_price(amazon));
+
+        // Order deadlines stay within the difficulty range
+        let mut hard = hard;
+        for _ in 0..20 {
+            hard.generate_random_order();
+        }
+        let (min_deadline, max_deadline) = Difficulty::Hard.order_deadline_range();
+        assert!(hard.customer_orders.iter().all(|o| o.deadline_days >= min_deadline && o.deadline_days <= max_deadline));
 
-fn draw_orders(f: &mut Frame, app: &App) {
-    let size = f.area();
-    
-    // Create layout: Header, Orders list, Footer
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Orders content
-            Constraint::Length(3), // Footer
-        ])
-        .split(size);
+        // Customer forgiveness
+        assert_eq!(Difficulty::Easy.expired_order_penalty(1), 0);
+        assert_eq!(Difficulty::Normal.expired_order_penalty(1), 1);
+        assert_eq!(Difficulty::Hard.expired_order_penalty(1), 2);
 
-    // Header
-    let header_text = format!("Active Orders: {}", app.game_data.customer_orders.len());
-    let header = Paragraph::new(header_text)
-        .block(Block::default()
-            .title("Customer Orders")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Green))
-        .alignment(Alignment::Center);
-    
-    f.render_widget(header, chunks[0]);
+        // Descriptions quote the numbers the difficulty actually uses
+        assert!(Difficulty::Easy.face_value_margin() > Difficulty::Normal.face_value_margin());
+        assert!(Difficulty::Normal.face_value_margin() > Difficulty::Hard.face_value_margin());
+        let description = Difficulty::Hard.description();
+        assert!(description.starts_with("$2500 capital"));
+        assert!(description.contains(&format!("~{:.0}% margin", Difficulty::Hard.face_value_margin() * 100.0)));
+        assert!(description.contains("15-45 day expirations"));
+    }
 
-    // Orders list
-    if app.game_data.customer_orders.is_empty() {
-        let no_orders = Paragraph::new("No customer orders available\n\nNew orders will appear over time")
-            .block(Block::default()
-                .title("Orders")
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White)))
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center);
-        
-        f.render_widget(no_orders, chunks[1]);
-    } else {
-        // Create table header and rows
-        let mut table_content = vec![
-            "   Order #  │ Customer │ Item           │ Qty │ Offer │ Days │ Priority".to_string(),
-            "────────────┼──────────┼────────────────┼─────┼───────┼──────┼────────".to_string(),
-        ];
+    #[test]
+    fn test_new_game_setup_flow() {
+        let mut app = App::new(Some(5));
+        app.select_menu_item(); // New Game
+        assert!(matches!(app.screen, Screen::NewGameSetup));
 
-        for (i, order) in app.game_data.customer_orders.iter().enumerate() {
-            let style_char = if i == app.selected_menu_item { "►" } else { " " };
-            let priority_color = match order.priority {
-                OrderPriority::High => "🔴",
-                OrderPriority::Medium => "🟡", 
-                OrderPriority::Low => "🟢",
-            };
-            
-            // Check if order can be fulfilled
-            let fulfillment_indicator = if app.game_data.can_fulfill_order(order) {
-                "✅"
-            } else {
-                "❌"
-            };
-            
-            table_content.push(format!(
-                "{} {} #{:4} │ {:8} │ {} ${:2}      │  {:2} │ ${:3}  │  {:2}  │ {} {}",
-                style_char,
-                fulfillment_indicator,
-                order.id,
-                order.customer_name,
-                order.retailer,
-                order.denomination,
-                order.quantity,
-                order.offered_price_per_card,
-                order.deadline_days,
-                priority_color,
-                order.priority.display()
-            ));
-        }
+        app.next_menu_item();
+        app.next_menu_item(); // Hard
+        app.select_menu_item();
+        assert!(matches!(app.screen, Screen::Dashboard));
+        assert_eq!(app.game_data.difficulty, Difficulty::Hard);
+        assert_eq!(app.game_data.cash, 2500);
+        assert_eq!(app.game_data.rng.seed, 5);
+        assert_eq!(app.selected_menu_item, 0); // Dashboard opens on Market, not the picker's row
+    }
 
-        let table_items: Vec<ListItem> = table_content
-            .iter()
-            .enumerate()
-            .map(|(i, line)| {
-                let style = if i >= 2 && (i - 2) == app.selected_menu_item {
-                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-                } else if i < 2 {
-                    Style::default().fg(Color::Gray)
-                } else {
-                    Style::default().fg(Color::White)
-                };
-                
-                ListItem::new(Line::from(Span::styled(line.clone(), style)))
-            })
-            .collect();
+    #[test]
+    fn test_random_event_outcomes_applied() {
+        let mut game_data = GameData::with_seed(3);
+        game_data.cash = 1000;
+        game_data.reputation = 3;
 
-        let orders_list = List::new(table_items)
-            .block(Block::default()
-                .title("Available Orders")
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White)))
-            .style(Style::default().fg(Color::White));
+        // Auto events keep their cash and reputation impact
+        let catalog = EventCatalog::builtin();
+        let catalog_event = |title: &str| catalog.random_events.iter()
+            .find(|e| e.title == title)
+            .map(|e| e.instantiate())
+            .unwrap();
+
+        let mut loyal = catalog_event("Loyal Customer Returns");
+        game_data.apply_event_outcome(loyal.apply_choice(0));
+        assert_eq!(game_data.cash, 3000);
+        assert_eq!(game_data.reputation, 4);
+
+        let mut theft = catalog_event("Security Incident");
+        game_data.apply_event_outcome(theft.apply_choice(0));
+        assert_eq!(game_data.cash, 2700);
+        assert_eq!(game_data.reputation, 3);
 
-        f.render_widget(orders_list, chunks[1]);
+        // Auto events get the modifiers their descriptions promise
+        let mut discount = catalog_event("Supplier Discount");
+        game_data.apply_event_outcome(discount.apply_choice(0));
+        assert!(game_data.random_events.get_total_price_multiplier() < 1.0);
+
+        // Choice inventory impact removes cards
+        let amazon_before: u32 = game_data.inventory.iter().filter(|i| i.card.retailer == "Amazon").map(|i| i.quantity).sum();
+        let mut charity = catalog_event("Charity Fundraiser");
+        game_data.apply_event_outcome(charity.apply_choice(1));
+        let amazon_after: u32 = game_data.inventory.iter().filter(|i| i.card.retailer == "Amazon").map(|i| i.quantity).sum();
+        assert_eq!(amazon_after, amazon_before - 2);
+        assert_eq!(game_data.reputation, 4);
+
+        // Forced choices on an expired deadline are applied too
+        game_data.random_events.active_event = Some(catalog_event("Charity Fundraiser"));
+        game_data.random_events.player_choice_pending = true;
+        game_data.random_events.choice_deadline = game_data.day;
+        let cash_before = game_data.cash;
+        let event_context = game_data.event_context();
+        let outcomes = game_data.random_events.process_daily_events(&catalog, &event_context, &mut game_data.rng, &mut game_data.recent_activities);
+        assert_eq!(outcomes.len(), 1);
+        for outcome in outcomes {
+            game_data.apply_event_outcome(outcome);
+        }
+        assert_eq!(game_data.cash, cash_before - 500);
     }
 
-    // Footer with controls
-    let footer_text = "↑↓ Select  Enter Fulfill Order  Esc Back";
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Gray))
-        .alignment(Alignment::Center);
+    #[test]
+    fn test_event_catalog_loading() {
+        // Built-in catalog carries every original event
+        let builtin = EventCatalog::builtin();
+        assert_eq!(builtin.market_events.len(), 8);
+        assert_eq!(builtin.random_events.len(), 15);
+
+        // Designers can add events with weights, preconditions and effects
+        let json = r#"{
+            "random_events": [
+                {
+                    "title": "Winter Rush",
+                    "description": "Holiday shoppers line up",
+                    "weight": 5,
+                    "conditions": { "seasons": ["Winter"], "min_reputation": 4 },
+                    "effect": {
+                        "cash": 250,
+                        "modifiers": [{ "name": "Holiday Crowd", "demand_multiplier": 1.5, "duration_days": 2 }]
+                    }
+                }
+            ]
+        }"#;
+        let catalog = EventCatalog::from_json(json).unwrap();
+        let mut rng = GameRng::new(1);
+        let mut context = EventContext {
+            day: 300,
+            season: Season::Winter,
+            reputation: 4,
+            cash: 0,
+            retailers_in_stock: Vec::new(),
+        };
+        let mut event = catalog.pick_random_event(&context, &mut rng).unwrap().instantiate();
+        assert!(event.auto_resolve);
+        let outcome = event.apply_choice(0);
+        assert_eq!(outcome.cash, 250);
+        assert_eq!(outcome.modifiers[0].demand_multiplier, 1.5);
+        assert_eq!(outcome.modifiers[0].price_multiplier, 1.0);
+
+        // Preconditions filter events out
+        context.season = Season::Summer;
+        assert!(catalog.pick_random_event(&context, &mut rng).is_none());
+
+        // Invalid catalogs are rejected and fall back to the built-in one
+        let too_many_choices = r#"{ "random_events": [{ "title": "x", "description": "y", "choices": [
+            { "label": "1" }, { "label": "2" }, { "label": "3" }, { "label": "4" } ] }] }"#;
+        assert!(EventCatalog::from_json(too_many_choices).is_err());
+        let (fallback, warning) = EventCatalog::load_or_builtin("no_such_events_file.json");
+        assert!(warning.is_none());
+        assert_eq!(fallback.random_events.len(), 15);
+
+        let malformed = std::env::temp_dir().join(format!("gametime_malformed_events_{}.json", std::process::id()));
+        std::fs::write(&malformed, r#"{ "random_events": [ { "title": "Unfinished" "#).unwrap();
+        let (fallback, warning) = EventCatalog::load_or_builtin(malformed.to_str().unwrap());
+        let _ = std::fs::remove_file(&malformed);
+        assert!(warning.unwrap().starts_with("⚠️ Ignoring"));
+        assert_eq!(fallback.random_events.len(), 15);
+    }
 
-    f.render_widget(footer, chunks[2]);
-}
+    #[test]
+    fn test_pending_event_relinked_after_catalog_migration() {
+        let catalog = EventCatalog::builtin();
+        let choice_event = catalog.random_events.iter().find(|def| !def.choices.is_empty()).unwrap();
+
+        // A pre-catalog save has the pending event but none of its effects
+        let mut events = RandomEventManager::new();
+        let mut pending = choice_event.instantiate();
+        pending.choice_effects.clear();
+        events.active_event = Some(pending);
+        events.player_choice_pending = true;
+        events.relink_active_event(&catalog);
+        assert_eq!(events.active_event.as_ref().unwrap().choice_effects.len(), choice_event.choices.len());
+
+        // Events the catalog no longer knows are dropped rather than left doing nothing
+        let mut orphan = choice_event.instantiate();
+        orphan.title = "Retired Event".to_string();
+        orphan.choice_effects.clear();
+        events.active_event = Some(orphan);
+        events.relink_active_event(&catalog);
+        assert!(events.active_event.is_none());
+        assert!(!events.player_choice_pending);
+    }
 
-fn draw_inventory(f: &mut Frame, app: &App) {
-    let size = f.area();
-    
-    // Create layout: Header, Inventory list, Footer
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Inventory content
-            Constraint::Length(3), // Footer
-        ])
-        .split(size);
+    #[test]
+    fn test_cost_basis_policies() {
+        let lots = || {
+            let mut game_data = GameData::with_seed(9);
+            game_data.inventory.clear();
+            game_data.add_to_inventory(GiftCard::new("Amazon", 25, 18, 10), 2); // Oldest, expires last-but-one
+            game_data.add_to_inventory(GiftCard::new("Amazon", 25, 22, 5), 2);  // Newest, expires first
+            game_data
+        };
 
-    // Header showing total inventory value
-    let total_value = app.game_data.total_inventory_value();
-    let inventory_count = app.game_data.inventory_count();
-    let header_text = format!("Total Value: ${}    Items: {}", total_value, inventory_count);
-    let header = Paragraph::new(header_text)
-        .block(Block::default()
-            .title("Inventory Management")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Green))
-        .alignment(Alignment::Center);
-    
-    f.render_widget(header, chunks[0]);
+        let mut fifo = lots();
+        assert_eq!(fifo.take_cards("Amazon", Some(25), 3, CostBasisPolicy::Fifo), (3, 18 + 18 + 22));
 
-    // Inventory list
-    if app.game_data.inventory.is_empty() {
-        let no_inventory = Paragraph::new("No inventory available\n\nVisit the Market to purchase gift cards")
-            .block(Block::default()
-                .title("Inventory")
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White)))
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center);
-        
-        f.render_widget(no_inventory, chunks[1]);
-    } else {
-        // Create table header and rows
-        let mut table_content = vec![
-            "   Card          │ Qty │ Cost │ Days Left │ Market Price │ Profit │ Action".to_string(),
-            "─────────────────┼─────┼──────┼───────────┼──────────────┼────────┼───────".to_string(),
-        ];
+        let mut lifo = lots();
+        assert_eq!(lifo.take_cards("Amazon", Some(25), 3, CostBasisPolicy::Lifo), (3, 22 + 22 + 18));
 
-        for (i, item) in app.game_data.inventory.iter().enumerate() {
-            let style_char = if i == app.selected_menu_item { "►" } else { " " };
-            
-            // Calculate profit potential
-            let market_value = item.card.market_value();
-            let profit_per_card = market_value as i32 - item.card.purchase_price as i32;
-            let total_profit = profit_per_card * item.quantity as i32;
-            
-            // Enhanced expiration indicators with animation
-            let expiration_indicator = if item.card.days_until_expiration <= 3 {
-                // Critical - blinking warning
-                match (app.game_data.minute / 3) % 3 {
-                    0 => "🚨",
-                    1 => "❗",
-                    _ => "⚠️",
-                }
-            } else if item.card.days_until_expiration <= 7 {
-                // Warning - steady indicator
-                "⚠️"
-            } else if item.card.days_until_expiration <= 14 {
-                // Caution - mild indicator
-                "⚡"
-            } else {
-                // Fresh - good indicator
-                "✅"
-            };
-            
-            // Format retailer name with padding
-            let retailer_display = format!("{:<10}", item.card.retailer);
-            
-            table_content.push(format!(
-                "{}{} {} ${:>2} │ {:>3} │ ${:>3} │    {:>3}    │     ${:>3}     │  ${:>4} │ [Sell]",
-                style_char,
-                expiration_indicator,
-                retailer_display,
-                item.card.denomination,
-                item.quantity,
-                item.card.purchase_price,
-                item.card.days_until_expiration,
-                market_value,
-                total_profit
-            ));
-        }
+        let mut soonest = lots();
+        assert_eq!(soonest.take_cards("Amazon", Some(25), 1, CostBasisPolicy::SoonestExpiring), (1, 22));
 
-        let table_items: Vec<ListItem> = table_content
-            .iter()
-            .enumerate()
-            .map(|(i, line)| {
-                let style = if i >= 2 && (i - 2) == app.selected_menu_item {
-                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-                } else if i < 2 {
-                    Style::default().fg(Color::Gray)
-                } else if line.contains("❗") {
-                    Style::default().fg(Color::Red) // Expiring items in red
-                } else {
-                    Style::default().fg(Color::White)
-                };
-                
-                ListItem::new(Line::from(Span::styled(line.clone(), style)))
-            })
-            .collect();
+        let mut average = lots();
+        assert_eq!(average.take_cards("Amazon", Some(25), 2, CostBasisPolicy::WeightedAverage), (2, 40));
 
-        let inventory_list = List::new(table_items)
-            .block(Block::default()
-                .title("Current Stock")
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White)))
-            .style(Style::default().fg(Color::White));
+        // Fulfillment records the real cost of goods sold
+        let mut game_data = lots();
+        game_data.cost_basis_policy = CostBasisPolicy::Lifo;
+        game_data.customer_orders.clear();
+        game_data.customer_orders.push_back(CustomerOrder::new(1, "Test", "Amazon", 25, 2, 24, 3, OrderPriority::Low));
+        assert!(game_data.fulfill_order(0));
+        assert_eq!(game_data.analytics.cost_of_goods_sold, 44);
+        assert_eq!(game_data.analytics.realized_gains(), 48 - 44);
+        assert_eq!(game_data.total_inventory_cost(), 36);
+        assert!(game_data.unrealized_gains() > 0);
+    }
 
-        f.render_widget(inventory_list, chunks[1]);
+    #[test]
+    fn test_market_stock_and_restock() {
+        let mut app = App::new(Some(11));
+        app.screen = Screen::Market;
+        let target = app.game_data.market.listing_index("Target", 100).unwrap(); // One of the scarcest listings
+        app.selected_menu_item = target;
+        let max_stock = app.game_data.market.listings[target].max_stock;
+        let full_price = app.game_data.wholesale_price(&app.game_data.market.listings[target]);
+
+        // Purchases deplete stock
+        app.game_data.cash = 100_000;
+        app.purchase_from_market();
+        assert_eq!(app.game_data.market.listings[target].stock, max_stock - 1);
+
+        // Scarcity raises prices
+        app.game_data.market.listings[target].stock = 1;
+        assert!(app.game_data.wholesale_price(&app.game_data.market.listings[target]) > full_price);
+
+        // Sold out listings can't be bought
+        app.game_data.market.listings[target].stock = 0;
+        let cash_before = app.game_data.cash;
+        app.purchase_from_market();
+        assert_eq!(app.game_data.cash, cash_before);
+        assert!(app.game_data.market.listings[target].days_until_full(&app.game_data.market_conditions).is_some());
+
+        // Daily restock refills over time, never beyond capacity
+        for _ in 0..30 {
+            app.game_data.market.restock(&app.game_data.market_conditions);
+        }
+        assert_eq!(app.game_data.market.listings[target].stock, max_stock);
+        assert!(app.game_data.market.listings[target].days_until_full(&app.game_data.market_conditions).is_none());
     }
 
-    // Footer with controls
-    let footer_text = "↑↓ Select  Enter Sell Item  Esc Back  ❗ = Expiring Soon";
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Gray))
-        .alignment(Alignment::Center);
+    #[test]
+    fn test_bulk_purchase_discounts() {
+        let mut app = App::new(Some(12));
+        app.screen = Screen::Market;
+        let amazon = app.game_data.market.listing_index("Amazon", 25).unwrap(); // 5% at 10, 8% at 25, 12% at 50
+        app.selected_menu_item = amazon;
+        app.game_data.cash = 100_000;
+
+        // Quantity selector: typed digits, +/- and backspace
+        app.type_purchase_quantity_digit('1');
+        app.type_purchase_quantity_digit('0');
+        assert_eq!(app.purchase_quantity, 10);
+        app.adjust_purchase_quantity(1);
+        assert_eq!(app.purchase_quantity, 11);
+        app.adjust_purchase_quantity(-20);
+        assert_eq!(app.purchase_quantity, 1);
+        app.type_purchase_quantity_digit('2');
+        app.type_purchase_quantity_digit('5');
+        app.erase_purchase_quantity_digit();
+        assert_eq!(app.purchase_quantity, 2);
+
+        // Volume tiers lower the per-card price
+        let listing = app.game_data.market.listings[amazon].clone();
+        let single = app.game_data.bulk_unit_price(&listing, 1);
+        assert!(app.game_data.bulk_unit_price(&listing, 25) < single);
+        assert_eq!(listing.volume_discount(9), 0.0);
+        assert_eq!(listing.volume_discount(50), 0.12);
+
+        // Bulk purchase charges the discounted total and takes stock once
+        app.purchase_quantity = 25;
+        let unit_price = app.game_data.bulk_unit_price(&listing, 25);
+        app.purchase_from_market();
+        assert_eq!(app.game_data.cash, 100_000 - unit_price * 25);
+        assert_eq!(app.game_data.market.listings[amazon].stock, listing.max_stock - 25);
+        assert_eq!(app.game_data.analytics.total_purchases, unit_price * 25);
+
+        // Lifetime volume unlocks a better base price
+        assert_eq!(app.game_data.market.relationship_discount("Amazon"), 0.0);
+        app.game_data.market.listings[amazon].stock = listing.stock;
+        let regular_price = app.game_data.wholesale_price(&listing);
+        app.game_data.market.lifetime_units.insert("Amazon".to_string(), 150);
+        assert!(app.game_data.wholesale_price(&app.game_data.market.listings[amazon]) < regular_price);
+
+        // Max affordable respects cash, stock and storage space
+        app.game_data.cash = single * 3;
+        app.set_max_affordable_quantity();
+        assert!(app.purchase_quantity >= 3);
+        app.game_data.cash = 100_000;
+        app.set_max_affordable_quantity();
+        assert_eq!(app.purchase_quantity, app.game_data.free_storage());
+        app.game_data.storage_level = STORAGE_TIERS.len() - 1;
+        app.set_max_affordable_quantity();
+        assert_eq!(app.purchase_quantity, app.game_data.market.listings[amazon].stock);
+    }
 
-    f.render_widget(footer, chunks[2]);
-}
+    #[test]
+    fn test_relationship_pricing_is_per_retailer() {
+        let mut game = GameData::with_seed(7);
+        game.cash = 100_000;
+        game.storage_level = STORAGE_TIERS.len() - 1;
+        let amazon_10 = game.market.listing_index("Amazon", 10).unwrap();
+        let amazon_25 = game.market.listing_index("Amazon", 25).unwrap();
+        let amazon_50 = game.market.listing_index("Amazon", 50).unwrap();
+        let starbucks_10 = game.market.listing_index("Starbucks", 10).unwrap();
+        let starbucks_price = game.wholesale_price(&game.market.listings[starbucks_10]);
+
+        // Cards of different denominations add up toward the same retailer tier
+        assert!(game.market.take_stock(amazon_10, 30));
+        assert!(game.market.take_stock(amazon_25, 25));
+        assert_eq!(game.market.lifetime_units("Amazon"), 55);
+        assert_eq!(game.market.relationship_discount("Amazon"), RELATIONSHIP_TIERS[0].1);
+        assert_eq!(game.market.next_relationship_tier("Amazon"), Some(RELATIONSHIP_TIERS[1]));
+
+        // ...and every Amazon card gets the better base price, while other retailers don't
+        let listing = game.market.listings[amazon_50].clone();
+        let base = (listing.base_cost as f32
+            * game.market_conditions.get_price_multiplier_with_random_events("Amazon", &game.random_events)
+            * listing.scarcity_multiplier()
+            * game.difficulty.wholesale_cost_multiplier()).round() as u32;
+        assert!(game.wholesale_price(&listing) < base);
+        assert_eq!(game.market.relationship_discount("Starbucks"), 0.0);
+        assert_eq!(game.wholesale_price(&game.market.listings[starbucks_10]), starbucks_price);
+
+        // Saves that tracked volume per listing are pooled by retailer on load
+        let mut save = serde_json::to_value(&game).unwrap();
+        save["market"].as_object_mut().unwrap().remove("lifetime_units");
+        save["market"]["listings"][amazon_10]["lifetime_units"] = 30.into();
+        save["market"]["listings"][amazon_50]["lifetime_units"] = 20.into();
+        GameData::migrate_relationship_volume(&mut save);
+        let loaded: GameData = serde_json::from_value(save).unwrap();
+        assert_eq!(loaded.market.lifetime_units("Amazon"), 50);
+        assert_eq!(loaded.market.lifetime_units("Starbucks"), 0);
+    }
 
-fn draw_analytics(f: &mut Frame, app: &App) {
-    let size = f.area();
-    
-    // Create layout: Header, Main content (left metrics, right charts), Footer
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Main content
-            Constraint::Length(3), // Footer
-        ])
-        .split(size);
+    #[test]
+    fn test_shopping_cart_checkout() {
+        let mut app = App::new(Some(13));
+        app.screen = Screen::Market;
+        app.game_data.cash = 100_000;
+
+        // Build a cart: Amazon twice (merged into one line) and Starbucks once
+        let amazon = app.game_data.market.listing_index("Amazon", 25).unwrap();
+        let starbucks = app.game_data.market.listing_index("Starbucks", 10).unwrap();
+        app.selected_menu_item = amazon;
+        app.purchase_quantity = 6;
+        app.add_to_cart();
+        app.add_to_cart();
+        app.selected_menu_item = starbucks;
+        app.purchase_quantity = 3;
+        app.add_to_cart();
+        assert_eq!(app.cart, vec![
+            CartLine { listing_index: amazon, quantity: 12 },
+            CartLine { listing_index: starbucks, quantity: 3 },
+        ]);
+
+        // The quote applies the volume tier to the merged quantity
+        let quotes = app.cart_quote();
+        assert!(quotes[0].volume_discount > 0.0);
+        let total = app.cart_total();
+        let starting_cards: u32 = app.game_data.inventory.iter().map(|item| item.quantity).sum();
+        assert_eq!(total, quotes.iter().map(CartQuote::line_total).sum::<u32>());
+
+        // Short on cash: nothing is bought and the cart is kept
+        app.game_data.cash = total - 1;
+        app.checkout_cart();
+        assert_eq!(app.game_data.cash, total - 1);
+        assert_eq!(app.cart.len(), 2);
+        assert_eq!(app.game_data.inventory.iter().map(|item| item.quantity).sum::<u32>(), starting_cards);
+        assert!(app.game_data.recent_activities[0].contains("short $1"));
+
+        // Enough cash: one charge, one purchase record per line, stock and inventory updated
+        app.game_data.cash = total;
+        let amazon_stock = app.game_data.market.listings[amazon].stock;
+        app.checkout_cart();
+        assert_eq!(app.game_data.cash, 0);
+        assert!(app.cart.is_empty());
+        assert_eq!(app.game_data.analytics.total_purchases, total);
+        assert_eq!(app.game_data.market.listings[amazon].stock, amazon_stock - 12);
+        assert_eq!(app.game_data.inventory.iter().map(|item| item.quantity).sum::<u32>(), starting_cards + 15);
+
+        // Removing a line drops it from the cart
+        app.selected_menu_item = starbucks;
+        app.add_to_cart();
+        app.remove_from_cart();
+        assert!(app.cart.is_empty());
+    }
 
-    // Header
-    let analytics = &app.game_data.analytics;
-    let total_profit = analytics.total_profit();
-    let profit_color = if total_profit >= 0 { Color::Green } else { Color::Red };
-    
-    let header_text = format!(
-        "Total Profit: ${:+}    Revenue: ${}    Orders: {}    Avg Margin: {:.1}%",
-        total_profit,
-        analytics.total_revenue,
-        analytics.orders_completed,
-        analytics.average_profit_margin()
-    );
-    
-    let header = Paragraph::new(header_text)
-        .block(Block::default()
-            .title("Business Analytics Dashboard")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(profit_color))
-        .alignment(Alignment::Center);
-    
-    f.render_widget(header, chunks[0]);
+    #[test]
+    fn test_order_decline_and_counter_offer() {
+        let mut game = GameData::with_seed(14);
+        game.reputation = 3;
+        game.customer_orders.clear();
+        for id in 0..4 {
+            game.customer_orders.push_back(CustomerOrder::new(id, "Alice", "Amazon", 25, 2, 22, 4, OrderPriority::Low));
+        }
 
-    // Main content area split into two columns
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(50), // Key metrics
-            Constraint::Percentage(50), // Performance data
-        ])
-        .split(chunks[1]);
+        // Declining removes the order; only every third decline costs a star
+        assert!(game.decline_order(0));
+        assert!(game.decline_order(0));
+        assert_eq!(game.reputation, 3);
+        assert!(game.decline_order(0));
+        assert_eq!(game.reputation, 2);
+        assert_eq!(game.analytics.orders_declined, 3);
+        assert_eq!(game.analytics.orders_expired, 0);
+
+        // Acceptance model (on the order total, 2 x $22 = $44): asking less is always fine,
+        // greed and impatience hurt, reputation and demand help
+        let order = game.customer_orders[0].clone();
+        assert_eq!(order.counter_offer_acceptance(44, 1, 1.0), 1.0);
+        assert!(order.counter_offer_acceptance(46, 3, 1.0) > order.counter_offer_acceptance(50, 3, 1.0));
+        assert!(order.counter_offer_acceptance(46, 5, 1.0) > order.counter_offer_acceptance(46, 1, 1.0));
+        assert!(order.counter_offer_acceptance(46, 3, 1.5) > order.counter_offer_acceptance(46, 3, 1.0));
+        assert_eq!(order.counter_offer_acceptance(80, 5, 1.0), 0.0);
+        let mut impatient = order.clone();
+        impatient.patience = 0;
+        assert_eq!(impatient.counter_offer_acceptance(46, 5, 1.0), 0.0);
+
+        // Outcomes: a modest ask is accepted or met halfway, an absurd ask makes them walk
+        let mut rng = GameRng::new(1);
+        match order.respond_to_counter_offer(46, 5, 1.0, &mut rng) {
+            NegotiationOutcome::Accepted(revised) => assert_eq!(revised.total_offered(), 46),
+            NegotiationOutcome::Countered(revised) => assert!((44..=46).contains(&revised.total_offered())),
+            NegotiationOutcome::WalkedAway => panic!("a $1/card ask at 5 stars should not end the talks"),
+        }
+        assert!(matches!(order.respond_to_counter_offer(80, 3, 1.0, &mut rng), NegotiationOutcome::WalkedAway));
 
-    // Left column: Key Business Metrics
-    let key_metrics = vec![
-        format!("💰 Total Revenue:          ${}", analytics.total_revenue),
-        format!("💳 Total Purchases:        ${}", analytics.total_purchases),
-        format!("📈 Net Profit:            ${:+}", total_profit),
-        format!(""),
-        format!("📋 Orders Completed:       {}", analytics.orders_completed),
-        format!("⏰ Orders Expired:         {}", analytics.orders_expired),
-        format!("📊 Success Rate:          {:.1}%", {
-            let total_orders = analytics.orders_completed + analytics.orders_expired;
-            if total_orders > 0 {
-                (analytics.orders_completed as f32 / total_orders as f32) * 100.0
-            } else {
-                0.0
+        // Negotiating through the game revises or removes the order and spends patience
+        match game.counter_offer(0, 44) {
+            Some(NegotiationOutcome::Accepted(revised)) => {
+                assert_eq!(game.customer_orders[0].total_offered(), 44);
+                assert_eq!(revised.patience, DEFAULT_ORDER_PATIENCE - 1);
             }
-        }),
-        format!(""),
-        format!("🎯 Cards Sold:            {}", analytics.cards_sold),
-        format!("💀 Cards Expired:         {}", analytics.cards_expired),
-        format!("🔄 Card Efficiency:       {:.1}%", {
-            let total_cards = analytics.cards_sold + analytics.cards_expired;
-            if total_cards > 0 {
-                (analytics.cards_sold as f32 / total_cards as f32) * 100.0
-            } else {
-                0.0
+            other => panic!("matching the offer should be accepted, got {:?}", other),
+        }
+        assert!(matches!(game.counter_offer(0, 80), Some(NegotiationOutcome::WalkedAway)));
+        assert!(game.customer_orders.is_empty());
+        assert_eq!(game.analytics.orders_declined, 4);
+    }
+
+    #[test]
+    fn test_counter_offer_split_always_moves() {
+        // Asking $1 over a $22 offer leaves nothing to split; the customer never "counters" at their
+        // original price and charges us patience for it
+        let order = CustomerOrder::new(1, "Alice", "Amazon", 25, 1, 22, 4, OrderPriority::Low);
+        for seed in 0..50 {
+            match order.respond_to_counter_offer(23, 1, 1.0, &mut GameRng::new(seed)) {
+                NegotiationOutcome::Accepted(revised) => assert_eq!(revised.total_offered(), 23),
+                NegotiationOutcome::Countered(revised) => panic!("countered at ${}", revised.total_offered()),
+                NegotiationOutcome::WalkedAway => {}
             }
-        }),
-        format!(""),
-        format!("⭐ Best Day Revenue:      ${}", analytics.best_day_revenue),
-        format!("📅 Recent Daily Avg:      ${:.0}", analytics.recent_daily_average()),
-    ];
+        }
 
-    let metrics_items: Vec<ListItem> = key_metrics
-        .iter()
-        .map(|metric| {
-            let style = if metric.contains("Net Profit") {
-                if total_profit >= 0 {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::Red)
-                }
-            } else if metric.is_empty() {
-                Style::default().fg(Color::Gray)
-            } else {
-                Style::default().fg(Color::White)
-            };
-            
-            ListItem::new(Line::from(Span::styled(metric.clone(), style)))
-        })
-        .collect();
+        // Odd gaps round up, in our favour
+        let order = CustomerOrder::new(2, "Bob", "Amazon", 50, 1, 40, 4, OrderPriority::Low);
+        let countered = (0..50).find_map(|seed| match order.respond_to_counter_offer(43, 5, 1.0, &mut GameRng::new(seed)) {
+            NegotiationOutcome::Countered(revised) => Some(revised.total_offered()),
+            _ => None,
+        });
+        assert_eq!(countered, Some(42));
+    }
 
-    let metrics_list = List::new(metrics_items)
-        .block(Block::default()
-            .title("Key Metrics")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::White));
+    #[test]
+    fn test_customer_base_and_loyalty() {
+        let mut game = GameData::with_seed(15);
+        assert!(game.customers.len() >= 8);
+
+        // Every generated order comes from a known customer and lands in their history
+        for order in &game.customer_orders {
+            let customer = game.customers.iter().find(|customer| customer.id == order.customer_id).unwrap();
+            assert!(customer.history.iter().any(|entry| entry.order_id == order.id));
+        }
 
-    f.render_widget(metrics_list, main_chunks[0]);
+        // Fulfilling orders builds loyalty and lifetime spend
+        game.customer_orders.clear();
+        game.generate_random_order();
+        let order = game.customer_orders[0].clone();
+        for line in &order.requested_cards {
+            game.inventory.push(InventoryItem::new(GiftCard::new(&line.retailer, line.denomination, 1, 60), line.quantity));
+        }
+        assert!(game.fulfill_order(0));
+        let customer = game.customers.iter().find(|customer| customer.id == order.customer_id).unwrap().clone();
+        assert_eq!(customer.orders_fulfilled, 1);
+        assert_eq!(customer.total_spent, order.total_offered());
+        assert_eq!(customer.loyalty_tier(), LoyaltyTier::Regular);
+        assert_eq!(customer.history.last().unwrap().outcome, OrderOutcome::Fulfilled);
+
+        // Repeat customers pay better than new ones with the same temperament
+        let mut newcomer = customer.clone();
+        newcomer.orders_fulfilled = 0;
+        let mut regular = customer.clone();
+        regular.orders_fulfilled = 8;
+        assert_eq!(regular.loyalty_tier(), LoyaltyTier::Vip);
+        assert!(game.customer_offer_price(&regular, "Target", 50) > game.customer_offer_price(&newcomer, "Target", 50));
+
+        // Expired orders cost loyalty and eventually drive customers away
+        let mut unhappy = newcomer.clone();
+        unhappy.orders_expired = 3;
+        assert!(unhappy.has_churned());
+        for customer in &mut game.customers {
+            customer.orders_expired = 3;
+            customer.orders_fulfilled = 0;
+        }
+        let churned_ids: Vec<u32> = game.customers.iter().map(|customer| customer.id).collect();
+        game.generate_random_order();
+        let newest = game.customer_orders.back().unwrap();
+        assert!(!churned_ids.contains(&newest.customer_id) || game.customers.len() == CUSTOMER_NAMES.len());
+
+        // The customer detail view opens from Orders and returns to the same row
+        let mut app = App::new(Some(15));
+        app.screen = Screen::Orders;
+        app.selected_menu_item = 1;
+        app.view_order_customer();
+        assert!(matches!(app.screen, Screen::CustomerDetail));
+        assert_eq!(app.customer_detail.unwrap().0, app.game_data.customer_orders[1].customer_id);
+        app.go_back();
+        assert!(matches!(app.screen, Screen::Orders));
+        assert_eq!(app.selected_menu_item, 1);
+    }
 
-    // Right column: Performance Trends and Daily Revenue
-    let mut performance_data = vec![
-        format!("📊 RECENT DAILY REVENUES"),
-        format!("────────────────────────"),
-    ];
+    #[test]
+    fn test_multi_line_orders() {
+        let mut game = GameData::with_seed(16);
+        game.customer_orders.clear();
+        game.inventory.clear();
+        let lines = vec![
+            CardRequest::new("Amazon", 25, 3, 22),
+            CardRequest::new("Starbucks", 10, 2, 9),
+        ];
+        let mut order = CustomerOrder::from_lines(1, "Alice", lines, 4);
+        assert_eq!(order.total_offered(), 3 * 22 + 2 * 9);
+        assert_eq!(order.card_count(), 5);
+        assert_eq!(order.summary(), "3 Amazon $25 + 2 Starbucks $10");
+        game.customer_orders.push_back(order.clone());
+
+        // Every line must be in stock for an all-or-nothing order
+        game.inventory.push(InventoryItem::new(GiftCard::new("Amazon", 25, 20, 60), 3));
+        assert!(game.can_fulfill_line(&order.requested_cards[0]));
+        assert!(!game.can_fulfill_line(&order.requested_cards[1]));
+        assert!(!game.can_fulfill_order(&order));
+        assert!(!game.fulfill_order(0));
+        assert_eq!(game.inventory_count(), 3);
+
+        // Partial-OK orders ship the lines we have and stay open for the rest
+        order.allow_partial = true;
+        game.customer_orders[0] = order;
+        let cash_before = game.cash;
+        assert!(game.fulfill_order(0));
+        assert_eq!(game.cash, cash_before + 66);
+        assert_eq!(game.customer_orders[0].outstanding_cards(), 2);
+        assert_eq!(game.customer_orders[0].shipped_value(), 66);
+        assert_eq!(game.analytics.orders_completed, 0);
+
+        // Shipping the last line completes the order and pays only for that line
+        game.inventory.push(InventoryItem::new(GiftCard::new("Starbucks", 10, 8, 60), 2));
+        assert!(game.fulfill_order(0));
+        assert!(game.customer_orders.is_empty());
+        assert_eq!(game.cash, cash_before + 84);
+        assert_eq!(game.analytics.orders_completed, 1);
+        assert_eq!(game.analytics.cards_sold, 5);
+
+        // Orders saved before multi-line orders load as a single line
+        let legacy = r#"{"id":7,"customer_name":"Bob","retailer":"Target","denomination":50,"quantity":2,
+            "offered_price_per_card":45,"deadline_days":3,"priority":"Low"}"#;
+        let loaded: CustomerOrder = serde_json::from_str(legacy).unwrap();
+        assert_eq!(loaded.requested_cards, vec![CardRequest::new("Target", 50, 2, 45)]);
+        assert_eq!(loaded.total_offered(), 90);
+        assert_eq!(loaded.patience, DEFAULT_ORDER_PATIENCE);
+    }
 
-    // Show last 7 days of revenue (or whatever we have)
-    let recent_days = analytics.daily_revenues.len().min(7);
-    let current_day = app.game_data.day;
-    
-    for (i, &revenue) in analytics.daily_revenues.iter().rev().take(recent_days).enumerate() {
-        let day_num = current_day.saturating_sub(i as u32);
-        let bar_length = if analytics.best_day_revenue > 0 {
-            ((revenue as f32 / analytics.best_day_revenue as f32) * 20.0) as usize
-        } else {
-            0
-        };
-        let bar = "█".repeat(bar_length) + &"░".repeat(20 - bar_length);
-        
-        performance_data.push(format!(
-            "Day {:2} │ ${:4} │ {}",
-            day_num, revenue, bar
-        ));
+    #[test]
+    fn test_partial_fulfillment_and_backorders() {
+        let mut game = GameData::with_seed(17);
+        game.customer_orders.clear();
+        game.inventory.clear();
+        game.reputation = 3;
+        let customer_id = game.customers[0].id;
+        game.customers[0].backorder_days = 4;
+
+        let mut order = CustomerOrder::from_lines(1, "Alice", vec![CardRequest::new("Amazon", 25, 5, 22)], 6);
+        order.customer_id = customer_id;
+        game.customer_orders.push_back(order.clone());
+        game.inventory.push(InventoryItem::new(GiftCard::new("Amazon", 25, 20, 60), 2));
+
+        // All-or-nothing orders wait for full stock
+        assert!(!game.fulfill_order(0));
+
+        // Per-order switch: ship the 2 we have, backorder the other 3 on the customer's clock
+        game.customer_orders[0].allow_partial = true;
+        assert!(game.fulfill_order(0));
+        let backorder = game.customer_orders[0].clone();
+        assert!(backorder.backordered);
+        assert_eq!(backorder.outstanding_cards(), 3);
+        assert_eq!(backorder.requested_cards[0].progress(), "2/5");
+        assert_eq!(backorder.deadline_days, 4);
+        assert_eq!(game.cash, game.difficulty.starting_cash() + 44);
+        assert_eq!(game.reputation, 3);
+
+        // Filling the backorder completes the order and earns reputation
+        game.inventory.push(InventoryItem::new(GiftCard::new("Amazon", 25, 20, 60), 3));
+        assert!(game.fulfill_order(0));
+        assert!(game.customer_orders.is_empty());
+        assert_eq!(game.reputation, 4);
+        assert_eq!(game.analytics.orders_completed, 1);
+
+        // A mostly-shipped backorder that expires is a minor letdown, not a full expiry hit
+        let mut mostly = CustomerOrder::from_lines(2, "Alice", vec![CardRequest::new("Amazon", 25, 4, 22)], 1);
+        mostly.customer_id = customer_id;
+        mostly.backordered = true;
+        mostly.requested_cards[0].shipped = 3;
+        game.customer_orders.push_back(mostly);
+        game.process_order_aging();
+        assert_eq!(game.reputation, 4);
+        assert_eq!(game.decline_strain, 1);
+        let customer = game.customers.iter().find(|customer| customer.id == customer_id).unwrap();
+        assert_eq!(customer.orders_partial, 1);
+        assert_eq!(customer.total_spent, 110 + 66);
+
+        // Barely-started backorders still count as expired orders
+        let mut barely = CustomerOrder::from_lines(3, "Alice", vec![CardRequest::new("Amazon", 25, 4, 22)], 1);
+        barely.backordered = true;
+        barely.requested_cards[0].shipped = 1;
+        game.customer_orders.push_back(barely);
+        game.process_order_aging();
+        assert_eq!(game.reputation, 3);
+
+        // Per-customer default is toggled from their profile
+        let mut app = App::new(Some(17));
+        let customer_id = app.game_data.customers[0].id;
+        let before = app.game_data.customers[0].partial_shipments;
+        app.customer_detail = Some((customer_id, 0));
+        app.screen = Screen::CustomerDetail;
+        app.toggle_customer_partial_shipments();
+        assert_eq!(app.game_data.customers[0].partial_shipments, !before);
+    }
+
+    #[test]
+    fn test_counter_offer_locked_after_shipping() {
+        let mut app = App::new(Some(11));
+        let game = &mut app.game_data;
+        game.customer_orders.clear();
+        game.inventory.clear();
+        let mut order = CustomerOrder::from_lines(1, "Alice", vec![CardRequest::new("Amazon", 25, 5, 22)], 6);
+        order.allow_partial = true;
+        game.customer_orders.push_back(order);
+        game.inventory.push(InventoryItem::new(GiftCard::new("Amazon", 25, 20, 60), 2));
+        assert!(game.fulfill_order(0));
+        assert_eq!(game.customer_orders[0].shipped_value(), 44);
+
+        // The shipped cards keep the price they were paid at: no negotiating the rest either
+        assert!(game.customer_orders[0].has_shipped());
+        assert!(game.counter_offer(0, 200).is_none());
+        assert_eq!(game.customer_orders[0].shipped_value(), 44);
+        assert_eq!(game.customer_orders[0].total_offered(), 110);
+
+        app.screen = Screen::Orders;
+        app.selected_menu_item = 0;
+        app.start_counter_offer();
+        assert!(app.counter_offer.is_none());
+        assert!(app.game_data.recent_activities[0].contains("can't be renegotiated"));
     }
 
-    performance_data.push(format!(""));
-    performance_data.push(format!("📈 PROFIT MARGIN TRENDS"));
-    performance_data.push(format!("───────────────────────"));
+    #[test]
+    fn test_card_catalog_denominations() {
+        // Retailers offer several denominations, each with its own supply and demand
+        let amazon: Vec<u32> = CARD_CATALOG.iter()
+            .filter(|product| product.retailer == "Amazon")
+            .map(|product| product.denomination)
+            .collect();
+        assert_eq!(amazon, vec![10, 25, 50, 100]);
+        assert_eq!(GiftCard::new("Amazon", 25, 20, 30).market_value(), 32);
+        assert_eq!(GiftCard::new("Amazon", 100, 82, 30).market_value(), 126);
+
+        // Each denomination has its own wholesale cost and market value
+        let cost_rate = |denomination: u32| catalog_product("Amazon", denomination).unwrap().base_cost() as f32 / denomination as f32;
+        assert_eq!(cost_rate(25), 0.80);
+        assert_eq!(cost_rate(50), 0.82);
+        assert!(catalog_product("Amazon", 25).unwrap().markup() > catalog_product("Amazon", 100).unwrap().markup());
+
+        // The market lists every catalog card
+        let game = GameData::with_seed(18);
+        assert_eq!(game.market.listings.len(), CARD_CATALOG.len());
+        for product in CARD_CATALOG {
+            let index = game.market.listing_index(product.retailer, product.denomination).unwrap();
+            assert_eq!(game.market.listings[index].base_cost, product.base_cost());
+        }
 
-    // Show recent profit margins
-    let recent_margins = analytics.profit_margins.len().min(5);
-    if recent_margins > 0 {
-        for (i, &margin) in analytics.profit_margins.iter().rev().take(recent_margins).enumerate() {
-            let trend_indicator = if i > 0 && i < analytics.profit_margins.len() {
-                let prev_margin = analytics.profit_margins[analytics.profit_margins.len() - i];
-                if margin > prev_margin { "↗" } 
-                else if margin < prev_margin { "↘" } 
-                else { "→" }
-            } else {
-                "→"
-            };
-            
-            performance_data.push(format!(
-                "Sale {:2} │ {:5.1}% │ {}",
-                analytics.profit_margins.len() - i, margin, trend_indicator
-            ));
+        // Orders only ask for catalog cards, in batches the catalog allows
+        let mut game = game;
+        for _ in 0..40 {
+            game.generate_random_order();
         }
-    } else {
-        performance_data.push(format!("No sales data available yet"));
+        for line in game.customer_orders.iter().flat_map(|order| order.requested_cards.iter()) {
+            let product = catalog_product(&line.retailer, line.denomination).unwrap();
+            assert!(line.quantity <= product.max_order_quantity);
+        }
+
+        // Markets from older saves pick up new catalog cards
+        let mut market = Market { listings: vec![MarketListing::from_product(&CARD_CATALOG[1])], lifetime_units: BTreeMap::new() };
+        market.listings[0].stock = 3;
+        market.sync_with_catalog();
+        assert_eq!(market.listings.len(), CARD_CATALOG.len());
+        assert_eq!(market.listings[1].stock, 3);
     }
 
-    performance_data.push(format!(""));
-    performance_data.push(format!("🎯 STRATEGIC INSIGHTS"));
-    performance_data.push(format!("──────────────────"));
+    #[test]
+    fn test_retailer_registry() {
+        // Every catalog card belongs to a registered retailer and is priced from its terms unless it overrides them
+        for product in CARD_CATALOG {
+            let registered = retailer(product.retailer).unwrap();
+            assert_eq!(product.base_cost(), product.cost_override.unwrap_or(registered.wholesale_cost(product.denomination)));
+            let card = GiftCard::new(product.retailer, product.denomination, product.base_cost(), 30);
+            let markup = product.markup_override.unwrap_or(registered.markup);
+            assert_eq!(card.market_value(), (product.denomination as f32 * markup) as u32);
+        }
+        assert_eq!(GiftCard::new("Amazon", 30, 24, 30).market_value(), 38); // Off-catalog cards use the retailer's markup
+        assert!(RETAILERS.iter().all(|registered| registered.flagship_product().is_some()));
+
+        // Seasons read their bonuses from the registry
+        assert_eq!(Season::Winter.retailer_bonus("Amazon"), 1.5);
+        assert_eq!(Season::Winter.retailer_bonus("Walmart"), 1.2);
+        assert_eq!(Season::Summer.retailer_bonus("Starbucks"), 1.0);
+        assert_eq!(Season::Winter.retailer_bonus("Unknown"), 1.2);
+
+        // Diversified Portfolio asks for every retailer, even on older saves
+        let mut tracker = AchievementTracker::new();
+        let target = |tracker: &AchievementTracker| tracker.achievements.iter()
+            .find(|a| a.achievement_type == AchievementType::DiversifiedPortfolio)
+            .map(|a| a.target);
+        assert_eq!(target(&tracker), Some(RETAILERS.len() as u32));
+        tracker.achievements.iter_mut()
+            .filter(|a| a.achievement_type == AchievementType::DiversifiedPortfolio)
+            .for_each(|a| a.target = 3);
+        tracker.sync_with_retailers();
+        assert_eq!(target(&tracker), Some(RETAILERS.len() as u32));
+
+        // Customers only favour registered retailers
+        let mut rng = GameRng::new(14);
+        for customer in Customer::initial_base(&mut rng) {
+            assert!(customer.preferred_retailers.iter().all(|id| retailer(id).is_some()));
+        }
 
-    // Add some strategic insights based on the data
-    if analytics.orders_completed > 0 {
-        let avg_revenue_per_order = analytics.total_revenue / analytics.orders_completed;
-        performance_data.push(format!("Avg Revenue/Order: ${}", avg_revenue_per_order));
+        // Event files can't refer to retailers we don't carry
+        let json = r#"{"market_events": [{"name": "Sale", "description": "", "retailer": "Sears"}], "random_events": []}"#;
+        assert!(EventCatalog::from_json(json).is_err());
+
+        // Gifted cards for a retailer we don't stock yet start a new stack
+        let mut game = GameData::with_seed(14);
+        game.inventory.clear();
+        game.apply_event_outcome(EventOutcome {
+            title: "Gift".to_string(),
+            inventory: vec![("Target".to_string(), 2)],
+            ..Default::default()
+        });
+        assert_eq!(game.inventory.len(), 1);
+        assert_eq!(game.inventory[0].card.retailer, "Target");
+        assert_eq!(game.inventory[0].quantity, 2);
     }
-    
-    if total_profit < 0 {
-        performance_data.push(format!("⚠️  Operating at a loss"));
-        performance_data.push(format!("   Focus on higher margins"));
-    } else if analytics.average_profit_margin() < 15.0 {
-        performance_data.push(format!("⚠️  Low profit margins"));
-        performance_data.push(format!("   Seek better deals"));
-    } else {
-        performance_data.push(format!("✅ Healthy profit margins"));
+
+    #[test]
+    fn test_achievement_reward_ledger() {
+        let mut game = GameData::with_seed(15);
+        game.cash = 10000;
+        game.achievements.check_cash_achievements(game.cash, game.day, &mut game.recent_activities);
+        let pending = game.achievements.pending_rewards();
+        assert!(pending > 0);
+        assert_eq!(game.cash, 10000); // Unlocking only queues the reward
+
+        // Claiming pays the ledger out once and books it as its own income category
+        assert_eq!(game.claim_achievement_rewards(), pending);
+        assert_eq!(game.cash, 10000 + pending);
+        assert_eq!(game.analytics.achievement_rewards, pending);
+        assert_eq!(game.analytics.total_revenue, 0);
+        assert_eq!(game.achievements.pending_rewards(), 0);
+        assert_eq!(game.claim_achievement_rewards(), 0);
+
+        // Auto-claim settles new unlocks straight away
+        game.auto_claim_rewards = true;
+        game.reputation = 5;
+        game.achievements.check_order_achievements(0, game.reputation, game.day, &mut game.recent_activities);
+        let legendary = game.achievements.pending_rewards();
+        assert!(legendary > 0);
+        let cash_before = game.cash;
+        game.settle_achievement_rewards();
+        assert_eq!(game.cash, cash_before + legendary);
+        assert!(game.achievements.reward_ledger.iter().all(|entry| entry.claimed_day == Some(game.day)));
+
+        // Unlocks from saves that predate the ledger are queued once
+        let mut tracker = AchievementTracker::new();
+        tracker.check_cash_achievements(10000, 3, &mut Vec::new());
+        let unlocked = tracker.reward_ledger.len();
+        tracker.reward_ledger.clear();
+        tracker.backfill_reward_ledger();
+        tracker.backfill_reward_ledger();
+        assert_eq!(tracker.reward_ledger.len(), unlocked);
+    }
+
+    fn achievement_unlocked(game: &GameData, achievement_type: AchievementType) -> bool {
+        game.achievements.achievements.iter()
+            .any(|a| a.achievement_type == achievement_type && a.unlocked)
     }
 
-    let performance_items: Vec<ListItem> = performance_data
-        .iter()
-        .map(|item| {
-            let style = if item.contains("REVENUES") || item.contains("TRENDS") || item.contains("INSIGHTS") {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-            } else if item.contains("─") {
-                Style::default().fg(Color::Gray)
-            } else if item.contains("⚠️") {
-                Style::default().fg(Color::Red)
-            } else if item.contains("✅") {
-                Style::default().fg(Color::Green)
-            } else {
-                Style::default().fg(Color::White)
-            };
-            
-            ListItem::new(Line::from(Span::styled(item.clone(), style)))
-        })
-        .collect();
+    #[test]
+    fn test_perfect_week_achievement() {
+        let mut game = GameData::with_seed(16);
+        game.inventory.clear();
+        let complete_one_order = |game: &mut GameData, id: u32| {
+            game.customer_orders.clear();
+            game.customer_orders.push_back(CustomerOrder::from_lines(id, "Alice", vec![CardRequest::new("Amazon", 25, 1, 22)], 5));
+            game.inventory.push(InventoryItem::new(GiftCard::new("Amazon", 25, 20, 60), 1));
+            assert!(game.fulfill_order(0));
+        };
 
-    let performance_list = List::new(performance_items)
-        .block(Block::default()
-            .title("Performance Analysis")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::White));
+        // An expired order breaks the streak
+        for id in 1..=6 {
+            complete_one_order(&mut game, id);
+            game.process_daily_events();
+        }
+        assert_eq!(game.achievements.consecutive_perfect_days, 6);
+        game.customer_orders.push_back(CustomerOrder::from_lines(50, "Bob", vec![CardRequest::new("Target", 50, 1, 55)], 0));
+        game.process_daily_events();
+        assert_eq!(game.achievements.consecutive_perfect_days, 0);
+
+        // Seven days in a row of completed orders with nothing expiring
+        for id in 10..17 {
+            complete_one_order(&mut game, id);
+            game.customer_orders.clear();
+            game.process_daily_events();
+        }
+        assert!(achievement_unlocked(&game, AchievementType::PerfectWeek));
+    }
 
-    f.render_widget(performance_list, main_chunks[1]);
+    #[test]
+    fn test_event_survivor_achievement() {
+        let mut game = GameData::with_seed(16);
+
+        // Market events count once they run out
+        game.market_conditions.active_events = vec![MarketEvent::new("Flash Sale", "", None, 0.9, 1.0, 0); 6];
+        game.process_daily_events();
+        assert_eq!(game.achievements.events_survived, 6);
+        assert!(!achievement_unlocked(&game, AchievementType::EventSurvivor));
+
+        // Random events count once they are resolved
+        let catalog = EventCatalog::builtin();
+        for _ in 0..4 {
+            let mut event = catalog.random_events.iter()
+                .find(|e| e.title == "Loyal Customer Returns")
+                .map(|e| e.instantiate())
+                .unwrap();
+            game.apply_event_outcome(event.apply_choice(0));
+        }
+        assert!(achievement_unlocked(&game, AchievementType::EventSurvivor));
+    }
 
-    // Footer with controls
-    let footer_text = "View comprehensive business metrics and trends • Esc Back";
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Gray))
-        .alignment(Alignment::Center);
+    #[test]
+    fn test_sale_timing_achievements() {
+        let mut game = GameData::with_seed(16);
+        game.inventory.clear();
+        game.customer_orders.clear();
+        game.day = 20;
+
+        // Cards held longer than 3 days don't count, and stacks bought on different days stay apart
+        game.add_to_inventory(GiftCard::new("Amazon", 25, 20, 60).purchased_on(12), 1);
+        game.add_to_inventory(GiftCard::new("Amazon", 25, 20, 60).purchased_on(19), 1);
+        assert_eq!(game.inventory.len(), 2);
+        game.customer_orders.push_back(CustomerOrder::from_lines(1, "Alice", vec![CardRequest::new("Amazon", 25, 1, 22)], 5));
+        assert!(game.fulfill_order(0));
+        assert!(!achievement_unlocked(&game, AchievementType::QuickTurnaround));
+
+        // Selling the card bought yesterday is a quick turnaround
+        game.customer_orders.push_back(CustomerOrder::from_lines(2, "Alice", vec![CardRequest::new("Amazon", 25, 1, 22)], 5));
+        assert!(game.fulfill_order(0));
+        assert!(achievement_unlocked(&game, AchievementType::QuickTurnaround));
+
+        // Winter profit accumulates from sales and is checked at the start of each day
+        game.market_conditions.current_season = Season::Winter;
+        game.day = 300;
+        game.add_to_inventory(GiftCard::new("Amazon", 100, 10, 60), 50);
+        game.customer_orders.push_back(CustomerOrder::from_lines(3, "Alice", vec![CardRequest::new("Amazon", 100, 50, 120)], 5));
+        assert!(game.fulfill_order(0));
+        assert!(game.achievements.seasonal_winter_profit >= 5000);
+        game.process_daily_events();
+        assert!(achievement_unlocked(&game, AchievementType::WinterWinner));
+    }
 
-    f.render_widget(footer, chunks[2]);
-}
+    #[test]
+    fn test_card_expiration_dates() {
+        let mut game = GameData::with_seed(17);
+
+        // Starting stock keeps its full shelf life from day 1
+        let days_left: Vec<u32> = game.inventory.iter().map(|item| item.card.days_until_expiration(game.day)).collect();
+        assert_eq!(days_left, vec![45, 30, 120, 15, 60]);
+
+        game.inventory.clear();
+        game.day = 10;
+
+        // Same price and day but different expirations stay in separate stacks
+        game.add_to_inventory(GiftCard::new("Amazon", 25, 20, 15).purchased_on(10), 2);
+        game.add_to_inventory(GiftCard::new("Amazon", 25, 20, 100).purchased_on(10), 3);
+        game.add_to_inventory(GiftCard::new("Amazon", 25, 20, 100).purchased_on(10), 1);
+        assert_eq!(game.inventory.len(), 2);
+        assert_eq!(game.inventory[1].quantity, 4);
+
+        // Cards expire on their expiration day without being counted down
+        game.day = 15;
+        game.process_daily_events();
+        assert_eq!(game.inventory.len(), 1);
+        assert_eq!(game.inventory[0].card.expiration_day, 100);
+        assert_eq!(game.inventory[0].card.days_until_expiration(game.day), 85);
+
+        // Older saves with a days-left countdown are converted on load
+        let mut save = serde_json::to_value(&game).unwrap();
+        let card = save["inventory"][0]["card"].as_object_mut().unwrap();
+        card.remove("expiration_day");
+        card.insert("days_until_expiration".to_string(), 30.into());
+        GameData::migrate_card_expiration(&mut save);
+        let loaded: GameData = serde_json::from_value(save).unwrap();
+        assert_eq!(loaded.inventory[0].card.expiration_day, 45);
+
+        // The Inventory screen sorts and filters by time left
+        let mut app = App::new(Some(17));
+        app.screen = Screen::Inventory;
+        app.game_data.inventory.clear();
+        app.game_data.day = 1;
+        app.game_data.inventory.push(InventoryItem::new(GiftCard::new("Amazon", 25, 20, 60), 1));
+        app.game_data.inventory.push(InventoryItem::new(GiftCard::new("Target", 50, 42, 5), 1));
+        app.game_data.inventory.push(InventoryItem::new(GiftCard::new("iTunes", 15, 12, 12), 1));
+        assert_eq!(app.inventory_view(), vec![0, 1, 2]);
+        app.toggle_inventory_expiry_sort();
+        assert_eq!(app.inventory_view(), vec![1, 2, 0]);
+        app.cycle_inventory_filter();
+        assert_eq!(app.inventory_view(), vec![1, 2]);
+        app.cycle_inventory_filter();
+        assert_eq!(app.inventory_view(), vec![1]);
+
+        // Selling acts on the listed row, not the raw inventory position
+        app.sell_inventory_item();
+        assert!(app.game_data.inventory.iter().all(|item| item.card.retailer != "Target"));
+        assert_eq!(app.game_data.inventory.len(), 2);
+    }
 
-fn draw_achievements_screen(f: &mut Frame, app: &App) {
-    let size = f.area();
-    
-    // Create layout: Header, Main content (left unlocked, right in-progress), Footer
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(0),    // Main content
-            Constraint::Length(3), // Footer
-        ])
-        .split(size);
+    #[test]
+    fn test_storage_capacity_and_upgrades() {
+        let mut app = App::new(Some(18));
+        app.screen = Screen::Market;
+        app.game_data.cash = 100_000;
+        app.game_data.inventory.clear();
+        app.game_data.inventory.push(InventoryItem::new(GiftCard::new("Amazon", 25, 20, 60), 95));
+        assert_eq!(app.game_data.storage_display(), "95/100 (95%)");
+
+        // Purchases that don't fit are refused outright
+        let amazon = app.game_data.market.listing_index("Amazon", 25).unwrap();
+        app.selected_menu_item = amazon;
+        app.purchase_quantity = 10;
+        app.purchase_from_market();
+        assert_eq!(app.game_data.cash, 100_000);
+        assert_eq!(app.game_data.inventory_count(), 95);
+        app.add_to_cart();
+        app.checkout_cart();
+        assert_eq!(app.game_data.cash, 100_000);
+        assert_eq!(app.cart.len(), 1);
+
+        // An upgrade makes room but costs money up front and every day after
+        let tier = &STORAGE_TIERS[1];
+        assert!(app.game_data.upgrade_storage());
+        assert_eq!(app.game_data.storage_capacity(), tier.capacity);
+        assert_eq!(app.game_data.cash, 100_000 - tier.upgrade_cost);
+        app.checkout_cart();
+        assert_eq!(app.game_data.inventory_count(), 105);
+
+        app.game_data.customer_orders.clear();
+        app.game_data.process_daily_events();
+        let expenses = &app.game_data.analytics.expenses;
+        assert_eq!(expenses.get(&ExpenseCategory::StorageUpgrades), Some(&tier.upgrade_cost));
+        assert_eq!(expenses.get(&ExpenseCategory::StorageUpkeep), Some(&tier.daily_upkeep));
+
+        // No upgrade without the cash for it, and none past the top tier
+        app.game_data.cash = 0;
+        assert!(!app.game_data.upgrade_storage());
+        assert_eq!(app.game_data.storage_level, 1);
+        app.game_data.cash = 1_000_000;
+        while app.game_data.upgrade_storage() {}
+        assert_eq!(app.game_data.storage_level, STORAGE_TIERS.len() - 1);
+    }
 
-    // Header with achievement stats
-    let total_achievements = app.game_data.achievements.achievements.len();
-    let unlocked_count = app.game_data.achievements.total_unlocked;
-    let total_rewards = app.game_data.achievements.calculate_total_rewards();
-    
-    let completion_percentage = if total_achievements == 0 {
-        0.0
-    } else {
-        (unlocked_count as f32 / total_achievements as f32) * 100.0
-    };
-    
-    let header_text = format!(
-        "Achievements: {}/{}    Total Rewards: ${}    Completion: {:.1}%",
-        unlocked_count,
-        total_achievements,
-        total_rewards,
-        completion_percentage
-    );
-    
-    let header = Paragraph::new(header_text)
-        .block(Block::default()
-            .title("Achievement Gallery")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Green))
-        .alignment(Alignment::Center);
-    
-    f.render_widget(header, chunks[0]);
+    #[test]
+    fn test_operating_expenses_schedule() {
+        let mut game = quiet_game(19);
 
-    // Main content area split into two columns
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(50), // Unlocked achievements
-            Constraint::Percentage(50), // In-progress achievements
-        ])
-        .split(chunks[1]);
+        // Wages every day, rent when a new week starts, fees on the previous day's sales
+        *game.analytics.daily_revenues.last_mut().unwrap() = 500;
+        game.day = 8;
+        let due = game.operating_expenses_due();
+        assert!(due.contains(&(ExpenseCategory::Rent, 70)));
+        assert!(due.contains(&(ExpenseCategory::Wages, 5)));
+        assert!(due.contains(&(ExpenseCategory::PaymentFees, 10)));
+        assert!(!due.iter().any(|(category, _)| *category == ExpenseCategory::StorageUpkeep)); // Back room is free
 
-    // Left column: Unlocked Achievements
-    let unlocked = app.game_data.achievements.get_unlocked_achievements();
-    let unlocked_items: Vec<ListItem> = if unlocked.is_empty() {
-        vec![ListItem::new(Line::from(Span::styled(
-            "No achievements unlocked yet!",
-            Style::default().fg(Color::Gray)
-        )))]
-    } else {
-        unlocked.iter().map(|achievement| {
-            let unlock_day = achievement.unlock_date.unwrap_or(0);
-            let lines = vec![
-                Line::from(Span::styled(
-                    format!("[*] {} (+${})", achievement.name, achievement.reward_cash),
-                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-                )),
-                Line::from(Span::styled(
-                    format!("    {}", achievement.description),
-                    Style::default().fg(Color::Yellow)
-                )),
-                Line::from(Span::styled(
-                    format!("    Unlocked: Day {}", unlock_day),
-                    Style::default().fg(Color::Yellow)
-                )),
-            ];
-            
-            ListItem::new(Text::from(lines))
-        }).collect()
-    };
+        // Mid-week days only pay wages; harder games pay more
+        *game.analytics.daily_revenues.last_mut().unwrap() = 0;
+        game.day = 9;
+        game.difficulty = Difficulty::Hard;
+        assert_eq!(game.operating_expenses_due(), vec![(ExpenseCategory::Wages, 6)]);
+    }
 
-    let unlocked_list = List::new(unlocked_items)
-        .block(Block::default()
-            .title(format!("* Unlocked ({}/{})", unlocked_count, total_achievements))
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::White));
+    #[test]
+    fn test_operating_expenses_charged() {
+        let mut game = quiet_game(19);
+        game.inventory.clear();
+        game.cash = 1000;
+        game.day_start_cash = Some(1000);
+        *game.analytics.daily_revenues.last_mut().unwrap() = 500;
+        game.day = 8;
+
+        game.process_daily_events();
+        assert_eq!(game.cash, 1000 - 85);
+        assert_eq!(game.analytics.total_expenses(), 85);
+        assert_eq!(game.analytics.expenses.get(&ExpenseCategory::Rent), Some(&70));
+        assert_eq!(game.analytics.daily_cash_flow, vec![-85]);
+        assert_eq!(game.analytics.total_profit(), -85);
+    }
 
-    f.render_widget(unlocked_list, main_chunks[0]);
+    #[test]
+    fn test_unpaid_expenses() {
+        // Bills we can't cover are paid in part and the rest is on the books as unpaid
+        let mut game = quiet_game(19);
+        game.inventory.clear();
+        game.difficulty = Difficulty::Hard;
+        game.day = 9;
+        game.cash = 2;
+        game.day_start_cash = Some(2);
+        game.process_daily_events();
+        assert_eq!(game.cash, 0);
+        assert_eq!(game.analytics.unpaid_expenses, 4);
+        assert_eq!(game.analytics.recent_cash_flow(7), -2);
+    }
 
-    // Right column: In-Progress Achievements
-    let in_progress = app.game_data.achievements.get_in_progress_achievements();
-    let mut remaining_achievements: Vec<&Achievement> = app.game_data.achievements.achievements.iter()
-        .filter(|a| !a.unlocked && a.progress == 0)
-        .take(5) // Show top 5 remaining
-        .collect();
+    #[test]
+    fn test_victory_needs_every_active_goal() {
+        // Only active goals count toward a win, but every goal reached is recorded
+        let mut game = quiet_game(20);
+        game.toggle_goal(GameGoal::Reputation);
+        assert_eq!(game.active_goals, vec![GameGoal::NetWorth, GameGoal::Reputation]);
+        game.cash = NET_WORTH_GOAL;
+        game.process_daily_events();
+        assert_eq!(game.goal_reached(GameGoal::NetWorth), Some(1));
+        assert!(game.game_over.is_none());
+        game.toggle_goal(GameGoal::Reputation);
+        game.process_daily_events();
+        assert_eq!(game.game_over.as_ref().map(|over| over.outcome), Some(GameOutcome::Victory));
+    }
 
-    // Combine in-progress and some remaining achievements
-    let mut progress_items: Vec<ListItem> = Vec::new();
-    
-    // Add in-progress achievements first
-    for achievement in in_progress {
-        let progress_bar_length = ((achievement.progress_percentage() / 100.0 * 20.0) as usize).min(20);
-        let progress_bar = "=".repeat(progress_bar_length) + &"-".repeat(20_usize.saturating_sub(progress_bar_length));
-        
-        let lines = vec![
-            Line::from(Span::styled(
-                format!("[>] {} ({}/{})", achievement.name, achievement.progress, achievement.target),
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
-            )),
-            Line::from(Span::styled(
-                format!("    {}", achievement.description),
-                Style::default().fg(Color::Cyan)
-            )),
-            Line::from(Span::styled(
-                format!("    [{}] {:.1}%", progress_bar, achievement.progress_percentage()),
-                Style::default().fg(Color::Cyan)
-            )),
-        ];
-        
-        progress_items.push(ListItem::new(Text::from(lines)));
+    #[test]
+    fn test_bankruptcy() {
+        // Broke, losing money and nothing to sell
+        let mut game = quiet_game(20);
+        game.inventory.clear();
+        game.cash = 3;
+        game.day_start_cash = Some(3);
+        game.process_daily_events();
+        assert_eq!(game.game_over.as_ref().map(|over| over.outcome), Some(GameOutcome::Bankruptcy));
     }
 
-    // Add some locked achievements (only if we have room for more)
-    let remaining_slots = if progress_items.len() >= 5 { 0 } else { 5 - progress_items.len() };
-    remaining_achievements.truncate(remaining_slots);
-    for achievement in remaining_achievements {
-        let lines = vec![
-            Line::from(Span::styled(
-                format!("[-] {} (Reward: ${})", achievement.name, achievement.reward_cash),
-                Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD)
-            )),
-            Line::from(Span::styled(
-                format!("    {}", achievement.description),
-                Style::default().fg(Color::Gray)
-            )),
-            Line::from(Span::styled(
-                format!("    Progress: {}/{}", achievement.progress, achievement.target),
-                Style::default().fg(Color::Gray)
-            )),
-        ];
-        
-        progress_items.push(ListItem::new(Text::from(lines)));
+    #[test]
+    fn test_reputation_loss() {
+        // A month below two stars
+        let mut game = quiet_game(20);
+        game.reputation = 1;
+        game.low_reputation_days = REPUTATION_LOSS_DAYS - 2;
+        game.process_daily_events();
+        assert!(game.game_over.is_none());
+        game.process_daily_events();
+        assert_eq!(game.game_over.as_ref().map(|over| over.outcome), Some(GameOutcome::ReputationLoss));
     }
 
-    if progress_items.is_empty() {
-        let lines = vec![
-            Line::from(Span::styled(
-                "All achievements unlocked!",
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-            )),
-            Line::from(Span::styled(
-                "Congratulations!",
-                Style::default().fg(Color::Green)
-            )),
-        ];
-        progress_items.push(ListItem::new(Text::from(lines)));
+    #[test]
+    fn test_inventory_crisis() {
+        // Half the stock expiring within 30 days
+        let mut game = quiet_game(20);
+        let held = game.inventory_count();
+        game.analytics.daily_expired_cards = vec![held, 0];
+        game.process_daily_events();
+        assert_eq!(game.game_over.as_ref().map(|over| over.outcome), Some(GameOutcome::InventoryCrisis));
     }
 
-    let progress_list = List::new(progress_items)
-        .block(Block::default()
-            .title("> Progress & Locked")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::White));
+    #[test]
+    fn test_game_over_stops_the_clock() {
+        // A finished run stops the clock and shows the summary
+        let mut app = App::new(Some(20));
+        app.start_new_game(Difficulty::Normal);
+        app.game_data.game_over = Some(GameOver { outcome: GameOutcome::Bankruptcy, day: 1 });
+        app.check_for_active_events();
+        assert!(matches!(app.screen, Screen::GameOver));
+        app.last_time_update = Instant::now() - app.game_speed;
+        app.update_time();
+        assert_eq!((app.game_data.hour, app.game_data.minute), (9, 0));
+        app.go_back();
+        assert!(matches!(app.screen, Screen::MainMenu));
+    }
 
-    f.render_widget(progress_list, main_chunks[1]);
+    #[test]
+    fn test_competitors_buy_from_shared_market() {
+        let mut game = quiet_game(21);
+        assert_eq!(game.competitors.len(), RIVALS.len());
 
-    // Footer with controls
-    let footer_text = "View your accomplishments and track progress • Esc Back";
-    let footer = Paragraph::new(footer_text)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::Gray))
-        .alignment(Alignment::Center);
+        // Rivals restock from the shared wholesale market
+        let market_stock: u32 = game.market.listings.iter().map(|listing| listing.stock).sum();
+        let rival_cash: u32 = game.competitors.iter().map(|competitor| competitor.cash).sum();
+        game.process_competitors();
+        let bought: u32 = game.competitors.iter().map(|competitor| competitor.card_count()).sum();
+        assert!(bought > 0);
+        assert_eq!(game.market.listings.iter().map(|listing| listing.stock).sum::<u32>(), market_stock - bought);
+        assert!(game.competitors.iter().map(|competitor| competitor.cash).sum::<u32>() < rival_cash);
+        assert!(game.market.lifetime_units.is_empty()); // Not our relationship
+    }
 
-    f.render_widget(footer, chunks[2]);
-}
+    #[test]
+    fn test_competitor_restock_pricing() {
+        // Rivals pay the same market price we do, difficulty and price events included, but no relationship discount
+        let mut game = quiet_game(21);
+        game.competitors.truncate(1);
+        game.market.listings.truncate(1);
+        game.difficulty = Difficulty::Hard;
+        game.random_events.temp_modifiers.push(TempModifier {
+            name: "Shortage".to_string(),
+            description: String::new(),
+            price_multiplier: 1.5,
+            demand_multiplier: 1.0,
+            reputation_protection: false,
+            remaining_days: 3,
+        });
+        let retailer = game.market.listings[0].retailer.clone();
+        game.market.lifetime_units.insert(retailer, 1000);
+        let listing = game.market.listings[0].clone();
+        let unit_price = (listing.base_cost as f32 * game.market_price_multiplier(&listing)).round() as u32;
+        assert!(unit_price > game.wholesale_price(&listing));
+        assert!(unit_price as f32 > listing.base_cost as f32 * 1.5);
 
-fn draw_placeholder(f: &mut Frame, title: &str, description: &str) {
-    let size = f.area();
+        let cash = game.competitors[0].cash;
+        game.process_competitors();
+        let bought = game.competitors[0].card_count();
+        assert!(bought > 0);
+        assert_eq!(game.competitors[0].cash, cash - COMPETITOR_DAILY_OVERHEAD - bought * unit_price);
+    }
 
-    let content = format!("{}\n\n{}\n\nPress Esc to return to dashboard", title, description);
-    let placeholder = Paragraph::new(content)
-        .block(Block::default()
-            .title(format!("Gift Card Empire - {}", title))
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White)))
-        .style(Style::default().fg(Color::White))
-        .alignment(Alignment::Center);
+    #[test]
+    fn test_competitor_order_pricing() {
+        let game = quiet_game(21);
+
+        // A rival can only take orders it can fill, and bargain hunters follow the lower price
+        let order = CustomerOrder::new(1, "Alice", "Amazon", 25, 2, 22, 3, OrderPriority::Medium);
+        let mut customer = game.customers[0].clone();
+        customer.price_sensitivity = 1.0;
+        let mut rival = game.competitors[0].clone();
+        rival.stock.clear();
+        assert_eq!(rival.win_chance(&order, &customer, 3), 0.0);
+        rival.add_stock("Amazon", 25, 5);
+        assert_eq!(rival.asking_total(&order), 44); // $22 a card at 88% of face value
+        assert!(rival.win_chance(&order, &customer, 3) > 0.0);
+        rival.reputation = 4;
+        rival.price_war_days = 1;
+        assert_eq!(rival.asking_total(&order), 42);
+        let paid = rival.fill_order(&order);
+        assert_eq!((paid, rival.stock_of("Amazon", 25), rival.orders_won), (42, 3, 1));
+    }
 
-    f.render_widget(placeholder, size);
-}
+    #[test]
+    fn test_competitors_win_orders() {
+        // Well-stocked rivals win some new orders before we ever see them
+        let mut game = quiet_game(21);
+        for competitor in &mut game.competitors {
+            for product in CARD_CATALOG {
+                competitor.add_stock(product.retailer, product.denomination, 50);
+            }
+        }
+        for _ in 0..40 {
+            game.generate_random_order();
+        }
+        let lost = game.competitor_orders_won();
+        assert!(lost > 0);
+        assert_eq!(game.customer_orders.len() as u32 + lost, 40);
+        let lost_in_history = game.customers.iter()
+            .flat_map(|customer| &customer.history)
+            .filter(|entry| entry.outcome == OrderOutcome::Lost)
+            .count() as u32;
+        assert_eq!(lost_in_history, lost);
+        assert!(!game.analytics.market_today.rival_sales.is_empty()); // Counted in the market share model
+    }
 
-fn draw_random_event(f: &mut Frame, app: &App) {
-    let size = f.area();
-    
-    if let Some(event) = &app.game_data.random_events.active_event {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(2)
-            .constraints([
-                Constraint::Length(3),  // Header
-                Constraint::Min(6),     // Event content
-                Constraint::Length(3),  // Instructions
-            ])
-            .split(size);
-        
-        // Header
-        let header = Paragraph::new("🎲 Random Event - Choose Your Response")
-            .block(Block::default()
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::Yellow)))
-            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
-            .alignment(Alignment::Center);
-        f.render_widget(header, chunks[0]);
-        
-        // Event content
-        let event_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(4),  // Title and description
-                Constraint::Min(2),     // Choices
-            ])
-            .split(chunks[1]);
-        
-        // Event title and description
-        let event_content = format!("{}\n\n{}", event.title, event.description);
-        let event_text = Paragraph::new(event_content)
-            .block(Block::default()
-                .title("Event Details")
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White)))
-            .style(Style::default().fg(Color::White))
-            .wrap(Wrap { trim: true });
-        f.render_widget(event_text, event_chunks[0]);
-        
-        // Choices
-        let choice_items: Vec<ListItem> = event.get_choices().iter().enumerate()
-            .map(|(i, choice)| {
-                let style = if i == app.selected_menu_item {
-                    Style::default().fg(Color::Black).bg(Color::Yellow)
-                } else {
-                    Style::default().fg(Color::White)
-                };
-                ListItem::new(choice.to_string()).style(style)
-            })
-            .collect();
-        
-        let choices_list = List::new(choice_items)
-            .block(Block::default()
-                .title("Your Choices")
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White)))
-            .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
-        
-        f.render_widget(choices_list, event_chunks[1]);
-        
-        // Instructions
-        let instructions = Paragraph::new("↑/↓: Navigate  Enter: Select Choice  Time remaining: ⏰")
-            .block(Block::default()
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::Gray)))
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center);
-        f.render_widget(instructions, chunks[2]);
-        
-    } else {
-        // No active event - this shouldn't happen but handle gracefully
-        let content = "No active random event.\n\nReturning to dashboard...";
-        let placeholder = Paragraph::new(content)
-            .block(Block::default()
-                .title("Random Events")
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White)))
-            .style(Style::default().fg(Color::White))
-            .alignment(Alignment::Center);
-        f.render_widget(placeholder, size);
+    #[test]
+    fn test_competitor_price_war() {
+        // Discounters and balanced rivals join a price war; premium ones hold their prices
+        let mut game = quiet_game(21);
+        game.random_events.temp_modifiers.push(TempModifier {
+            name: PRICE_WAR_MODIFIER.to_string(),
+            description: "Cheaper purchases but lower demand".to_string(),
+            price_multiplier: 0.85,
+            demand_multiplier: 0.8,
+            reputation_protection: false,
+            remaining_days: 7,
+        });
+        game.process_competitors();
+        let in_war: Vec<bool> = game.competitors.iter().map(|competitor| competitor.in_price_war()).collect();
+        assert_eq!(in_war, vec![true, true, false]);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    // Run rival turns until `done` holds, up to `days` of them
+    fn run_competitors_until(game: &mut GameData, days: u32, done: impl Fn(&GameData) -> bool) -> bool {
+        for _ in 0..days {
+            game.process_competitors();
+            if done(game) {
+                return true;
+            }
+        }
+        false
+    }
 
     #[test]
-    fn test_game_data_initialization() {
-        let game_data = GameData::new();
-        
-        assert_eq!(game_data.cash, 5000);
-        assert_eq!(game_data.reputation, 3);
-        assert_eq!(game_data.day, 1);
-        assert_eq!(game_data.hour, 9);
-        assert_eq!(game_data.minute, 0);
-        assert!(!game_data.inventory.is_empty());
-        assert!(!game_data.customer_orders.is_empty());
+    fn test_undercutting_provokes_price_war() {
+        // Asking less than the rivals do starts a price war, and keeping it up escalates it
+        let mut game = quiet_game(21);
+        assert!(!run_competitors_until(&mut game, 30, GameData::price_war_active));
+        let card_types: Vec<(String, u32)> = game.inventory.iter().map(|item| (item.card.retailer.clone(), item.card.denomination)).collect();
+        for (retailer, denomination) in card_types {
+            game.set_retail_price(&retailer, denomination, denomination * 4 / 5);
+        }
+        assert!(game.average_ask_ratio().unwrap() < PricingStrategy::Discounter.price_ratio(false));
+        assert!(run_competitors_until(&mut game, 100, GameData::price_war_active));
+        assert!(game.recent_activities.iter().any(|activity| activity.contains("started a price war")));
+        let premium = game.competitors.iter().find(|competitor| competitor.strategy == PricingStrategy::Premium).unwrap();
+        assert!(!premium.in_price_war());
+
+        let war_days = |game: &GameData| game.random_events.temp_modifiers.iter()
+            .find(|modifier| modifier.name == PRICE_WAR_MODIFIER)
+            .map_or(0, |modifier| modifier.remaining_days);
+        game.random_events.temp_modifiers.iter_mut()
+            .filter(|modifier| modifier.name == PRICE_WAR_MODIFIER)
+            .for_each(|modifier| modifier.remaining_days = 1);
+        assert!(run_competitors_until(&mut game, 100, |game| war_days(game) == PRICE_WAR_DAYS));
+        assert!(game.recent_activities.iter().any(|activity| activity.contains("escalated the price war")));
     }
 
     #[test]
-    fn test_purchase_mechanics() {
-        let mut game_data = GameData::new();
-        let initial_cash = game_data.cash;
-        let initial_inventory_count = game_data.inventory.len();
-        
-        // Test successful purchase
-        assert!(game_data.can_afford(100));
-        assert!(game_data.spend_money(100));
-        assert_eq!(game_data.cash, initial_cash - 100);
-        
-        // Test insufficient funds
-        assert!(!game_data.can_afford(10000));
-        assert!(!game_data.spend_money(10000));
-        assert_eq!(game_data.cash, initial_cash - 100); // Should not change
-        
-        // Test inventory addition
-        let card = GiftCard::new("TestCard", 25, 20, 30);
-        game_data.add_to_inventory(card, 2);
-        assert_eq!(game_data.inventory.len(), initial_inventory_count + 1);
+    fn test_market_share_provokes_price_war() {
+        // Taking a big slice of the market makes the rivals fight back, but premium shops never start one
+        let mut game = quiet_game(21);
+        for day in 1..=MARKET_SHARE_MIN_DAYS as u32 {
+            let mut market_day = MarketDay { day, ..Default::default() };
+            market_day.demand.insert("Amazon".to_string(), 10);
+            market_day.our_sales.insert("Amazon".to_string(), 5);
+            game.analytics.market_history.push(market_day);
+        }
+        assert!(game.market_share() >= PRICE_WAR_SHARE_TRIGGER);
+
+        let mut premium_only = quiet_game(21);
+        premium_only.analytics.market_history = game.analytics.market_history.clone();
+        premium_only.competitors.retain(|competitor| competitor.strategy == PricingStrategy::Premium);
+        assert!(!run_competitors_until(&mut premium_only, 100, GameData::price_war_active));
+
+        assert!(run_competitors_until(&mut game, 100, GameData::price_war_active));
     }
 
     #[test]
-    fn test_time_progression() {
-        let mut game_data = GameData::new();
-        let initial_day = game_data.day;
-        let initial_hour = game_data.hour;
-        let initial_minute = game_data.minute;
-        
-        // Test minute advancement
-        game_data.advance_time(30);
-        assert_eq!(game_data.minute, initial_minute + 30);
-        
-        // Test hour rollover
-        game_data.advance_time(35); // Should go to next hour
-        assert_eq!(game_data.hour, initial_hour + 1);
-        assert_eq!(game_data.minute, 5); // 30 + 35 = 65, 65 % 60 = 5
-        
-        // Test day advancement (would need to advance many hours)
-        game_data.hour = 23;
-        game_data.minute = 50;
-        game_data.advance_time(20); // Should trigger new day
-        assert_eq!(game_data.day, initial_day + 1);
-        assert_eq!(game_data.hour, 0);
-        assert_eq!(game_data.minute, 10);
+    fn test_market_demand() {
+        let mut game = quiet_game(22);
+
+        // The town's market is sized from the season and events, scaled from normal demand
+        let autumn = game.local_market_size("Amazon");
+        assert!(autumn > 0);
+        game.market_conditions.current_season = Season::Winter;
+        game.market_conditions.base_demand_modifier = Season::Winter.demand_modifier();
+        let winter = game.local_market_size("Amazon");
+        assert!(winter > autumn);
+        let target = game.local_market_size("Target");
+        game.market_conditions.active_events.push(MarketEvent::new("Rush", "", Some("Target"), 1.0, 2.0, 3));
+        assert!(game.local_market_size("Target") > target);
+        game.market_conditions.active_events.clear();
+
+        // Walk-in traffic follows the catalog weights and demand too
+        assert!(game.walk_in_demand_chance("Amazon", 25) > game.walk_in_demand_chance("Amazon", 200));
+
+        // Orders and walk-ins we see are part of that market, and only raise it when more turn up
+        game.analytics.market_today = MarketDay::default();
+        game.analytics.record_order_demand("Amazon", winter + 3);
+        game.analytics.record_walk_in_demand("Amazon");
+        game.analytics.record_walk_in_demand("Target");
+        let market_size = BTreeMap::from([("Amazon".to_string(), winter), ("Target".to_string(), 5)]);
+        game.analytics.close_market_day(1, market_size);
+        let market_day = game.analytics.market_history.last().unwrap();
+        assert_eq!(market_day.demand.get("Amazon"), Some(&(winter + 4)));
+        assert_eq!(market_day.demand.get("Target"), Some(&5));
+
+        // A day with nobody seen still has its market, and orders placed at the rollover belong to the new day
+        game.reputation = 5;
+        game.analytics.market_history.clear();
+        let town_size: u32 = RETAILERS.iter().map(|retailer| game.local_market_size(retailer.id)).sum();
+        game.process_daily_events();
+        let market_day = game.analytics.market_history.last().unwrap();
+        assert_eq!(market_day.demand.get("Amazon"), Some(&winter));
+        assert_eq!(market_day.total_demand(), town_size);
+        assert!(game.analytics.market_today.ordered.values().sum::<u32>() > 0);
     }
 
     #[test]
-    fn test_customer_order_system() {
-        let mut game_data = GameData::new();
-        let initial_order_count = game_data.customer_orders.len();
-        
-        // Test order generation
-        game_data.generate_random_order();
-        assert_eq!(game_data.customer_orders.len(), initial_order_count + 1);
-        
-        // Test order properties
-        if let Some(order) = game_data.customer_orders.back() {
-            assert!(order.id >= 1000);
-            assert!(!order.customer_name.is_empty());
-            assert!(!order.retailer.is_empty());
-            assert!(order.denomination > 0);
-            assert!(order.quantity > 0);
-            assert!(order.offered_price_per_card > 0);
-            assert!(order.deadline_days > 0);
-        }
+    fn test_market_share_accounting() {
+        let mut game = quiet_game(22);
+
+        // Our sales and the rivals' together can't exceed what customers asked for
+        game.analytics.market_today = MarketDay::default();
+        game.analytics.record_order_demand("Amazon", 5);
+        game.analytics.record_order_demand("Walmart", 2);
+        game.analytics.record_rival_sale("CardHub", "Amazon", 3);
+        game.analytics.record_market_sale("Amazon", 4);
+        game.analytics.record_market_sale("Walmart", 50);
+        game.analytics.close_market_day(1, BTreeMap::new());
+        assert_eq!(game.analytics.market_history.last().unwrap().total_demand(), 7);
+        assert_eq!(game.analytics.retailer_market_share("Amazon", MARKET_SHARE_WINDOW_DAYS), (2.0 / 5.0, 5));
+        assert_eq!(game.analytics.retailer_market_share("Walmart", MARKET_SHARE_WINDOW_DAYS), (1.0, 2));
+        assert_eq!(game.market_share(), 4.0 / 7.0);
+        let card_hub = game.competitors.iter().find(|competitor| competitor.name == "CardHub").unwrap();
+        assert_eq!(game.competitor_share(card_hub), 3.0 / 7.0);
+
+        // With no customers there is no market to hold, however much we sell
+        game.analytics.market_history.clear();
+        game.analytics.record_market_sale("Amazon", 20);
+        game.analytics.close_market_day(2, BTreeMap::new());
+        assert_eq!(game.market_share(), 0.0);
     }
 
     #[test]
-    fn test_gift_card_pricing() {
-        let amazon_card = GiftCard::new("Amazon", 25, 20, 30);
-        assert_eq!(amazon_card.market_value(), 32); // 25 * 1.30 = 32.5 -> 32
-        assert_eq!(amazon_card.potential_profit(), 12); // 32 - 20 = 12
-        
-        let starbucks_card = GiftCard::new("Starbucks", 10, 8, 60);
-        assert_eq!(starbucks_card.market_value(), 12); // 10 * 1.25 = 12.5 -> 12
-        assert_eq!(starbucks_card.potential_profit(), 4); // 12 - 8 = 4
-        
-        // Test expiration detection
-        let expiring_card = GiftCard::new("Target", 50, 42, 10);
-        assert!(expiring_card.is_expiring_soon()); // <= 15 days
-        
-        let fresh_card = GiftCard::new("iTunes", 15, 12, 30);
-        assert!(!fresh_card.is_expiring_soon()); // > 15 days
+    fn test_order_sales_count_toward_market_share() {
+        // An order placed at the day rollover and filled a day later counts toward our share
+        let mut game = quiet_game(22);
+        game.competitors.clear();
+        game.reputation = 5;
+        for _ in 0..10 {
+            game.process_daily_events();
+            if !game.customer_orders.is_empty() {
+                break;
+            }
+        }
+        let order = game.customer_orders[0].clone();
+        assert_eq!(game.analytics.market_today.ordered.values().sum::<u32>(), order.card_count());
+        for line in &order.requested_cards {
+            game.add_to_inventory(GiftCard::new(&line.retailer, line.denomination, 1, game.day + 60), line.quantity);
+        }
+        game.process_daily_events();
+        let order_index = game.customer_orders.iter().position(|open| open.id == order.id).unwrap();
+        assert!(game.fulfill_order(order_index));
+        game.process_daily_events();
+
+        let history = &game.analytics.market_history;
+        let placed = &history[history.len() - 2];
+        let filled = &history[history.len() - 1];
+        assert_eq!(placed.ordered.values().sum::<u32>(), order.card_count());
+        assert_eq!(filled.total_our_sales(), order.card_count());
+        assert!(game.market_share() > 0.0);
+        let retailer = &order.requested_cards[0].retailer;
+        assert!(game.analytics.retailer_market_share(retailer, MARKET_SHARE_WINDOW_DAYS).0 > 0.0);
     }
 
-    #[test] 
-    fn test_app_initialization() {
-        let app = App::new();
-        
-        assert!(matches!(app.screen, Screen::MainMenu));
-        assert_eq!(app.selected_menu_item, 0);
-        assert!(!app.should_quit);
-        assert!(!app.paused);
-        assert_eq!(app.game_data.cash, 5000);
+    #[test]
+    fn test_liquidation_is_not_a_market_sale() {
+        // Dumping stock back to the trade earns cash but no market share
+        let mut app = App::new(Some(22));
+        app.screen = Screen::Inventory;
+        let cash = app.game_data.cash;
+        while !app.game_data.inventory.is_empty() {
+            app.sell_inventory_item();
+        }
+        assert!(app.game_data.cash > cash);
+        assert_eq!(app.game_data.analytics.market_today.total_our_sales(), 0);
     }
 
     #[test]
-    fn test_order_fulfillment() {
-        let mut game_data = GameData::new();
-        let initial_cash = game_data.cash;
-        
-        // Find existing Amazon inventory to know the starting amount
-        let initial_amazon_quantity = game_data.inventory.iter()
-            .find(|item| item.card.retailer == "Amazon" && item.card.denomination == 25)
-            .map(|item| item.quantity)
-            .unwrap_or(0);
-        
-        // Create a test order
-        let order = CustomerOrder::new(
-            9999,
-            "TestCustomer",
-            "Amazon", 
-            25,
-            2,
-            30, // $30 per card
-            3,
-            OrderPriority::High
-        );
-        
-        game_data.customer_orders.push_back(order.clone());
-        let order_count = game_data.customer_orders.len();
-        
-        // Test fulfillment capability (should work with sample inventory)
-        assert!(game_data.can_fulfill_order(&order));
-        
-        // Fulfill the order
-        assert!(game_data.fulfill_order(order_count - 1));
-        
-        // Verify results
-        assert_eq!(game_data.customer_orders.len(), order_count - 1); // Order removed
-        assert_eq!(game_data.cash, initial_cash + 60); // 2 cards * $30 = $60
-        
-        // Check inventory was reduced by 2
-        let final_amazon_quantity = game_data.inventory.iter()
-            .find(|item| item.card.retailer == "Amazon" && item.card.denomination == 25)
-            .map(|item| item.quantity)
-            .unwrap_or(0);
-        assert_eq!(final_amazon_quantity, initial_amazon_quantity - 2);
+    fn test_market_share_achievements() {
+        // A week of holding the market meets the goal; the achievements want the full 30 days
+        let mut game = quiet_game(22);
+        let hold_market = |game: &mut GameData, days: usize| {
+            for _ in 0..days {
+                for retailer in RETAILERS {
+                    game.analytics.record_order_demand(retailer.id, 10);
+                    game.analytics.record_market_sale(retailer.id, 10);
+                }
+                game.process_daily_events();
+            }
+        };
+        hold_market(&mut game, MARKET_SHARE_MIN_DAYS);
+        assert!(game.market_share() > MARKET_SHARE_GOAL);
+        assert!(game.goal_reached(GameGoal::MarketShare).is_some());
+        assert!(!achievement_unlocked(&game, AchievementType::MarketPlayer));
+        hold_market(&mut game, MARKET_SHARE_WINDOW_DAYS - MARKET_SHARE_MIN_DAYS);
+        assert!(achievement_unlocked(&game, AchievementType::MarketPlayer));
+        assert!(achievement_unlocked(&game, AchievementType::MarketLeader));
+
+        // Older saves pick up achievements added since
+        game.achievements.achievements.retain(|achievement| achievement.achievement_type != AchievementType::MarketLeader);
+        game.achievements.add_missing_achievements();
+        assert_eq!(game.achievements.achievements.len(), AchievementTracker::new().achievements.len());
     }
 
     #[test]
-    fn test_order_fulfillment_failure() {
-        let mut game_data = GameData::new();
-        
-        // Create an order we can't fulfill
-        let order = CustomerOrder::new(
-            9999,
-            "TestCustomer", 
-            "NonExistent",
-            100,
-            5,
-            200,
-            3,
-            OrderPriority::High
-        );
-        
-        game_data.customer_orders.push_back(order.clone());
-        let order_count = game_data.customer_orders.len();
-        let initial_cash = game_data.cash;
-        
-        // Test that we can't fulfill it
-        assert!(!game_data.can_fulfill_order(&order));
-        
-        // Attempt fulfillment should fail
-        assert!(!game_data.fulfill_order(order_count - 1));
-        
-        // Order should still be there, cash unchanged
-        assert_eq!(game_data.customer_orders.len(), order_count);
-        assert_eq!(game_data.cash, initial_cash);
+    fn test_retail_price_limits() {
+        // Cards sell at face value until we set a price, within $1 to twice face value
+        let mut game = quiet_game(23);
+        assert_eq!(game.retail_price("Amazon", 25), 25);
+        game.set_retail_price("Amazon", 25, 500);
+        assert_eq!(game.retail_price("Amazon", 25), 50);
+        game.set_retail_price("Amazon", 25, 0);
+        assert_eq!(game.retail_price("Amazon", 25), 1);
     }
 
     #[test]
-    fn test_reputation_system() {
-        let mut game_data = GameData::new();
-        let initial_reputation = game_data.reputation;
-        
-        // Test reputation improvement
-        game_data.improve_reputation("order_fulfilled");
-        assert_eq!(game_data.reputation, initial_reputation + 1);
-        
-        // Test reputation decrease
-        game_data.decrease_reputation("order_expired");
-        assert_eq!(game_data.reputation, initial_reputation);
-        
-        // Test reputation bounds (can't go above 5)
-        game_data.reputation = 5;
-        game_data.improve_reputation("order_fulfilled");
-        assert_eq!(game_data.reputation, 5);
-        
-        // Test reputation bounds (can't go below 1)
-        game_data.reputation = 1;
-        game_data.decrease_reputation("order_expired");
-        assert_eq!(game_data.reputation, 1);
-        
-        // Test reputation descriptions
-        game_data.reputation = 5;
-        assert_eq!(game_data.reputation_description(), "Legendary");
-        game_data.reputation = 3;
-        assert_eq!(game_data.reputation_description(), "Good");
-        game_data.reputation = 1;
-        assert_eq!(game_data.reputation_description(), "Poor");
+    fn test_walk_in_chance() {
+        // Cheaper asks and better reputation sell faster
+        let mut game = quiet_game(23);
+        let card = game.inventory.iter().find(|item| item.card.retailer == "Amazon").unwrap().card.clone();
+        game.set_retail_price("Amazon", 25, card.market_value());
+        let at_market = game.walk_in_chance(&card);
+        game.set_retail_price("Amazon", 25, 45);
+        assert!(game.walk_in_chance(&card) < at_market);
+        game.set_retail_price("Amazon", 25, 22);
+        let bargain = game.walk_in_chance(&card);
+        assert!(bargain > at_market);
+        game.reputation = 5;
+        assert!(game.walk_in_chance(&card) > bargain);
+
+        // Even the best deal only sells to shoppers who came looking for the card
+        game.set_retail_price("Amazon", 25, 1);
+        assert_eq!(game.walk_in_purchase_chance(&card), 1.0);
+        assert_eq!(game.walk_in_chance(&card), game.walk_in_demand_chance("Amazon", 25));
     }
 
     #[test]
-    fn test_reputation_affects_pricing() {
-        let mut game_data = GameData::new();
-        
-        // Test different reputation levels affect order generation
-        // We'll test the pricing logic by examining the bonus calculation
-        
-        // High reputation (5 stars)
-        game_data.reputation = 5;
-        let denomination = 25;
-        let bonus_5_star = denomination / 3; // Should be 8
-        assert_eq!(bonus_5_star, 8);
-        
-        // Medium reputation (3 stars)  
-        game_data.reputation = 3;
-        let bonus_3_star = denomination / 5; // Should be 5
-        assert_eq!(bonus_3_star, 5);
-        
-        // Low reputation (1 star)
-        game_data.reputation = 1;
-        let bonus_1_star = 0; // Should be 0
-        assert_eq!(bonus_1_star, 0);
+    fn test_walk_in_sales() {
+        // A few days of trading hours sell cards at our price and count toward market share
+        let mut game = quiet_game(23);
+        game.set_retail_price("Amazon", 25, 22);
+        let cash_before = game.cash;
+        let cards_before = game.inventory_count();
+        for _ in 0..(3 * 24 * 3) {
+            game.advance_time(20);
+        }
+        let sold = game.analytics.walk_in_cards_sold;
+        assert!(sold > 0);
+        assert_eq!(game.inventory_count(), cards_before - sold);
+        assert_eq!(game.cash, cash_before + game.analytics.total_revenue - game.analytics.total_expenses());
+        assert_eq!(game.analytics.cards_sold, sold);
+        let market_sales: u32 = game.analytics.market_history.iter()
+            .chain(std::iter::once(&game.analytics.market_today))
+            .flat_map(|market_day| market_day.our_sales.values())
+            .sum();
+        assert!(market_sales > 0 && market_sales <= sold);
+        for market_day in game.analytics.market_history.iter().chain(std::iter::once(&game.analytics.market_today)) {
+            for (retailer, sold) in &market_day.our_sales {
+                assert!(*sold <= market_day.walk_ins.get(retailer).copied().unwrap_or(0));
+            }
+        }
     }
 
     #[test]
-    fn test_analytics_tracking() {
-        let mut game_data = GameData::new();
-        let initial_purchases = game_data.analytics.total_purchases;
-        let initial_revenue = game_data.analytics.total_revenue;
-        
-        // Test purchase tracking
-        game_data.analytics.record_purchase(100);
-        assert_eq!(game_data.analytics.total_purchases, initial_purchases + 100);
-        
-        // Test sale tracking
-        game_data.analytics.record_sale(150, 100, 2);
-        assert_eq!(game_data.analytics.total_revenue, initial_revenue + 150);
-        assert_eq!(game_data.analytics.orders_completed, 1);
-        assert_eq!(game_data.analytics.cards_sold, 2);
-        
-        // Test profit calculation
-        let total_profit = game_data.analytics.total_profit();
-        assert_eq!(total_profit, 150i32 - (initial_purchases + 100) as i32);
-        
-        // Test profit margin calculation
-        let avg_margin = game_data.analytics.average_profit_margin();
-        assert!(avg_margin > 0.0);
-        assert!(avg_margin < 100.0);
-        
-        // Test expired order tracking
-        game_data.analytics.record_expired_order();
-        assert_eq!(game_data.analytics.orders_expired, 1);
-        
-        // Test expired cards tracking
-        game_data.analytics.record_expired_cards(3);
-        assert_eq!(game_data.analytics.cards_expired, 3);
-        
-        // Test daily revenue tracking
-        let initial_days = game_data.analytics.daily_revenues.len();
-        game_data.analytics.start_new_day();
-        assert_eq!(game_data.analytics.daily_revenues.len(), initial_days + 1);
+    fn test_price_history_recording() {
+        let mut game = quiet_game(24);
+
+        // Every retailer starts with today's prices on record
+        assert_eq!(game.price_history.len(), RETAILERS.len());
+        let listing_index = game.market.listing_index("Amazon", 25).unwrap();
+        let opening_price = game.wholesale_price(&game.market.listings[listing_index]);
+        assert_eq!(game.price_history_for("Amazon", 25), vec![opening_price]);
+
+        // Each day adds a point
+        game.market.listings[listing_index].stock = 0; // Scarcity pushes the price up
+        game.day += 1;
+        game.process_daily_events();
+        let history = game.price_history_for("Amazon", 25);
+        assert_eq!(history.len(), 2);
+        assert!(history[1] > history[0]);
+        assert_eq!(game.demand_history_for("Amazon").len(), 2);
+        assert_eq!(price_range(&history), Some((history[0], (history[0] + history[1]) as f32 / 2.0, history[1])));
+        assert_eq!(price_range(&[]), None);
     }
 
     #[test]
-    fn test_save_load_functionality() {
-        use std::fs;
-        
-        let test_filename = "test_save.json";
-        
-        // Clean up any existing test file
-        let _ = fs::remove_file(test_filename);
-        
-        // Create test game data
-        let mut original_game_data = GameData::new();
-        original_game_data.cash = 12345;
-        original_game_data.reputation = 4;
-        original_game_data.day = 42;
-        original_game_data.hour = 15;
-        original_game_data.minute = 30;
-        original_game_data.recent_activities.push("Test activity".to_string());
-        
-        // Test save
-        let save_result = original_game_data.save_game(test_filename);
-        assert!(save_result.is_ok());
-        assert!(GameData::save_file_exists(test_filename));
-        
-        // Test load
-        let load_result = GameData::load_game(test_filename);
-        assert!(load_result.is_ok());
-        
-        let loaded_game_data = load_result.unwrap();
-        
-        // Verify data integrity
-        assert_eq!(loaded_game_data.cash, 12345);
-        assert_eq!(loaded_game_data.reputation, 4);
-        assert_eq!(loaded_game_data.day, 42);
-        assert_eq!(loaded_game_data.hour, 15);
-        assert_eq!(loaded_game_data.minute, 30);
-        assert!(loaded_game_data.recent_activities.contains(&"Test activity".to_string()));
-        
-        // Clean up test file
-        let _ = fs::remove_file(test_filename);
-        assert!(!GameData::save_file_exists(test_filename));
+    fn test_price_history_window() {
+        // The window keeps the last 30 days, and is saved with the game
+        let mut game = quiet_game(24);
+        for _ in 0..40 {
+            game.day += 1;
+            game.process_daily_events();
+        }
+        assert_eq!(game.price_history_for("Amazon", 25).len(), PRICE_HISTORY_DAYS);
+        assert_eq!(game.price_history["Amazon"].last().unwrap().day, game.day);
+
+        let json = serde_json::to_string(&game).unwrap();
+        let loaded: GameData = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.price_history_for("Amazon", 25), game.price_history_for("Amazon", 25));
+    }
+
+    // A quiet game with no market events, modifiers or stock, so forecasts only reflect what a test sets up
+    fn forecast_game(day: u32) -> GameData {
+        let mut game = quiet_game(25);
+        game.market_conditions.active_events.clear();
+        game.random_events.temp_modifiers.clear();
+        game.inventory.clear();
+        game.day = day;
+        game.market_conditions.update_season(day);
+        game
+    }
+
+    fn retailer_forecast(game: &GameData, retailer: &str) -> RetailerForecast {
+        game.forecast(FORECAST_DAYS).into_iter().find(|forecast| forecast.retailer == retailer).unwrap()
     }
 
     #[test]
-    fn test_seasonal_market_system() {
-        let mut game_data = GameData::new();
-        
-        // Test season progression
-        assert!(matches!(game_data.market_conditions.current_season, Season::Spring));
-        
-        // Test season changes
-        game_data.market_conditions.update_season(100); // Should be Summer
-        assert!(matches!(game_data.market_conditions.current_season, Season::Summer));
-        
-        game_data.market_conditions.update_season(200); // Should be Fall
-        assert!(matches!(game_data.market_conditions.current_season, Season::Fall));
-        
-        game_data.market_conditions.update_season(300); // Should be Winter
-        assert!(matches!(game_data.market_conditions.current_season, Season::Winter));
-        
-        // Test price multipliers
-        let amazon_multiplier = game_data.market_conditions.get_price_multiplier("Amazon");
-        assert!(amazon_multiplier > 1.0); // Winter should boost Amazon
-        
-        let starbucks_multiplier = game_data.market_conditions.get_price_multiplier("Starbucks"); 
-        assert!(starbucks_multiplier > 1.0); // Winter should boost Starbucks
-        
-        // Test demand multipliers
-        let demand_multiplier = game_data.market_conditions.get_demand_multiplier("Amazon");
-        assert!(demand_multiplier > 1.0); // Winter should increase demand
-        
-        // Test market event creation
-        let initial_events = game_data.market_conditions.active_events.len();
-        game_data.market_conditions.generate_random_event(42, &mut game_data.recent_activities);
-        assert_eq!(game_data.market_conditions.active_events.len(), initial_events + 1);
-        
-        // Test event affects pricing
-        let event = &game_data.market_conditions.active_events[0];
-        if let Some(retailer) = &event.retailer_affected {
-            let multiplier = game_data.market_conditions.get_price_multiplier(retailer);
-            // Should be different from base price due to event
-            assert_ne!(multiplier, 1.5); // 1.5 is winter Amazon base
+    fn test_forecast_season_boundary() {
+        // The season boundary lifts both expected orders and prices once it is crossed
+        let game = forecast_game(268); // Winter starts on day 271
+        assert_eq!(game.forecast(FORECAST_DAYS).len(), RETAILERS.len());
+        let amazon = retailer_forecast(&game, "Amazon");
+        assert_eq!(amazon.daily_orders.len(), FORECAST_DAYS as usize);
+        assert_eq!(amazon.daily_orders[0], amazon.daily_orders[1]);
+        assert!(amazon.daily_orders[2] > amazon.daily_orders[1]);
+        assert!(amazon.price_multipliers[2] > amazon.price_multipliers[1]);
+        assert!(amazon.price_rises_ahead());
+        assert!(amazon.recommendation(game.day).contains("rising"));
+    }
+
+    #[test]
+    fn test_forecast_event_window() {
+        // Events only shape the days they are still running
+        let mut game = forecast_game(10);
+        game.market_conditions.active_events.push(MarketEvent::new("Sale", "Half price", Some("Target"), 0.5, 2.0, 3));
+        let target = retailer_forecast(&game, "Target");
+        assert!(target.daily_orders[1] > target.daily_orders[2]);
+        assert_eq!(target.daily_orders[2], target.daily_orders[3]);
+        assert!((target.price_multipliers[1] - 1.0).abs() < 0.01);
+        assert!(target.price_multipliers[2] > 1.5); // The sale is over, prices return to normal
+    }
+
+    #[test]
+    fn test_forecast_follows_order_history() {
+        // Order history moves the base rate toward what customers actually ask for
+        let mut game = forecast_game(10);
+        let prior = game.base_order_rate("Amazon");
+        for day in 1..=7 {
+            let mut market_day = MarketDay { day, ..Default::default() };
+            market_day.ordered.insert("Amazon".to_string(), 20);
+            game.analytics.market_history.push(market_day);
         }
+        assert!(game.base_order_rate("Amazon") > prior * 2.0);
     }
 
     #[test]
-    fn test_achievement_system() {
-        let mut game_data = GameData::new();
-        let initial_achievements = game_data.achievements.total_unlocked;
-        
-        // Test cash achievements
-        game_data.cash = 10000;
-        game_data.achievements.check_cash_achievements(game_data.cash, game_data.day, &mut game_data.recent_activities);
-        assert!(game_data.achievements.total_unlocked > initial_achievements);
-        
-        // Test order achievements
-        game_data.analytics.orders_completed = 1;
-        game_data.achievements.check_order_achievements(game_data.analytics.orders_completed, game_data.reputation, game_data.day, &mut game_data.recent_activities);
-        
-        // Test reputation achievement
-        game_data.reputation = 5;
-        game_data.achievements.check_order_achievements(0, game_data.reputation, game_data.day, &mut game_data.recent_activities);
-        
-        // Check if any achievement unlocked for reputation
-        let legendary_unlocked = game_data.achievements.achievements.iter()
-            .find(|a| a.achievement_type == AchievementType::LegendaryStatus)
-            .map(|a| a.unlocked)
-            .unwrap_or(false);
-        assert!(legendary_unlocked);
-        
-        // Test inventory achievements
-        let total_cards: u32 = game_data.inventory.iter().map(|item| item.quantity).sum();
-        assert!(total_cards > 0); // Should have sample inventory
-        
-        // Test progress tracking
-        let in_progress = game_data.achievements.get_in_progress_achievements();
-        assert!(!in_progress.is_empty()); // Should have some progress
-        
-        // Test unlocked achievements
-        let unlocked = game_data.achievements.get_unlocked_achievements();
-        assert!(!unlocked.is_empty()); // Should have unlocked some
-        
-        // Test total rewards calculation
-        let total_rewards = game_data.achievements.calculate_total_rewards();
-        assert!(total_rewards > 0); // Should have earned some rewards
+    fn test_forecast_prior_matches_order_generation() {
+        // Before any history the forecast expects what order generation actually produces
+        let mut game = quiet_game(25);
+        let orders = 2000;
+        for _ in 0..orders {
+            game.generate_random_order();
+        }
+        let ordered = &game.analytics.market_today.ordered;
+        let per_order = |retailer: &str| game.expected_cards_per_order(retailer);
+        let expected: f32 = RETAILERS.iter().map(|retailer| per_order(retailer.id)).sum();
+        let actual = ordered.values().sum::<u32>() as f32 / orders as f32;
+        assert!((actual - expected).abs() < expected * 0.1, "expected {expected}, generated {actual}");
+        assert!((game.base_order_rate("Amazon") - game.expected_order_cards("Amazon")).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_forecast_suggested_purchase() {
+        // Stock on hand reduces the suggestion, but cards expiring early only cover orders before they lapse
+        let mut game = forecast_game(10);
+        for day in 1..=7 {
+            let mut market_day = MarketDay { day, ..Default::default() };
+            market_day.ordered.insert("Amazon".to_string(), 20);
+            game.analytics.market_history.push(market_day);
+        }
+        let needed = retailer_forecast(&game, "Amazon").suggested_purchase();
+        assert!(needed > 5);
+        game.add_to_inventory(GiftCard::new("Amazon", 25, 20, game.day + 60), 5);
+        let amazon = retailer_forecast(&game, "Amazon");
+        assert_eq!(amazon.suggested_purchase(), needed - 5);
+        assert_eq!(amazon.expiring, 0);
+
+        game.add_to_inventory(GiftCard::new("Amazon", 25, 20, game.day + 1), 50);
+        let amazon = retailer_forecast(&game, "Amazon");
+        assert_eq!(amazon.expiring, 50);
+        assert_eq!(amazon.suggested_purchase(), needed - 5);
+    }
+
+    #[test]
+    fn test_forecast_expiring_lots_share_orders() {
+        // Lots expiring together can only sell into the orders expected before that day once
+        let mut game = forecast_game(10);
+        for day in 1..=7 {
+            let mut market_day = MarketDay { day, ..Default::default() };
+            market_day.ordered.insert("Amazon".to_string(), 20);
+            game.analytics.market_history.push(market_day);
+        }
+        let before_expiry = retailer_forecast(&game, "Amazon").expected_orders(4).floor() as u32;
+        assert!(before_expiry > 0);
+        game.add_to_inventory(GiftCard::new("Amazon", 25, 20, game.day + 5), 50);
+        game.add_to_inventory(GiftCard::new("Amazon", 50, 40, game.day + 5), 50);
+        let amazon = retailer_forecast(&game, "Amazon");
+        assert_eq!(amazon.expiring, 100);
+        assert_eq!(amazon.usable_stock, before_expiry);
+
+        // A later lot only picks up the orders the earlier ones couldn't
+        game.add_to_inventory(GiftCard::new("Amazon", 10, 8, game.day + 10), 100);
+        let before_later_expiry = retailer_forecast(&game, "Amazon").expected_orders(9).floor() as u32;
+        assert_eq!(retailer_forecast(&game, "Amazon").usable_stock, before_later_expiry);
     }
 }